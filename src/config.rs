@@ -1,2 +1,197 @@
-#[derive(Debug, Default)]
-pub struct Config;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+/// サーバの設定。
+/// クライアントの `initializationOptions` や `workspace/didChangeConfiguration` から受け取る。
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Config {
+    /// lint ルールごとの重要度。キーはルールの code。
+    pub lint: HashMap<String, LintLevel>,
+    /// 保存時に satysfi を実行して得る診断の設定。
+    pub compiler: CompilerConfig,
+    /// 補完の設定。
+    pub completion: CompletionConfig,
+    /// 相互参照のキーの設定。
+    pub cross_reference: CrossReferenceConfig,
+}
+
+impl Config {
+    /// 設定の section 名。
+    pub const SECTION: &'static str = "satysfi-ls";
+
+    /// クライアントから受け取った JSON から設定を読み込む。
+    /// `{ "satysfi-ls": { ... } }` の形でも、中身だけでも受け付ける。
+    pub fn from_value(value: Value) -> Option<Config> {
+        let value = match value {
+            Value::Object(mut map) if map.contains_key(Config::SECTION) => {
+                map.remove(Config::SECTION).unwrap()
+            }
+            value => value,
+        };
+        serde_json::from_value(value).ok()
+    }
+
+    /// 設定で上書きされている場合、そのルールの重要度を返す。
+    pub fn lint_level(&self, code: &str) -> Option<LintLevel> {
+        self.lint.get(code).copied()
+    }
+}
+
+/// lint ルールの重要度。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    Off,
+    Hint,
+    Information,
+    Warning,
+    Error,
+}
+
+/// 保存時に satysfi を実行して型エラーなどを得るための設定。
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CompilerConfig {
+    /// 保存時に satysfi を実行するかどうか。
+    pub enable: bool,
+    /// 実行する satysfi のパス。
+    pub executable: String,
+    /// 追加で渡す引数。
+    pub args: Vec<String>,
+    /// 実行を打ち切るまでの秒数。
+    pub timeout: u64,
+}
+
+impl Default for CompilerConfig {
+    fn default() -> Self {
+        CompilerConfig {
+            enable: false,
+            executable: "satysfi".to_owned(),
+            args: vec![],
+            timeout: 30,
+        }
+    }
+}
+
+/// 補完の設定。
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CompletionConfig {
+    /// ファイルパスとして補完する文字列リテラルの引数。
+    pub path_arguments: Vec<PathArgument>,
+    /// 組み込みの補完候補に加える TOML ファイルのパス。
+    /// 相対パスはワークスペースのルートから、 `~/` で始まるものはホームディレクトリから辿る。
+    pub resources: Vec<String>,
+}
+
+impl CompletionConfig {
+    /// 補完候補のリソースファイルの絶対パス。
+    pub fn resource_paths(&self, workspace_root: Option<&Path>) -> Vec<PathBuf> {
+        let home = std::env::var("HOME").map(PathBuf::from).ok();
+        self.resources
+            .iter()
+            .filter_map(|path| match path.strip_prefix("~/") {
+                Some(rest) => home.as_ref().map(|home| home.join(rest)),
+                None if Path::new(path).is_absolute() => Some(PathBuf::from(path)),
+                None => workspace_root.map(|root| root.join(path)),
+            })
+            .collect()
+    }
+}
+
+impl Default for CompletionConfig {
+    fn default() -> Self {
+        let image = || vec!["jpg".to_owned(), "jpeg".to_owned(), "png".to_owned()];
+        CompletionConfig {
+            path_arguments: vec![
+                PathArgument {
+                    command: "load-image".to_owned(),
+                    position: 1,
+                    extensions: image(),
+                },
+                PathArgument {
+                    command: "load-pdf-image".to_owned(),
+                    position: 1,
+                    extensions: vec!["pdf".to_owned()],
+                },
+                PathArgument {
+                    command: "\\insert-image".to_owned(),
+                    position: 2,
+                    extensions: default_path_extensions(),
+                },
+            ],
+            resources: vec![],
+        }
+    }
+}
+
+/// ファイルパスを受け取る関数・コマンドの引数。
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PathArgument {
+    /// 関数名またはコマンド名。コマンドの場合は `\insert-image` のように先頭の記号も含める。
+    pub command: String,
+    /// 何番目の引数がファイルパスであるか。 1 から数える。
+    pub position: usize,
+    /// 補完するファイルの拡張子。
+    #[serde(default = "default_path_extensions")]
+    pub extensions: Vec<String>,
+}
+
+fn default_path_extensions() -> Vec<String> {
+    ["jpg", "jpeg", "png", "pdf"]
+        .iter()
+        .map(|&ext| ext.to_owned())
+        .collect()
+}
+
+/// 相互参照のキーを定義・参照する引数の設定。
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CrossReferenceConfig {
+    /// ``+section?:(`sec:intro`)`` のように、キーを定義する引数。
+    pub definitions: Vec<CommandArgument>,
+    /// ``\ref(`sec:intro`);`` のように、キーを参照する引数。
+    pub references: Vec<CommandArgument>,
+}
+
+impl Default for CrossReferenceConfig {
+    fn default() -> Self {
+        let args = |commands: &[&str]| {
+            commands
+                .iter()
+                .map(|&command| CommandArgument {
+                    command: command.to_owned(),
+                    position: 1,
+                })
+                .collect()
+        };
+        CrossReferenceConfig {
+            definitions: args(&["+chapter", "+section", "+subsection", "+subsubsection"]),
+            references: args(&["\\ref", "\\ref-page"]),
+        }
+    }
+}
+
+/// 関数・コマンドの何番目の引数であるか。
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandArgument {
+    /// 関数名またはコマンド名。コマンドの場合は先頭の記号も含める。
+    pub command: String,
+    /// 何番目の引数であるか。 1 から数える。 `?:` の付いたオプション引数も数える。
+    pub position: usize,
+}
+
+impl CommandArgument {
+    /// 関数・コマンドの名前と引数の位置がこれに一致するか。
+    pub fn matches(&self, command: &str, position: usize) -> bool {
+        self.command == command && self.position == position
+    }
+}
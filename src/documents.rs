@@ -6,14 +6,28 @@ use std::{
 use anyhow::{anyhow, Result};
 use itertools::Itertools;
 use log::info;
-use lspower::lsp::{Position, Url};
+use lspower::lsp::{Position, Range, Url};
 use satysfi_parser::{
     grammar::{type_block_cmd, type_inline_cmd, type_math_cmd},
     structure::{Header, LetRecInner, Program, ProgramText, Signature, Statement, TypeInner},
     Cst, CstText, LineCol, Rule, Span,
 };
 
-use crate::util::{ConvertPosition, UrlPos};
+use crate::util::{line_start, offset_to_position, ConvertPosition, UrlPos};
+
+use self::cross_references::{literal_arguments, LiteralArgument};
+pub use self::cross_references::{CrossReferences, KeyKind, KeyOccurrence};
+pub use self::fonts::{
+    font_literals, library_roots, unclosed_font_literal, FontIndex, FontKind, FontNames,
+};
+pub use self::packages::{package_dirs, PackageIndex};
+pub use self::recovery::{SyntaxError, DUMMY_RULES};
+
+mod arguments;
+mod cross_references;
+mod fonts;
+mod packages;
+mod recovery;
 
 /// オンメモリで取り扱うデータをまとめたデータ構造。
 #[derive(Debug, Default)]
@@ -146,17 +160,29 @@ pub enum DocumentData {
     NotParsed {
         /// テキスト本体。
         text: String,
-        /// エラー箇所。
-        linecol: LineCol,
-        /// エラー箇所にて期待するパターン（終端記号）列。
-        expect: Vec<&'static str>,
+        /// エラーからの回復を行いながら見つけた構文エラー。
+        errors: Vec<SyntaxError>,
     },
 }
 
 impl DocumentData {
     /// テキストから新たな DocumentData を作成する。
     pub fn new(text: &str, url: &Url) -> DocumentData {
-        match ProgramText::parse(text) {
+        DocumentData::from_parsed(recovery::parse_with_recovery(text), text, url)
+    }
+
+    /// テキストから新たな DocumentData を作成する。
+    /// パースに失敗した場合、エラーからの回復は行わず、最初の構文エラーのみを持つ。
+    pub fn new_without_recovery(text: &str, url: &Url) -> DocumentData {
+        DocumentData::from_parsed(recovery::parse_without_recovery(text), text, url)
+    }
+
+    fn from_parsed(
+        parsed: std::result::Result<ProgramText, Vec<SyntaxError>>,
+        text: &str,
+        url: &Url,
+    ) -> DocumentData {
+        match parsed {
             Ok(program_text) => {
                 let environment = Environment::from_program(&program_text, &url);
                 DocumentData::Parsed {
@@ -164,13 +190,9 @@ impl DocumentData {
                     environment,
                 }
             }
-            Err((linecol, expect)) => {
+            Err(errors) => {
                 let text = text.to_owned();
-                DocumentData::NotParsed {
-                    text,
-                    linecol,
-                    expect,
-                }
+                DocumentData::NotParsed { text, errors }
             }
         }
     }
@@ -184,6 +206,14 @@ impl DocumentData {
         }
     }
 
+    /// テキスト本体。
+    pub fn text(&self) -> &str {
+        match self {
+            DocumentData::Parsed { program_text, .. } => &program_text.text,
+            DocumentData::NotParsed { text, .. } => text,
+        }
+    }
+
     pub fn show_envs_debug(&self) {
         match self {
             DocumentData::Parsed {
@@ -252,12 +282,123 @@ impl DocumentData {
     }
 }
 
+/// パースに失敗しているファイルの最新の状態。
+/// パースに失敗している間は、最後にパースに成功したときの解析結果を IDE 機能に用い、
+/// 位置はその後の編集を考慮して変換する。
+#[derive(Debug)]
+pub struct Unparsed {
+    /// パースに失敗した最新のデータ。
+    pub doc_data: DocumentData,
+    /// 最後にパースに成功したときのテキストから最新のテキストへの差分。
+    shift: TextShift,
+}
+
+impl Unparsed {
+    /// 最後にパースに成功したときのテキストと比較して作成する。
+    pub fn new(doc_data: DocumentData, analyzed: &ProgramText) -> Unparsed {
+        let shift = TextShift::between(&analyzed.text, doc_data.text());
+        Unparsed { doc_data, shift }
+    }
+
+    /// 最新のテキスト上の位置を解析結果のテキスト上の位置に変換する。
+    /// 編集された箇所の内部にある位置は変換できない。
+    pub fn to_analyzed(&self, analyzed: &ProgramText, pos: &Position) -> Option<Position> {
+        let offset = self.doc_data.text().from_position(pos)?;
+        analyzed.get_position(self.shift.backward(offset)?)
+    }
+
+    /// 解析結果のテキスト上の範囲を最新のテキスト上の範囲に変換する。
+    /// 編集された箇所と重なる範囲は変換できない。
+    pub fn to_current(&self, analyzed: &ProgramText, range: &Range) -> Option<Range> {
+        let text = self.doc_data.text();
+        let start = self.shift.forward(analyzed.from_position(&range.start)?)?;
+        let end = self.shift.forward(analyzed.from_position(&range.end)?)?;
+        Some(Range {
+            start: offset_to_position(text, start),
+            end: offset_to_position(text, end),
+        })
+    }
+
+    /// 最新のテキストのうち、 line 行目の内容。
+    pub fn line(&self, line: u32) -> Option<&str> {
+        self.doc_data.text().split('\n').nth(line as usize)
+    }
+}
+
+/// 二つのテキストの差分を一つの置換として表したもの。
+/// 古いテキストの `start..old_end` が新しいテキストの `start..new_end` に置き換わっている。
+#[derive(Debug, Clone, Copy)]
+struct TextShift {
+    start: usize,
+    old_end: usize,
+    new_end: usize,
+}
+
+impl TextShift {
+    /// 共通の接頭辞・接尾辞を除いた部分を差分とする。
+    fn between(old: &str, new: &str) -> TextShift {
+        let mut prefix = old
+            .bytes()
+            .zip(new.bytes())
+            .take_while(|(a, b)| a == b)
+            .count();
+        while !old.is_char_boundary(prefix) || !new.is_char_boundary(prefix) {
+            prefix -= 1;
+        }
+        let max_suffix = old.len().min(new.len()) - prefix;
+        let mut suffix = old
+            .bytes()
+            .rev()
+            .zip(new.bytes().rev())
+            .take(max_suffix)
+            .take_while(|(a, b)| a == b)
+            .count();
+        while !old.is_char_boundary(old.len() - suffix) || !new.is_char_boundary(new.len() - suffix)
+        {
+            suffix -= 1;
+        }
+        TextShift {
+            start: prefix,
+            old_end: old.len() - suffix,
+            new_end: new.len() - suffix,
+        }
+    }
+
+    /// 古いテキスト上の位置を新しいテキスト上の位置に変換する。
+    /// 置き換えられた範囲の内部にある位置は変換できない。
+    fn forward(&self, pos: usize) -> Option<usize> {
+        if pos <= self.start {
+            Some(pos)
+        } else if pos >= self.old_end {
+            Some(pos - self.old_end + self.new_end)
+        } else {
+            None
+        }
+    }
+
+    /// 新しいテキスト上の位置を古いテキスト上の位置に変換する。
+    /// 置き換えられた範囲の内部にある位置は変換できない。
+    fn backward(&self, pos: usize) -> Option<usize> {
+        if pos <= self.start {
+            Some(pos)
+        } else if pos >= self.new_end {
+            Some(pos - self.new_end + self.old_end)
+        } else {
+            None
+        }
+    }
+}
+
 /// 変数やコマンドに関する情報。
 #[derive(Debug, Default)]
 pub struct Environment {
     dependencies: Vec<Dependency>,
     components: Vec<Component>,
     open_modules: Vec<OpenModule>,
+    /// このファイルでの、各変数名・コマンド名・module 名の出現回数。
+    usage: HashMap<String, usize>,
+    /// 関数・コマンドの引数として書かれた文字列リテラル。
+    literal_arguments: Vec<LiteralArgument>,
 }
 
 impl Environment {
@@ -277,10 +418,14 @@ impl Environment {
                 let dependencies = Dependency::from_header(&header, program_text, url);
                 let components = Component::from_preamble(&preamble, program_text, url);
                 let open_modules = OpenModule::from_preamble(&preamble, program_text, url);
+                let usage = count_usage(program_text);
+                let literal_arguments = literal_arguments(program_text);
                 Environment {
                     dependencies,
                     components,
                     open_modules,
+                    usage,
+                    literal_arguments,
                 }
             }
             Err(_) => Environment::default(),
@@ -292,6 +437,37 @@ impl Environment {
         self.dependencies.as_slice()
     }
 
+    /// Get a reference to the environment's components.
+    pub fn components(&self) -> &[Component] {
+        self.components.as_slice()
+    }
+
+    /// このファイルでの、各変数名・コマンド名・module 名の出現回数。
+    pub fn usage(&self) -> &HashMap<String, usize> {
+        &self.usage
+    }
+
+    /// 関数・コマンドの引数として書かれた文字列リテラル。
+    pub fn literal_arguments(&self) -> &[LiteralArgument] {
+        &self.literal_arguments
+    }
+
+    /// pos で定義されているコンポーネント。 module の中で定義されているものも含む。
+    pub fn component_at(&self, pos: usize) -> Option<&Component> {
+        fn find(components: &[Component], pos: usize) -> Option<&Component> {
+            components.iter().find_map(|c| {
+                if c.pos_definition.start == pos {
+                    return Some(c);
+                }
+                match &c.body {
+                    ComponentBody::Module { components } => find(components, pos),
+                    _ => None,
+                }
+            })
+        }
+        find(&self.components, pos)
+    }
+
     pub fn modules(&self) -> Vec<&Component> {
         self.components
             .iter()
@@ -529,8 +705,14 @@ pub struct Dependency {
 
 impl Dependency {
     fn from_header(headers: &[&Header], program_text: &ProgramText, url: &Url) -> Vec<Dependency> {
-        let require_packages = headers.iter().map(|header| &header.name);
-        let import_packages = headers.iter().map(|header| &header.name);
+        let require_packages = headers
+            .iter()
+            .filter(|header| header_kind(header, program_text) == DependencyKind::Require)
+            .map(|header| &header.name);
+        let import_packages = headers
+            .iter()
+            .filter(|header| header_kind(header, program_text) == DependencyKind::Import)
+            .map(|header| &header.name);
 
         let mut deps = vec![];
         let home_path = std::env::var("HOME").map(PathBuf::from).ok();
@@ -591,6 +773,18 @@ impl Dependency {
     }
 }
 
+/// ヘッダが `@require:` と `@import:` のどちらであるか。
+fn header_kind(header: &Header, program_text: &ProgramText) -> DependencyKind {
+    let text = program_text.text.as_str();
+    let start = header.name.span.start;
+    let prefix = &text[line_start(text, start)..start];
+    if prefix.contains("@import") {
+        DependencyKind::Import
+    } else {
+        DependencyKind::Require
+    }
+}
+
 /// 以下の4箇所から探す。
 /// - $PARENT_PATH/.satysfi/{kind}/packages/a.{ext}
 /// - $HOME/.satysfi/{kind}/packages/a.{ext}
@@ -603,13 +797,8 @@ fn require_candidate_paths(
     parent: Option<&Path>,
     home: Option<&Path>,
 ) -> Vec<PathBuf> {
-    let usr_local_share = Some(PathBuf::from("/usr/local/share/satysfi"));
-    let usr_share = Some(PathBuf::from("/usr/share/satysfi"));
-    let home = home.map(|p| p.join(".satysfi"));
-    let parent = parent.map(|p| p.join(".satysfi"));
-    [parent, home, usr_local_share, usr_share]
-        .iter()
-        .filter_map(|x| x.clone())
+    library_root_candidates(parent, home)
+        .into_iter()
         .map(|path| {
             vec![
                 path.join(format!("local/packages/{}.satyh", pkgname)),
@@ -622,6 +811,18 @@ fn require_candidate_paths(
 }
 
 pub fn require_candidate_dirs(parent: Option<&Path>, home: Option<&Path>) -> Vec<PathBuf> {
+    library_root_candidates(parent, home)
+        .into_iter()
+        .map(|path| vec![path.join("local/packages"), path.join("dist/packages")])
+        .concat()
+}
+
+/// SATySFi のライブラリのルートディレクトリの候補。先にあるものほど優先される。
+/// - $PARENT_PATH/.satysfi
+/// - $HOME/.satysfi
+/// - /usr/local/share/satysfi
+/// - /usr/share/satysfi
+pub fn library_root_candidates(parent: Option<&Path>, home: Option<&Path>) -> Vec<PathBuf> {
     let usr_local_share = Some(PathBuf::from("/usr/local/share/satysfi"));
     let usr_share = Some(PathBuf::from("/usr/share/satysfi"));
     let home = home.map(|p| p.join(".satysfi"));
@@ -629,11 +830,10 @@ pub fn require_candidate_dirs(parent: Option<&Path>, home: Option<&Path>) -> Vec
     [parent, home, usr_local_share, usr_share]
         .iter()
         .filter_map(|x| x.clone())
-        .map(|path| vec![path.join("local/packages"), path.join("dist/packages")])
-        .concat()
+        .collect()
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DependencyKind {
     Require,
     Import,
@@ -909,6 +1109,7 @@ impl Component {
                         (Visibility::Public, None, None)
                     }
                 };
+                let glyph = math_char_glyph(program_text.get_text(expr));
                 let body = if let Some(signature) = signature {
                     let text = program_text.get_text(signature);
                     let csts = type_math_cmd(text).ok().unwrap().inner;
@@ -918,11 +1119,13 @@ impl Component {
                             .into_iter()
                             .map(|cst| text[cst.span.start..cst.span.end].to_owned())
                             .collect_vec(),
+                        glyph,
                     }
                 } else {
                     ComponentBody::MathCmd {
                         type_declaration: None,
                         type_args: vec![],
+                        glyph,
                     }
                 };
                 vec![Component {
@@ -1090,6 +1293,78 @@ impl Component {
             url: url.clone(),
         }
     }
+
+    /// 同じファイル内で、このコンポーネントを参照している箇所の一覧。
+    /// 名前とスコープのみで判定するため、 shadowing は考慮しない。
+    pub fn references<'a>(&self, program_text: &'a ProgramText) -> Vec<&'a Cst> {
+        program_text
+            .cst
+            .pickup(self.name_rule())
+            .into_iter()
+            .filter(|cst| {
+                self.scope.includes(cst.span.start) && cst.span.start != self.pos_definition.start
+            })
+            .filter(|cst| program_text.get_text(cst) == self.name)
+            .collect()
+    }
+
+    /// このコンポーネントの名前を表す構文規則。
+    pub fn name_rule(&self) -> Rule {
+        match self.body {
+            ComponentBody::Module { .. } => Rule::module_name,
+            ComponentBody::Variable { .. } => Rule::var,
+            ComponentBody::Type => Rule::type_name,
+            ComponentBody::Variant { .. } => Rule::variant_name,
+            ComponentBody::InlineCmd { .. } => Rule::inline_cmd_name,
+            ComponentBody::BlockCmd { .. } => Rule::block_cmd_name,
+            ComponentBody::MathCmd { .. } => Rule::math_cmd_name,
+        }
+    }
+}
+
+/// 各変数名・コマンド名・module 名の出現回数を数える。
+fn count_usage(program_text: &ProgramText) -> HashMap<String, usize> {
+    let rules = [
+        Rule::var,
+        Rule::module_name,
+        Rule::inline_cmd_name,
+        Rule::block_cmd_name,
+        Rule::math_cmd_name,
+    ];
+    let mut usage = HashMap::new();
+    for &rule in &rules {
+        for cst in program_text.cst.pickup(rule) {
+            *usage
+                .entry(program_text.get_text(cst).to_owned())
+                .or_insert(0) += 1;
+        }
+    }
+    usage
+}
+
+/// ``math-char MathOrd `α` `` のように `math-char` 系の関数で文字を出力するだけの
+/// 数式コマンドの定義式から、その文字を取り出す。
+fn math_char_glyph(expr: &str) -> Option<String> {
+    let mut tokens = expr.split_whitespace();
+    let func = tokens.next()?;
+    if !(func.starts_with("math-") && func.contains("char")) {
+        return None;
+    }
+    // 関数名と文字列リテラルの間には数式クラスなどの単純な引数のみが来るものとする
+    let open = expr.find('`')?;
+    if expr[..open].contains(|c| matches!(c, '(' | '{' | '$' | '"')) {
+        return None;
+    }
+    let rest = &expr[open..];
+    let quote_len = rest.len() - rest.trim_start_matches('`').len();
+    let quote = &rest[..quote_len];
+    let content = &rest[quote_len..];
+    let glyph = content[..content.find(quote)?].trim();
+    if glyph.is_empty() || glyph.chars().count() > 4 {
+        None
+    } else {
+        Some(glyph.to_owned())
+    }
 }
 
 #[derive(Debug)]
@@ -1120,6 +1395,8 @@ pub enum ComponentBody {
         /// signature に型情報がある場合、その場所。
         type_declaration: Option<Span>,
         type_args: Vec<String>,
+        /// `math-char` で文字を出力するだけのコマンドである場合、その文字。
+        glyph: Option<String>,
     },
 }
 
@@ -1179,3 +1456,54 @@ impl OpenModule {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::TextShift;
+
+    #[test]
+    fn shift_replacement() {
+        // "1" の後に "00" を挿入したものとして扱われる
+        let shift = TextShift::between("let x = 1 in x", "let x = 100 in x");
+        assert_eq!(shift.forward(4), Some(4));
+        assert_eq!(shift.forward(8), Some(8));
+        assert_eq!(shift.forward(9), Some(9));
+        assert_eq!(shift.forward(10), Some(12));
+        assert_eq!(shift.forward(13), Some(15));
+        assert_eq!(shift.backward(4), Some(4));
+        assert_eq!(shift.backward(9), Some(9));
+        assert_eq!(shift.backward(11), Some(9));
+        assert_eq!(shift.backward(15), Some(13));
+    }
+
+    #[test]
+    fn shift_inside_changed_region() {
+        let shift = TextShift::between("let x = 1 in x", "let x = 100 in x");
+        assert_eq!(shift.backward(10), None);
+        let shift = TextShift::between("let x = 100 in x", "let x = 1 in x");
+        assert_eq!(shift.forward(10), None);
+    }
+
+    #[test]
+    fn shift_insertion_and_deletion() {
+        let shift = TextShift::between("ab", "aXYb");
+        assert_eq!(shift.forward(1), Some(1));
+        assert_eq!(shift.forward(2), Some(4));
+        assert_eq!(shift.backward(2), None);
+        assert_eq!(shift.backward(3), Some(1));
+        let shift = TextShift::between("aXYb", "ab");
+        assert_eq!(shift.forward(2), None);
+        assert_eq!(shift.forward(3), Some(1));
+        assert_eq!(shift.backward(1), Some(1));
+        assert_eq!(shift.backward(2), Some(4));
+    }
+
+    #[test]
+    fn shift_keeps_char_boundary() {
+        // 「あ」と「い」は先頭 2 バイトが共通だが、文字の途中で区切らない
+        let shift = TextShift::between("xあy", "xいy");
+        assert_eq!(shift.forward(1), Some(1));
+        assert_eq!(shift.backward(2), None);
+        assert_eq!(shift.backward(4), Some(4));
+    }
+}
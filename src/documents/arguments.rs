@@ -0,0 +1,195 @@
+//! 関数・コマンドの引数として書かれた式を CST の上で読み取る。
+
+use satysfi_parser::{structure::ProgramText, Cst, Span};
+
+use crate::util::{group_start, KEYWORDS};
+
+/// 文字列リテラルの中身の位置とテキスト。
+/// ``#`foo`#`` のような前後の `#` や、複数の `` ` `` による括りも扱う。
+pub(super) fn string_content<'a>(
+    program_text: &'a ProgramText,
+    literal: &Cst,
+) -> Option<(Span, &'a str)> {
+    let text = program_text.get_text(literal);
+    let quoted = text.trim_start_matches('#');
+    let quote_len = quoted.len() - quoted.trim_start_matches('`').len();
+    let quoted = quoted.trim_end_matches('#');
+    let content = quoted.get(quote_len..quoted.len().checked_sub(quote_len)?)?;
+    let start = literal.span.start + (text.len() - text.trim_start_matches('#').len()) + quote_len;
+    let span = Span {
+        start,
+        end: start + content.len(),
+    };
+    Some((span, content))
+}
+
+/// node が括弧を除いてそのまま引数として渡されている、関数・コマンドの適用について、
+/// 関数・コマンドの名前（書かれたままのもの）と、それが何番目の引数であるか（1 から数える）。
+/// `?:` の付いたオプション引数も、 `?*` で省略されたオプション引数も一つと数える。
+/// ``f (`a`, 1)`` の `` `a` `` のように、引数の一部であるものは対象としない。
+pub(super) fn enclosing_application<'a>(
+    program_text: &'a ProgramText,
+    node: &'a Cst,
+) -> Option<(&'a str, usize)> {
+    let (parent, child) = enclosing_node(program_text, node)?;
+    application_argument(program_text, parent, child)
+}
+
+/// node を括弧などで包んだだけのノードを除いて、 node を含む最も内側のノード。
+/// そのノードの子のうち、 node を含むものと組にして返す。
+pub(super) fn enclosing_node<'a>(
+    program_text: &'a ProgramText,
+    node: &'a Cst,
+) -> Option<(&'a Cst, &'a Cst)> {
+    let core = argument_core(program_text.get_text(node));
+    let mut child = node;
+    loop {
+        let parent = program_text.cst.get_parent(child)?;
+        if argument_core(program_text.get_text(parent)) != core {
+            return Some((parent, child));
+        }
+        child = parent;
+    }
+}
+
+/// app が関数・コマンドの適用であって arg がその引数であれば、関数・コマンドの名前と引数の位置。
+fn application_argument<'a>(
+    program_text: &'a ProgramText,
+    app: &Cst,
+    arg: &Cst,
+) -> Option<(&'a str, usize)> {
+    let text = program_text.text.as_str();
+    let (head, args) = app.inner.split_first()?;
+    let callee = program_text.get_text(head).trim();
+    let is_name = !callee.is_empty()
+        && callee
+            .chars()
+            .all(|c| c.is_alphanumeric() || "-_.\\+#'".contains(c));
+    if !is_name
+        || KEYWORDS.contains(&callee)
+        || !program_text.get_text(app).trim_start().starts_with(callee)
+    {
+        return None;
+    }
+
+    let mut position = 0;
+    let mut pos = head.span.end;
+    for child in args {
+        // 二項演算子の左辺を関数とみなさないようにする
+        let is_operator = program_text
+            .get_text(child)
+            .trim()
+            .chars()
+            .all(|c| "+-*/^=<>|&!:@'".contains(c));
+        if is_operator {
+            return None;
+        }
+        position += omitted_arguments(&text[pos..child.span.start])? + 1;
+        if child.span == arg.span {
+            return Some((callee, position));
+        }
+        pos = child.span.end;
+    }
+    None
+}
+
+/// 引数の間にあるテキストのうち、 `?*` で省略されたオプション引数の数。
+/// 括弧、 `?:`, `?*` 以外のものがあれば関数適用ではないので None。
+fn omitted_arguments(gap: &str) -> Option<usize> {
+    let mut count = 0;
+    let mut rest = gap.trim_start();
+    while !rest.is_empty() {
+        rest = if let Some(rest) = rest.strip_prefix("?*") {
+            count += 1;
+            rest
+        } else if let Some(rest) = rest.strip_prefix("?:") {
+            rest
+        } else {
+            rest.strip_prefix(|c| c == '(' || c == ')')?
+        }
+        .trim_start();
+    }
+    Some(count)
+}
+
+/// 前後の空白、先頭の `?:`、全体を囲む括弧を取り除いたもの。
+fn argument_core(text: &str) -> &str {
+    let text = text.trim();
+    let mut text = text.strip_prefix("?:").unwrap_or(text).trim_start();
+    while text.starts_with('(') && text.ends_with(')') && group_start(text) == Some(0) {
+        text = text[1..text.len() - 1].trim();
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use satysfi_parser::{structure::ProgramText, Rule};
+
+    use super::{enclosing_application, string_content};
+
+    /// 各文字列リテラルの中身と、それを受け取る関数・コマンドの名前と位置。
+    fn literal_arguments(text: &str) -> Vec<(String, Option<(String, usize)>)> {
+        let program_text = ProgramText::parse(text).unwrap();
+        program_text
+            .cst
+            .pickup(Rule::const_string)
+            .into_iter()
+            .map(|literal| {
+                let (_, content) = string_content(&program_text, literal).unwrap();
+                let application = enclosing_application(&program_text, literal)
+                    .map(|(callee, position)| (callee.to_owned(), position));
+                (content.to_owned(), application)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn arguments_of_function() {
+        let text = "let x = f `a` (`b`) ?* (`c`)\n";
+        assert_eq!(
+            literal_arguments(text),
+            vec![
+                ("a".to_owned(), Some(("f".to_owned(), 1))),
+                ("b".to_owned(), Some(("f".to_owned(), 2))),
+                ("c".to_owned(), Some(("f".to_owned(), 4))),
+            ]
+        );
+    }
+
+    #[test]
+    fn spanning_lines() {
+        let text = "let x =\n  M.load\n    `a`\n";
+        assert_eq!(
+            literal_arguments(text),
+            vec![("a".to_owned(), Some(("M.load".to_owned(), 1)))]
+        );
+    }
+
+    #[test]
+    fn part_of_argument() {
+        let text = "let x = f (`a`, 1)\nlet y = `b` ^ `c`\n";
+        let applications: Vec<_> = literal_arguments(text)
+            .into_iter()
+            .map(|(_, application)| application)
+            .collect();
+        assert_eq!(applications, vec![None, None, None]);
+    }
+
+    #[test]
+    fn content_of_literal() {
+        let text = "let x = ``a`b``\nlet y = #`c `#\n";
+        let program_text = ProgramText::parse(text).unwrap();
+        let contents: Vec<_> = program_text
+            .cst
+            .pickup(Rule::const_string)
+            .into_iter()
+            .map(|literal| {
+                let (span, content) = string_content(&program_text, literal).unwrap();
+                assert_eq!(&text[span.start..span.end], content);
+                content.to_owned()
+            })
+            .collect();
+        assert_eq!(contents, vec!["a`b", "c "]);
+    }
+}
@@ -0,0 +1,238 @@
+//! 相互参照のキー。
+//!
+//! ``+section?:(`sec:intro`)`` のようにキーを定義する引数と、
+//! ``\ref(`sec:intro`);`` のようにキーを参照する引数の文字列リテラルを、
+//! 文書を構成するすべてのファイルから集める。どの引数を対象とするかは設定で与える。
+//! 引数として書かれた文字列リテラルはパースしたときにファイルごとに取り出しておき、
+//! 設定に従って分類するのみとする。
+
+use std::collections::HashMap;
+
+use lspower::lsp::Url;
+use satysfi_parser::{structure::ProgramText, Rule, Span};
+
+use super::arguments::{enclosing_application, string_content};
+use super::{DependencyKind, DocumentCache, DocumentData};
+use crate::{
+    config::{CommandArgument, CrossReferenceConfig},
+    util::unqualified,
+};
+
+/// キーの現れ方。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyKind {
+    /// キーを定義している。
+    Definition,
+    /// キーを参照している。
+    Reference,
+}
+
+/// ファイル中に現れるキー。
+#[derive(Debug, Clone)]
+pub struct KeyOccurrence {
+    pub key: String,
+    pub kind: KeyKind,
+    /// 文字列リテラルの中身の位置。
+    pub span: Span,
+}
+
+/// 関数・コマンドの引数として書かれた文字列リテラル。
+#[derive(Debug, Clone)]
+pub struct LiteralArgument {
+    /// 受け取る関数・コマンドの、 module の修飾を除いた名前。
+    callee: String,
+    /// 何番目の引数であるか（1 から数える）。
+    position: usize,
+    /// 文字列リテラルの中身。
+    content: String,
+    /// 文字列リテラルの中身の位置。
+    span: Span,
+}
+
+/// あるファイルから見た相互参照のキー。
+#[derive(Debug, Default)]
+pub struct CrossReferences {
+    /// そのファイルに現れるキー。
+    pub occurrences: Vec<KeyOccurrence>,
+    /// 文書全体で定義されているキーと、その定義の場所。
+    pub definitions: HashMap<String, Vec<(Url, Span)>>,
+    /// 文書を構成するファイルをすべて辿れたか。
+    /// `.satyh` ファイルを単独で開いている場合など、他のファイルで定義されているキーが分からなければ false。
+    pub complete: bool,
+}
+
+impl CrossReferences {
+    /// 文書全体で定義されているキー。
+    pub fn keys(&self) -> Vec<&str> {
+        let mut keys: Vec<&str> = self.definitions.keys().map(String::as_str).collect();
+        keys.sort_unstable();
+        keys
+    }
+
+    /// 指定した位置にあるキー。
+    pub fn occurrence_at(&self, pos: usize) -> Option<&KeyOccurrence> {
+        self.occurrences
+            .iter()
+            .find(|occurrence| occurrence.span.start <= pos && pos <= occurrence.span.end)
+    }
+}
+
+impl DocumentCache {
+    /// そのファイルを含む文書としてコンパイルすべき `.saty` ファイル。
+    /// `.satyh` ファイルの場合、それに依存している開いた `.saty` ファイルを探す。
+    pub fn document_root(&self, url: &Url) -> Option<Url> {
+        let is_saty = |url: &Url| url.path().ends_with(".saty");
+        if is_saty(url) {
+            return Some(url.clone());
+        }
+        self.0
+            .iter()
+            .filter(|(root, _)| is_saty(root))
+            .find(|(_, doc_data)| match doc_data {
+                DocumentData::Parsed { environment, .. } => self
+                    .get_dependencies_recursive(environment.dependencies())
+                    .iter()
+                    .any(|dep| dep.url.as_ref() == Some(url)),
+                DocumentData::NotParsed { .. } => false,
+            })
+            .map(|(root, _)| root.clone())
+    }
+
+    /// そのファイルから見た相互参照のキー。
+    /// 文書の根となる `.saty` ファイルと、そこから `@import:` されているファイルを文書全体とする。
+    pub fn cross_references(&self, url: &Url, config: &CrossReferenceConfig) -> CrossReferences {
+        let root = self.document_root(url);
+        let mut urls = vec![root.clone().unwrap_or_else(|| url.clone())];
+        if let Some(DocumentData::Parsed { environment, .. }) =
+            urls.first().and_then(|u| self.get(u))
+        {
+            urls.extend(
+                self.get_dependencies_recursive(environment.dependencies())
+                    .into_iter()
+                    .filter(|dep| dep.kind == DependencyKind::Import)
+                    .filter_map(|dep| dep.url.clone()),
+            );
+        }
+        if !urls.contains(url) {
+            urls.push(url.clone());
+        }
+
+        let mut references = CrossReferences {
+            complete: root.is_some(),
+            ..Default::default()
+        };
+        for doc_url in &urls {
+            let environment = match self.get(doc_url) {
+                Some(DocumentData::Parsed { environment, .. }) => environment,
+                _ => continue,
+            };
+            let occurrences = key_occurrences(environment.literal_arguments(), config);
+            for occurrence in &occurrences {
+                if occurrence.kind == KeyKind::Definition {
+                    references
+                        .definitions
+                        .entry(occurrence.key.clone())
+                        .or_default()
+                        .push((doc_url.clone(), occurrence.span));
+                }
+            }
+            if doc_url == url {
+                references.occurrences = occurrences;
+            }
+        }
+        references
+    }
+
+    /// そのファイルで定義されているキー。
+    pub fn defined_keys(&self, url: &Url, config: &CrossReferenceConfig) -> Vec<String> {
+        match self.get(url) {
+            Some(DocumentData::Parsed { environment, .. }) => {
+                key_occurrences(environment.literal_arguments(), config)
+                    .into_iter()
+                    .filter(|occurrence| occurrence.kind == KeyKind::Definition)
+                    .map(|occurrence| occurrence.key)
+                    .collect()
+            }
+            _ => vec![],
+        }
+    }
+}
+
+/// ファイル中で関数・コマンドの引数として書かれている文字列リテラル。
+/// リテラルとそれを受け取る関数・コマンドは、どちらも CST から求める。
+/// 複数行にわたるリテラルは対象としない。
+pub(super) fn literal_arguments(program_text: &ProgramText) -> Vec<LiteralArgument> {
+    program_text
+        .cst
+        .pickup(Rule::const_string)
+        .into_iter()
+        .filter_map(|cst| {
+            let (span, content) = string_content(program_text, cst)?;
+            if content.is_empty() || content.contains('\n') {
+                return None;
+            }
+            let (callee, position) = enclosing_application(program_text, cst)?;
+            Some(LiteralArgument {
+                callee: unqualified(callee),
+                position,
+                content: content.to_owned(),
+                span,
+            })
+        })
+        .collect()
+}
+
+/// 引数として書かれた文字列リテラルのうち、設定によりキーを定義・参照するものとされているもの。
+fn key_occurrences(
+    arguments: &[LiteralArgument],
+    config: &CrossReferenceConfig,
+) -> Vec<KeyOccurrence> {
+    arguments
+        .iter()
+        .filter_map(|arg| {
+            let matches = |args: &[CommandArgument]| {
+                args.iter().any(|a| a.matches(&arg.callee, arg.position))
+            };
+            let kind = if matches(&config.definitions) {
+                KeyKind::Definition
+            } else if matches(&config.references) {
+                KeyKind::Reference
+            } else {
+                return None;
+            };
+            Some(KeyOccurrence {
+                key: arg.content.clone(),
+                kind,
+                span: arg.span,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use satysfi_parser::structure::ProgramText;
+
+    use super::literal_arguments;
+
+    /// 各引数の関数・コマンドの名前、位置、中身。
+    fn arguments(text: &str) -> Vec<(String, usize, String)> {
+        let program_text = ProgramText::parse(text).unwrap();
+        literal_arguments(&program_text)
+            .into_iter()
+            .map(|arg| (arg.callee, arg.position, arg.content))
+            .collect()
+    }
+
+    #[test]
+    fn callee_on_another_line() {
+        let text = "let x = {\\ref\n  (`sec:a`); \\M.ref-page?*\n  (`sec:b`);}\n";
+        assert_eq!(
+            arguments(text),
+            vec![
+                ("\\ref".to_owned(), 1, "sec:a".to_owned()),
+                ("\\ref-page".to_owned(), 2, "sec:b".to_owned()),
+            ]
+        );
+    }
+}
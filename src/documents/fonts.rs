@@ -0,0 +1,353 @@
+//! フォントハッシュファイルに登録されているフォント名。
+//!
+//! `set-font` や `set-math-font` に渡すフォント名を補完・検査するのに用いる。
+//! ライブラリのルートディレクトリごとに、初めて必要になったときに読み込み、
+//! その後はハッシュファイルが更新されていれば読み込み直す。
+
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+use itertools::Itertools;
+use lspower::lsp::Url;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use satysfi_parser::{structure::ProgramText, Cst, Rule, Span};
+
+use super::arguments::{enclosing_application, enclosing_node, string_content};
+use super::library_root_candidates;
+
+/// フォント名を指定する箇所の種類。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FontKind {
+    /// `set-font` などに渡すテキスト用のフォント。
+    Text,
+    /// `set-math-font` に渡す数式用のフォント。
+    Math,
+}
+
+/// フォントハッシュファイルに登録されているフォント名。
+#[derive(Debug, Default, Clone)]
+pub struct FontNames {
+    /// `fonts.satysfi-hash` に登録されているものと、
+    /// `default-font.satysfi-hash` で用いられているもの。
+    pub text: Vec<String>,
+    /// `math-fonts.satysfi-hash` に登録されているもの。
+    pub math: Vec<String>,
+}
+
+impl FontNames {
+    /// 指定した種類のフォント名。
+    pub fn get(&self, kind: FontKind) -> &[String] {
+        match kind {
+            FontKind::Text => &self.text,
+            FontKind::Math => &self.math,
+        }
+    }
+}
+
+/// フォント名を読み込むハッシュファイル。
+const HASH_FILES: &[&str] = &[
+    "fonts.satysfi-hash",
+    "default-font.satysfi-hash",
+    "math-fonts.satysfi-hash",
+];
+
+/// 読み込んだハッシュファイルの更新時刻を調べる間隔。
+const CHECK_INTERVAL: Duration = Duration::from_secs(3);
+
+/// ライブラリのルートディレクトリごとの、そこに登録されているフォント名。
+#[derive(Debug, Default)]
+pub struct FontIndex {
+    roots: HashMap<PathBuf, LoadedFonts>,
+}
+
+/// あるルートディレクトリから読み込んだフォント名。
+#[derive(Debug)]
+struct LoadedFonts {
+    names: FontNames,
+    /// 読み込んだときの、 HASH_FILES それぞれの更新時刻。
+    modified: Vec<Option<SystemTime>>,
+    /// 最後に更新時刻を調べた時刻。
+    checked_at: Instant,
+}
+
+impl FontIndex {
+    /// まだ読み込んでいないルートディレクトリと、ハッシュファイルが更新されたルートディレクトリの
+    /// フォントハッシュファイルを読み込む。
+    /// 更新時刻は前回調べてから CHECK_INTERVAL が経つまでは調べない。
+    pub fn load(&mut self, roots: &[PathBuf]) {
+        for root in roots {
+            if let Some(loaded) = self.roots.get(root) {
+                if loaded.checked_at.elapsed() < CHECK_INTERVAL {
+                    continue;
+                }
+            }
+            let modified = hash_modified(root);
+            match self.roots.get_mut(root) {
+                Some(loaded) if loaded.modified == modified => {
+                    loaded.checked_at = Instant::now();
+                }
+                _ => {
+                    let loaded = LoadedFonts {
+                        names: load_font_names(root),
+                        modified,
+                        checked_at: Instant::now(),
+                    };
+                    self.roots.insert(root.clone(), loaded);
+                }
+            }
+        }
+    }
+
+    /// 指定したルートディレクトリに登録されているフォント名をまとめたもの。
+    pub fn fonts(&self, roots: &[PathBuf]) -> FontNames {
+        let names = roots
+            .iter()
+            .filter_map(|root| self.roots.get(root))
+            .map(|loaded| &loaded.names)
+            .collect_vec();
+        FontNames {
+            text: names
+                .iter()
+                .flat_map(|n| &n.text)
+                .unique()
+                .cloned()
+                .collect(),
+            math: names
+                .iter()
+                .flat_map(|n| &n.math)
+                .unique()
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+/// そのファイルをコンパイルするときに参照されるライブラリのルートディレクトリ。
+pub fn library_roots(url: &Url) -> Vec<PathBuf> {
+    let file_path = url.to_file_path().ok();
+    let parent_path = file_path
+        .as_ref()
+        .and_then(|p| p.parent())
+        .map(Path::to_owned);
+    let home_path = std::env::var("HOME").map(PathBuf::from).ok();
+    library_root_candidates(parent_path.as_deref(), home_path.as_deref())
+}
+
+/// HASH_FILES それぞれの更新時刻。存在しないものは None とする。
+fn hash_modified(root: &Path) -> Vec<Option<SystemTime>> {
+    HASH_FILES
+        .iter()
+        .map(|name| {
+            fs::metadata(root.join("dist/hash").join(name))
+                .and_then(|m| m.modified())
+                .ok()
+        })
+        .collect()
+}
+
+fn load_font_names(root: &Path) -> FontNames {
+    let read = |name: &str| fs::read_to_string(root.join("dist/hash").join(name)).ok();
+    let mut text = read("fonts.satysfi-hash")
+        .map(|hash| top_level_keys(&hash))
+        .unwrap_or_default();
+    if let Some(hash) = read("default-font.satysfi-hash") {
+        static FONT_NAME: Lazy<Regex> =
+            Lazy::new(|| Regex::new(r#""font-name"\s*:\s*"([^"]*)""#).unwrap());
+        text.extend(
+            FONT_NAME
+                .captures_iter(&hash)
+                .map(|caps| caps[1].to_owned()),
+        );
+    }
+    let math = read("math-fonts.satysfi-hash")
+        .map(|hash| top_level_keys(&hash))
+        .unwrap_or_default();
+    FontNames { text, math }
+}
+
+/// `{"key": <Single: {...}>, ...}` の形のハッシュファイルから、
+/// 最も外側のオブジェクトのキーを取り出す。
+fn top_level_keys(hash: &str) -> Vec<String> {
+    let mut keys = vec![];
+    let mut depth = 0;
+    let mut chars = hash.char_indices();
+    while let Some((_, c)) = chars.next() {
+        match c {
+            '{' | '[' => depth += 1,
+            '}' | ']' => depth -= 1,
+            '"' => {
+                let mut key = String::new();
+                let mut end = hash.len();
+                while let Some((idx, c)) = chars.next() {
+                    match c {
+                        '\\' => key.extend(chars.next().map(|(_, c)| c)),
+                        '"' => {
+                            end = idx + 1;
+                            break;
+                        }
+                        c => key.push(c),
+                    }
+                }
+                // キーであれば直後に `:` が来る
+                if depth == 1 && hash[end..].trim_start().starts_with(':') {
+                    keys.push(key);
+                }
+            }
+            _ => {}
+        }
+    }
+    keys
+}
+
+/// ファイル中でフォント名を指定している文字列リテラルについて、その中身の位置と種類。
+/// CST の上で次の位置にあるものを対象とする。
+///
+/// - ``set-math-font `lmodern` `` の文字列
+/// - ``set-font Latin (`lmroman`, 1., 0.)`` の組の最初の要素
+/// - ``let f : font = (`ipaexm`, 0.88, 0.)`` のように、型が `font` と書かれた変数に束縛する
+///   「文字列・比率・位置」の組の最初の要素。 sig で ``val f : font`` と宣言したものも含む
+///
+/// 同じ形の組でもフォント以外を表すことがあるため、これ以外の位置にある組は対象としない。
+pub fn font_literals(program_text: &ProgramText) -> Vec<(Span, FontKind)> {
+    let declared = declared_fonts(program_text);
+    let mut literals: Vec<(Span, FontKind)> = program_text
+        .cst
+        .pickup(Rule::const_string)
+        .into_iter()
+        .filter_map(|literal| {
+            let (span, content) = string_content(program_text, literal)?;
+            if content.contains('\n') {
+                return None;
+            }
+            let kind = font_position(program_text, literal, &declared)?;
+            Some((span, kind))
+        })
+        .collect();
+    literals.sort_by_key(|(span, _)| span.start);
+    literals
+}
+
+/// sig で ``val f : font`` と宣言されている変数の名前。
+fn declared_fonts(program_text: &ProgramText) -> HashSet<&str> {
+    program_text
+        .cst
+        .pickup(Rule::sig_val_stmt)
+        .into_iter()
+        .filter_map(|stmt| {
+            let text = program_text.get_text(stmt).trim_start();
+            let (name, ty) = text.strip_prefix("val")?.split_once(':')?;
+            if ty.trim() == "font" {
+                Some(name.trim())
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// 文字列リテラルがフォント名を指定する位置にあれば、そのフォントの種類。
+fn font_position(
+    program_text: &ProgramText,
+    literal: &Cst,
+    declared: &HashSet<&str>,
+) -> Option<FontKind> {
+    if enclosing_application(program_text, literal) == Some(("set-math-font", 1)) {
+        return Some(FontKind::Math);
+    }
+
+    // 「文字列・比率・位置」の組の最初の要素であるか
+    let (tuple, first) = enclosing_node(program_text, literal)?;
+    let text = program_text.text.as_str();
+    let is_font_tuple = tuple.inner.len() == 3
+        && tuple.inner[0].span == first.span
+        && program_text.get_text(tuple).trim_start().starts_with('(')
+        && tuple
+            .inner
+            .windows(2)
+            .all(|pair| text[pair[0].span.end..pair[1].span.start].trim() == ",");
+    if !is_font_tuple {
+        return None;
+    }
+    if enclosing_application(program_text, tuple) == Some(("set-font", 2)) {
+        return Some(FontKind::Text);
+    }
+
+    // `let f : font = (...)` の右辺であるか
+    let (stmt, body) = enclosing_node(program_text, tuple)?;
+    let pattern = stmt.inner.first()?;
+    let is_body = stmt.rule == Rule::let_stmt
+        && pattern.rule == Rule::pattern
+        && stmt.inner.last().map(|last| last.span) == Some(body.span);
+    if !is_body {
+        return None;
+    }
+    let annotation: String = text[pattern.span.end..body.span.start]
+        .split_whitespace()
+        .collect();
+    let name = program_text.get_text(pattern).trim();
+    if annotation == ":font=" || (annotation == "=" && declared.contains(name)) {
+        Some(FontKind::Text)
+    } else {
+        None
+    }
+}
+
+/// 行のうちカーソルより前の部分が、フォント名を指定する文字列リテラルの途中で終わっていれば、
+/// そのフォントの種類。
+pub fn unclosed_font_literal(before: &str) -> Option<FontKind> {
+    static PATTERNS: Lazy<[(Regex, FontKind); 2]> = Lazy::new(|| {
+        [
+            (
+                Regex::new(r"(?:^|[^\w-])set-math-font\s*`[^`]*$").unwrap(),
+                FontKind::Math,
+            ),
+            (
+                Regex::new(r"(?:^|[^\w-])set-font\s+[\w.-]+\s*\(\s*`[^`]*$").unwrap(),
+                FontKind::Text,
+            ),
+        ]
+    });
+    PATTERNS
+        .iter()
+        .find(|(re, _)| re.is_match(before))
+        .map(|(_, kind)| *kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use satysfi_parser::structure::ProgramText;
+
+    use super::{font_literals, FontKind};
+
+    /// フォント名として読まれる文字列リテラルの中身と種類。
+    fn fonts(text: &str) -> Vec<(&str, FontKind)> {
+        let program_text = ProgramText::parse(text).unwrap();
+        font_literals(&program_text)
+            .into_iter()
+            .map(|(span, kind)| (&text[span.start..span.end], kind))
+            .collect()
+    }
+
+    #[test]
+    fn arguments_of_set_font() {
+        let text = "let ctx = get-initial-context 100pt (command \\math)\n  |> set-font Latin (`lmroman`, 1., 0.)\n  |> set-math-font\n    `lmodern`\n";
+        assert_eq!(
+            fonts(text),
+            vec![("lmroman", FontKind::Text), ("lmodern", FontKind::Math)]
+        );
+    }
+
+    #[test]
+    fn declared_as_font() {
+        let text = "module M : sig\n  val f :\n    font\n  val g : string * float * float\nend = struct\n  let f = (`ipaexm`, 0.88, 0.)\n  let g = (`abc`, 1., 0.)\n  let h : font =\n    (`ipaexg`, 0.88, 0.)\nend\n";
+        assert_eq!(
+            fonts(text),
+            vec![("ipaexm", FontKind::Text), ("ipaexg", FontKind::Text)]
+        );
+    }
+}
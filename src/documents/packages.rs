@@ -0,0 +1,191 @@
+//! インストールされているパッケージの一覧。
+//!
+//! `@require:` されていないパッケージで定義されているコマンドを探すのに用いる。
+//! パッケージの読み込みはサーバのロックを外して裏で行い、
+//! 二度目以降は更新日時の変わったファイルだけを読み直す。
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use glob::glob;
+use itertools::Itertools;
+use lspower::lsp::Url;
+use satysfi_parser::Rule;
+
+use super::{require_candidate_dirs, Component, DocumentData, Visibility};
+
+/// インストールされている一つのパッケージ。
+#[derive(Debug)]
+pub struct Package {
+    /// `@require:` に書くパッケージ名。
+    pub name: String,
+    pub url: Url,
+    pub doc_data: DocumentData,
+    /// 読み込んだときのファイルの更新日時。
+    modified: Option<SystemTime>,
+}
+
+impl Package {
+    /// パッケージが外部に公開しているコマンドのうち、種類と名前が一致するもの。
+    /// rule には `inline_cmd_name`, `block_cmd_name`, `math_cmd_name` のいずれかを指定する。
+    pub fn find_command(&self, rule: Rule, name: &str) -> Option<&Component> {
+        self.commands(rule).into_iter().find(|cmd| cmd.name == name)
+    }
+
+    /// パッケージが外部に公開しているコマンドのうち、指定した種類のもの。
+    pub fn commands(&self, rule: Rule) -> Vec<&Component> {
+        let environment = match &self.doc_data {
+            DocumentData::Parsed { environment, .. } => environment,
+            DocumentData::NotParsed { .. } => return vec![],
+        };
+        let cmds = match rule {
+            Rule::inline_cmd_name => environment.inline_cmds_external(&[]),
+            Rule::block_cmd_name => environment.block_cmds_external(&[]),
+            Rule::math_cmd_name => environment.math_cmds_external(&[]),
+            _ => return vec![],
+        };
+        cmds.into_iter()
+            .filter(|cmd| matches!(cmd.visibility, Visibility::Public | Visibility::Direct))
+            .collect()
+    }
+}
+
+/// パッケージのディレクトリごとの、そこにインストールされているパッケージ。
+#[derive(Debug, Default)]
+pub struct PackageIndex {
+    dirs: HashMap<PathBuf, Vec<Package>>,
+    /// 読み込み中のディレクトリ。
+    loading: HashSet<PathBuf>,
+}
+
+/// 一つのディレクトリのパッケージを読み込み直す作業。サーバのロックを外して実行する。
+#[derive(Debug)]
+pub struct PackageLoad {
+    dir: PathBuf,
+    /// 既に読み込んでいるパッケージと、読み込んだときの更新日時。
+    known: HashMap<Url, Option<SystemTime>>,
+}
+
+/// パッケージを読み込み直した結果。
+#[derive(Debug)]
+pub struct LoadedPackages {
+    /// 新たに読み込んだパッケージ。
+    packages: Vec<Package>,
+    /// 読み込み済みで、更新されていないパッケージ。
+    unchanged: HashSet<Url>,
+}
+
+impl PackageIndex {
+    /// 読み込み中でないディレクトリについて、それを読み込み直す作業を返す。
+    pub fn start_loading(&mut self, dirs: &[PathBuf]) -> Vec<PackageLoad> {
+        dirs.iter()
+            .filter(|dir| self.loading.insert(dir.to_path_buf()))
+            .map(|dir| PackageLoad {
+                dir: dir.clone(),
+                known: self
+                    .dirs
+                    .get(dir)
+                    .into_iter()
+                    .flatten()
+                    .map(|pkg| (pkg.url.clone(), pkg.modified))
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// 読み込み直した結果を反映する。読み込みに失敗していれば loaded は None。
+    pub fn finish_loading(&mut self, dir: &Path, loaded: Option<LoadedPackages>) {
+        self.loading.remove(dir);
+        let loaded = match loaded {
+            Some(loaded) => loaded,
+            None => return,
+        };
+        let mut packages = self
+            .dirs
+            .remove(dir)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|pkg| loaded.unchanged.contains(&pkg.url))
+            .collect_vec();
+        packages.extend(loaded.packages);
+        packages.sort_by(|a, b| a.name.cmp(&b.name));
+        self.dirs.insert(dir.to_owned(), packages);
+    }
+
+    /// 読み込んだパッケージのうち、その URL のもの。
+    pub fn find(&self, url: &Url) -> Option<&Package> {
+        self.dirs.values().flatten().find(|pkg| &pkg.url == url)
+    }
+
+    /// 指定したディレクトリにあるパッケージの一覧。
+    /// 同名のパッケージは先に指定したディレクトリにあるものを優先する。
+    pub fn packages(&self, dirs: &[PathBuf]) -> Vec<&Package> {
+        let mut names = HashSet::new();
+        dirs.iter()
+            .filter_map(|dir| self.dirs.get(dir))
+            .flatten()
+            .filter(|pkg| names.insert(pkg.name.as_str()))
+            .collect_vec()
+    }
+}
+
+/// そのファイルから `@require:` できるパッケージのディレクトリ。
+pub fn package_dirs(url: &Url) -> Vec<PathBuf> {
+    let file_path = url.to_file_path().ok();
+    let parent_path = file_path
+        .as_ref()
+        .and_then(|p| p.parent())
+        .map(Path::to_owned);
+    let home_path = std::env::var("HOME").map(PathBuf::from).ok();
+    require_candidate_dirs(parent_path.as_deref(), home_path.as_deref())
+}
+
+impl PackageLoad {
+    /// 読み込むディレクトリ。
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// ディレクトリにあるパッケージのうち、新しいものと更新されたものを読み込む。
+    pub fn run(self) -> LoadedPackages {
+        let mut loaded = LoadedPackages {
+            packages: vec![],
+            unchanged: HashSet::new(),
+        };
+        let pattern = format!("{}/**/*.saty[hg]", self.dir.to_string_lossy());
+        let entries = match glob(&pattern) {
+            Ok(entries) => entries,
+            Err(_) => return loaded,
+        };
+        for path in entries.flatten() {
+            let url = match Url::from_file_path(&path) {
+                Ok(url) => url,
+                Err(_) => continue,
+            };
+            let modified = std::fs::metadata(&path)
+                .and_then(|meta| meta.modified())
+                .ok();
+            if modified.is_some() && self.known.get(&url) == Some(&modified) {
+                loaded.unchanged.insert(url);
+                continue;
+            }
+            let relative = match path.strip_prefix(&self.dir) {
+                Ok(relative) => relative.with_extension(""),
+                Err(_) => continue,
+            };
+            let name = relative.to_string_lossy().replace('\\', "/");
+            if let Ok(doc_data) = DocumentData::new_from_file(&url) {
+                loaded.packages.push(Package {
+                    name,
+                    url,
+                    doc_data,
+                    modified,
+                });
+            }
+        }
+        loaded
+    }
+}
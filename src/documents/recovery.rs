@@ -0,0 +1,293 @@
+//! 構文エラーからの回復。
+//!
+//! パーサは最初のエラーで止まってしまうため、エラーのあった文・ヘッダ・段落を
+//! 空白で塗りつぶしてから再度パースすることを繰り返し、独立した構文エラーを
+//! 一度にすべて集める。塗りつぶしは行単位で行い、バイト位置と改行を保つため、
+//! 塗りつぶした行以外の位置はもとのテキストと一致する。
+
+use std::time::{Duration, Instant};
+
+use satysfi_parser::{structure::ProgramText, LineCol, Rule, Span};
+
+use crate::util::line_start;
+
+/// 一つのファイルについて報告する構文エラーの最大数。
+const MAX_ERRORS: usize = 32;
+
+/// 回復のための再パースに費やす時間の上限。
+/// 編集のたびに実行されるため、大きなファイルでは残りのエラーの報告を諦めて打ち切る。
+const RECOVERY_BUDGET: Duration = Duration::from_millis(200);
+
+/// パーサが dummy ノードとして読み飛ばす（回復可能な）構文エラーの種類。
+pub const DUMMY_RULES: &[Rule] = &[
+    Rule::dummy_stmt,
+    Rule::dummy_header,
+    Rule::dummy_sig_stmt,
+    Rule::dummy_block_cmd_incomplete,
+    Rule::dummy_inline_cmd_incomplete,
+];
+
+/// 文・ヘッダの始まりとみなすキーワード。
+const BOUNDARY_KEYWORDS: &[&str] = &[
+    "let",
+    "let-rec",
+    "let-inline",
+    "let-block",
+    "let-math",
+    "let-mutable",
+    "type",
+    "module",
+    "open",
+    "document",
+    "@require:",
+    "@import:",
+    "@stage:",
+];
+
+/// 構文エラー。
+#[derive(Debug)]
+pub enum SyntaxError {
+    /// パースを続行できなかった箇所。
+    Unexpected {
+        linecol: LineCol,
+        /// エラー箇所にて期待するパターン（終端記号）列。
+        expect: Vec<&'static str>,
+    },
+    /// パーサが dummy ノードとして読み飛ばした箇所。
+    Incomplete { span: Span, rule: Rule },
+}
+
+/// テキストをパースする。失敗した場合はエラーからの回復を試み、
+/// 見つかったすべての構文エラーを返す。
+pub fn parse_with_recovery(text: &str) -> Result<ProgramText, Vec<SyntaxError>> {
+    let (linecol, expect) = match ProgramText::parse(text) {
+        Ok(program_text) => return Ok(program_text),
+        Err(err) => err,
+    };
+    Err(recover(text, linecol, expect, RECOVERY_BUDGET))
+}
+
+/// テキストをパースする。失敗した場合はエラーからの回復を行わず、最初の構文エラーのみを返す。
+/// 入力中のように、すぐに次の編集が来る場合に用いる。
+pub fn parse_without_recovery(text: &str) -> Result<ProgramText, Vec<SyntaxError>> {
+    ProgramText::parse(text)
+        .map_err(|(linecol, expect)| vec![SyntaxError::Unexpected { linecol, expect }])
+}
+
+/// 最初のエラーの位置から、塗りつぶしと再パースを budget の時間内で繰り返して構文エラーを集める。
+fn recover(
+    text: &str,
+    linecol: LineCol,
+    expect: Vec<&'static str>,
+    budget: Duration,
+) -> Vec<SyntaxError> {
+    let started = Instant::now();
+    let mut patched = text.to_owned();
+    let mut blanked: Vec<Span> = vec![];
+    let mut pos = offset_of(&patched, &linecol);
+    let mut errors = vec![SyntaxError::Unexpected { linecol, expect }];
+
+    while let Some(error_pos) = pos {
+        if errors.len() >= MAX_ERRORS || started.elapsed() >= budget {
+            break;
+        }
+        let region = resync_region(&patched, error_pos);
+        // これ以上塗りつぶせるものがなければ打ち切る
+        if patched[region.start..region.end].trim().is_empty() {
+            break;
+        }
+        blank(&mut patched, region);
+        blanked.push(region);
+
+        match ProgramText::parse(&patched) {
+            Ok(program_text) => {
+                // 回復後に残っている dummy ノードも構文エラーとして報告する
+                for &rule in DUMMY_RULES {
+                    for cst in program_text.cst.pickup(rule) {
+                        errors.push(SyntaxError::Incomplete {
+                            span: cst.span,
+                            rule,
+                        });
+                    }
+                }
+                break;
+            }
+            Err((linecol, expect)) => {
+                pos = offset_of(&patched, &linecol);
+                let next_pos = match pos {
+                    Some(next_pos) => next_pos,
+                    None => break,
+                };
+                // 末尾でのエラーは塗りつぶしによって生じたものとみなして打ち切る
+                if patched[next_pos..].trim().is_empty() {
+                    break;
+                }
+                // 塗りつぶした範囲の直後で生じたエラーは連鎖的なものなので報告しない
+                let next_line = line_start(&patched, next_pos);
+                if blanked.iter().all(|span| span.end != next_line) {
+                    errors.push(SyntaxError::Unexpected { linecol, expect });
+                }
+            }
+        }
+    }
+
+    errors
+}
+
+/// LineCol（column は文字数）をバイト位置に変換する。
+fn offset_of(text: &str, linecol: &LineCol) -> Option<usize> {
+    let start = if linecol.line == 0 {
+        0
+    } else {
+        text.match_indices('\n').nth(linecol.line - 1)?.0 + 1
+    };
+    let column: usize = text[start..]
+        .chars()
+        .take(linecol.column)
+        .map(|c| c.len_utf8())
+        .sum();
+    Some(start + column)
+}
+
+/// その行が文・ヘッダ・段落の境界であるか。
+fn is_boundary(line: &str) -> bool {
+    let line = line.trim_start();
+    if line.is_empty() || line.starts_with('+') {
+        return true;
+    }
+    BOUNDARY_KEYWORDS.iter().any(|&kwd| {
+        line.starts_with(kwd)
+            && !line[kwd.len()..].starts_with(|c: char| c.is_ascii_alphanumeric() || c == '-')
+    })
+}
+
+/// エラー位置を含む文・ヘッダ・段落の範囲を行単位で求める。
+/// エラー行から遡って最初の境界から、エラー行より後の最初の境界の直前まで。
+fn resync_region(text: &str, pos: usize) -> Span {
+    let lines: Vec<(usize, &str)> = {
+        let mut offset = 0;
+        text.split('\n')
+            .map(|line| {
+                let start = offset;
+                offset += line.len() + 1;
+                (start, line)
+            })
+            .collect()
+    };
+    let error_line = lines
+        .iter()
+        .rposition(|&(start, _)| start <= pos)
+        .unwrap_or(0);
+
+    let first = (0..=error_line)
+        .rev()
+        .find_map(|idx| {
+            let line = lines[idx].1;
+            if line.trim().is_empty() {
+                // 空行の次の行から段落が始まる
+                Some(idx + 1)
+            } else if is_boundary(line) {
+                Some(idx)
+            } else {
+                None
+            }
+        })
+        .unwrap_or(0)
+        .min(error_line);
+    let last = (error_line + 1..lines.len())
+        .find(|&idx| is_boundary(lines[idx].1))
+        .unwrap_or(lines.len());
+
+    let start = lines[first].0;
+    let end = lines
+        .get(last)
+        .map(|&(start, _)| start)
+        .unwrap_or(text.len());
+    Span { start, end }
+}
+
+/// 範囲内の改行以外の文字をバイト数を保ったまま空白に置き換える。
+fn blank(text: &mut String, span: Span) {
+    let replaced: String = text[span.start..span.end]
+        .chars()
+        .map(|c| {
+            if c == '\n' {
+                "\n".to_owned()
+            } else {
+                " ".repeat(c.len_utf8())
+            }
+        })
+        .collect();
+    text.replace_range(span.start..span.end, &replaced);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 構文エラーのある行（0 から数える）。
+    fn error_lines(errors: &[SyntaxError]) -> Vec<usize> {
+        errors
+            .iter()
+            .filter_map(|error| match error {
+                SyntaxError::Unexpected { linecol, .. } => Some(linecol.line),
+                SyntaxError::Incomplete { .. } => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parse_valid_text() {
+        assert!(parse_with_recovery("let x = 1\n").is_ok());
+    }
+
+    #[test]
+    fn collect_independent_errors() {
+        let text = "let a = ;\nlet b = 1\n\nlet c = ]\nlet d = 2\n";
+        let errors = parse_with_recovery(text).err().unwrap();
+        assert_eq!(error_lines(&errors), vec![0, 3]);
+    }
+
+    #[test]
+    fn without_recovery_reports_first_error() {
+        let text = "let a = ;\nlet b = 1\n\nlet c = ]\n";
+        let errors = parse_without_recovery(text).err().unwrap();
+        assert_eq!(error_lines(&errors), vec![0]);
+    }
+
+    #[test]
+    fn stop_when_budget_is_exhausted() {
+        let text = "let a = ;\nlet b = 1\n\nlet c = ]\n";
+        let (linecol, expect) = ProgramText::parse(text).err().unwrap();
+        let errors = recover(text, linecol, expect, Duration::from_millis(0));
+        assert_eq!(error_lines(&errors), vec![0]);
+    }
+
+    #[test]
+    fn boundary_lines() {
+        assert!(is_boundary("let-inline ctx \\foo = {}"));
+        assert!(is_boundary("  +p{ text }"));
+        assert!(is_boundary(""));
+        assert!(!is_boundary("letter"));
+        assert!(!is_boundary("let-foo x"));
+        assert!(!is_boundary("  in x"));
+    }
+
+    #[test]
+    fn resync_to_surrounding_boundaries() {
+        let text = "let a = 1\nlet b =\n  (2 +\n  3\nlet c = 4\n";
+        let error_pos = text.find("(2").unwrap();
+        let region = resync_region(text, error_pos);
+        assert_eq!(&text[region.start..region.end], "let b =\n  (2 +\n  3\n");
+    }
+
+    #[test]
+    fn blank_keeps_offsets() {
+        let mut text = "let α = 1\nlet b = 2\n".to_owned();
+        let len = text.len();
+        blank(&mut text, Span { start: 0, end: 11 });
+        assert_eq!(text.len(), len);
+        assert_eq!(text.matches('\n').count(), 2);
+        assert!(text.ends_with("let b = 2\n"));
+    }
+}
@@ -1,30 +1,55 @@
+use itertools::Itertools;
 use log::{error, info};
 use lspower::{
-    jsonrpc::Result as LspResult,
+    jsonrpc::{Error, Result as LspResult},
     lsp::{
-        CompletionParams, CompletionResponse, DidChangeTextDocumentParams,
-        DidOpenTextDocumentParams, DidSaveTextDocumentParams, DocumentFormattingParams,
+        CodeActionParams, CodeActionResponse, CodeLens, CodeLensParams, CompletionItem,
+        CompletionParams, CompletionResponse, Diagnostic, DiagnosticSeverity,
+        DidChangeConfigurationParams, DidChangeTextDocumentParams, DidOpenTextDocumentParams,
+        DidSaveTextDocumentParams, DocumentFormattingParams, ExecuteCommandParams,
         GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverParams, InitializeParams,
-        InitializeResult, ServerInfo, TextEdit,
+        InitializeResult, Location, LocationLink, Range, ServerInfo, TextEdit, Url,
     },
 };
-use std::sync::Arc;
+use serde_json::Value;
+use std::{collections::HashMap, path::PathBuf, sync::Arc, time::Duration};
 
 use lspower::Client;
 
 use crate::{
     config::Config,
-    documents::{DocumentCache, DocumentData},
+    documents::{
+        library_roots, package_dirs, DocumentCache, DocumentData, FontIndex, FontNames,
+        PackageIndex, SyntaxError, Unparsed,
+    },
     util::UrlPos,
 };
 
-use self::diagnostics::{get_diagnostics, DiagnosticCollection};
+use self::code_action::get_unparsed_code_actions;
+use self::code_lens::COMPILE_COMMAND;
+use self::compiler::{parse_compiler_output, CompileTask};
+use self::completion::{
+    completion_query, get_literal_completion, recompute_edit_ranges, CompletionResources,
+};
+use self::diagnostics::{
+    get_lint_diagnostics, get_syntax_diagnostics, DiagnosticCollection, DiagnosticSource,
+};
 
 mod capabilities;
+mod code_action;
+mod code_lens;
+mod compiler;
 mod completion;
 mod definition;
 mod diagnostics;
 mod hover;
+mod snippet_edit;
+mod unicode_names;
+
+pub use self::snippet_edit::SnippetEditService;
+
+/// 入力が止まってから、パースに失敗したファイルのエラーからの回復を行うまでの時間。
+const RECOVERY_DELAY: Duration = Duration::from_millis(300);
 
 #[derive(Debug, Clone)]
 pub struct LanguageServer(Arc<tokio::sync::Mutex<Inner>>);
@@ -33,6 +58,52 @@ impl LanguageServer {
     pub fn new(client: Client) -> Self {
         Self(Arc::new(tokio::sync::Mutex::new(Inner::new(client))))
     }
+
+    /// そのファイルから `@require:` できるパッケージを、ロックを外して裏で読み込み直す。
+    /// ファイルを開いたときと保存したときに呼び、パッケージの追加や更新を反映する。
+    /// 読み込みが終わるまでのリクエストには、それまでに読み込んだパッケージを用いる。
+    fn refresh_packages(&self, url: &Url) {
+        let server = self.clone();
+        let dirs = package_dirs(url);
+        tokio::spawn(async move {
+            let loads = server.0.lock().await.packages.start_loading(&dirs);
+            for load in loads {
+                let dir = load.dir().to_owned();
+                let loaded = tokio::task::spawn_blocking(move || load.run()).await.ok();
+                server.0.lock().await.packages.finish_loading(&dir, loaded);
+            }
+        });
+    }
+
+    /// パースに失敗したファイルについて、少し待ってもテキストが text のままであれば、
+    /// ロックを外してエラーからの回復を行い、見つかった構文エラーを診断に反映する。
+    /// 入力のたびに回復のための再パースを繰り返さないようにするためのもの。
+    fn recover_later(&self, url: Url, text: String) {
+        let server = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(RECOVERY_DELAY).await;
+            let is_latest = matches!(
+                server.0.lock().await.current_document(&url),
+                Some(DocumentData::NotParsed { text: current, .. }) if current == &text
+            );
+            if !is_latest {
+                return;
+            }
+            let recovered = tokio::task::spawn_blocking(move || {
+                let doc_data = DocumentData::new(&text, &url);
+                (url, text, doc_data)
+            })
+            .await;
+            if let Ok((url, text, DocumentData::NotParsed { errors, .. })) = recovered {
+                server
+                    .0
+                    .lock()
+                    .await
+                    .set_syntax_errors(&url, &text, errors)
+                    .await;
+            }
+        });
+    }
 }
 
 #[lspower::async_trait]
@@ -45,20 +116,45 @@ impl lspower::LanguageServer for LanguageServer {
         self.0.lock().await.get_completion(params).await
     }
 
+    async fn completion_resolve(&self, params: CompletionItem) -> LspResult<CompletionItem> {
+        let inner = self.0.lock().await;
+        Ok(inner
+            .documents
+            .resolve_completion_item(params, &inner.packages))
+    }
+
     async fn did_change(&self, params: DidChangeTextDocumentParams) {
-        self.0.lock().await.did_change(params).await;
+        let url = params.text_document.uri.clone();
+        if let Some(text) = self.0.lock().await.did_change(params).await {
+            self.recover_later(url, text);
+        }
     }
 
     async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        self.refresh_packages(&params.text_document.uri);
         self.0.lock().await.did_open(params).await;
     }
 
+    async fn did_change_configuration(&self, params: DidChangeConfigurationParams) {
+        self.0.lock().await.did_change_configuration(params).await;
+    }
+
     async fn hover(&self, params: HoverParams) -> LspResult<Option<Hover>> {
         self.0.lock().await.hover(params).await
     }
 
     async fn did_save(&self, params: DidSaveTextDocumentParams) {
-        self.0.lock().await.did_save(params).await;
+        self.refresh_packages(&params.text_document.uri);
+        let task = self.0.lock().await.did_save(params).await;
+        // コンパイル中も他のリクエストに応答できるよう、ロックを外して実行する
+        if let Some(task) = task {
+            let result = task.run().await;
+            self.0
+                .lock()
+                .await
+                .publish_compiler_result(&task, result)
+                .await;
+        }
     }
 
     async fn goto_definition(
@@ -75,6 +171,30 @@ impl lspower::LanguageServer for LanguageServer {
         Ok(self.0.lock().await.formatting(params).await)
     }
 
+    async fn code_action(&self, params: CodeActionParams) -> LspResult<Option<CodeActionResponse>> {
+        self.0.lock().await.code_action(params).await
+    }
+
+    async fn code_lens(&self, params: CodeLensParams) -> LspResult<Option<Vec<CodeLens>>> {
+        self.0.lock().await.code_lens(params).await
+    }
+
+    async fn code_lens_resolve(&self, params: CodeLens) -> LspResult<CodeLens> {
+        Ok(self.0.lock().await.documents.resolve_code_lens(params))
+    }
+
+    async fn execute_command(&self, params: ExecuteCommandParams) -> LspResult<Option<Value>> {
+        let task = self.0.lock().await.execute_command(params)?;
+        // did_save と同様、コンパイル中はロックを外しておく
+        let result = task.run().await;
+        self.0
+            .lock()
+            .await
+            .publish_compiler_result(&task, result)
+            .await;
+        Ok(None)
+    }
+
     async fn shutdown(&self) -> LspResult<()> {
         Ok(())
     }
@@ -90,6 +210,23 @@ pub struct Inner {
     diagnostics: DiagnosticCollection,
     /// The "in-memory" documents in the editor which can be updated and changed.
     documents: DocumentCache,
+    /// The latest state of the documents which failed to be parsed.
+    /// While a document is here, `documents` keeps its last successfully parsed data.
+    unparsed: HashMap<Url, Unparsed>,
+    /// Packages installed in the package directories, loaded in the background.
+    packages: PackageIndex,
+    /// Font names registered in the font hash files of the library roots, loaded on demand.
+    fonts: FontIndex,
+    /// The root directory of the workspace, if any.
+    workspace_root: Option<PathBuf>,
+    /// User-defined completion items, reloaded when the resource files change.
+    resources: CompletionResources,
+    /// Whether the client accepts snippets in the edits of code actions
+    /// (the `snippetTextEdit` experimental capability).
+    snippet_text_edit: bool,
+    /// The number of compilations requested so far for each compiled document.
+    /// Results of a compilation superseded by a later one are dropped.
+    compile_generations: HashMap<PathBuf, u64>,
 }
 
 impl Inner {
@@ -99,16 +236,41 @@ impl Inner {
             config: Config::default(),
             diagnostics: DiagnosticCollection::default(),
             documents: DocumentCache::default(),
+            unparsed: HashMap::new(),
+            packages: PackageIndex::default(),
+            fonts: FontIndex::default(),
+            workspace_root: None,
+            resources: CompletionResources::default(),
+            snippet_text_edit: false,
+            compile_generations: HashMap::new(),
         }
     }
 
     async fn initialize(&mut self, params: InitializeParams) -> LspResult<InitializeResult> {
         let capabilities = capabilities::server_capabilities(&params.capabilities);
+        self.snippet_text_edit = params
+            .capabilities
+            .experimental
+            .as_ref()
+            .and_then(|experimental| experimental.get("snippetTextEdit"))
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
         let server_info = ServerInfo {
             name: "satysfi-language-server".to_owned(),
             version: Some(crate::version()),
         };
 
+        if let Some(config) = params.initialization_options.and_then(Config::from_value) {
+            self.config = config;
+        }
+        self.workspace_root = params
+            .workspace_folders
+            .and_then(|folders| folders.into_iter().next())
+            .map(|folder| folder.uri)
+            .or(params.root_uri)
+            .and_then(|uri| uri.to_file_path().ok());
+        self.load_resources();
+
         if let Some(client_info) = params.client_info {
             info!(
                 "Connected to \"{}\" {}",
@@ -124,7 +286,7 @@ impl Inner {
     }
 
     async fn get_completion(
-        &self,
+        &mut self,
         params: CompletionParams,
     ) -> LspResult<Option<CompletionResponse>> {
         let url = params.text_document_position.text_document.uri;
@@ -132,9 +294,50 @@ impl Inner {
         let trigger = params.context.and_then(|ctx| ctx.trigger_character);
         if self.documents.0.get(&url).is_some() {
             let curpos = UrlPos { url, pos };
-            Ok(self
+            // 文字列リテラルの中身や絞り込みに使う入力中の文字列は最新のテキストから求める
+            let line = self
+                .current_document(&curpos.url)
+                .and_then(|doc_data| doc_data.text().split('\n').nth(pos.line as usize))
+                .map(ToOwned::to_owned)
+                .unwrap_or_default();
+            let fonts = self.font_names(&curpos.url);
+            let references = self
                 .documents
-                .get_completion_list(&curpos, trigger.as_deref()))
+                .cross_references(&curpos.url, &self.config.cross_reference);
+            if let Some(resp) = get_literal_completion(
+                &curpos.url,
+                &line,
+                &pos,
+                &fonts,
+                &self.config.completion.path_arguments,
+                &self.config.cross_reference,
+                &references,
+            ) {
+                return Ok(Some(resp));
+            }
+            let analyzed = match self.analyzed_position(&curpos) {
+                Some(analyzed) => analyzed,
+                None => return Ok(None),
+            };
+            let query = completion_query(&line, &pos);
+            self.load_resources();
+            let resp = self.documents.get_completion_list(
+                &analyzed,
+                trigger.as_deref(),
+                &query,
+                &self.packages,
+                &self.resources,
+                self.workspace_root.as_deref(),
+            );
+            // 古い解析結果を使っている場合、置き換える範囲は最新のテキストから求める
+            match (resp, self.unparsed.get(&curpos.url)) {
+                (Some(resp), Some(unparsed)) => Ok(unparsed.line(pos.line).map(|line| {
+                    recompute_edit_ranges(resp, line, &pos, |range| {
+                        self.current_range(&curpos.url, range)
+                    })
+                })),
+                (resp, _) => Ok(resp),
+            }
         } else {
             Ok(None)
         }
@@ -142,7 +345,7 @@ impl Inner {
 
     async fn formatting(&self, params: DocumentFormattingParams) -> Option<Vec<TextEdit>> {
         let uri = params.text_document.uri;
-        let d_data = self.documents.0.get(&uri).unwrap();
+        let d_data = self.current_document(&uri)?;
         match d_data {
             DocumentData::Parsed { program_text, .. } => {
                 let result = satysfi_formatter::formatting(&program_text.text, params.options);
@@ -155,52 +358,276 @@ impl Inner {
         }
     }
 
-    async fn did_change(&mut self, params: DidChangeTextDocumentParams) {
+    /// 変更されたテキストを格納する。入力中はエラーからの回復を行わないため、
+    /// パースに失敗した場合はそのテキストを返し、回復は後で行う。
+    async fn did_change(&mut self, params: DidChangeTextDocumentParams) -> Option<String> {
         let url = params.text_document.uri;
-        if let Some(cc) = params.content_changes.into_iter().last() {
-            let text = cc.text;
-            let doc_data = DocumentData::new(&text, &url);
-
-            if let DocumentData::Parsed { environment, .. } = &doc_data {
-                self.documents
-                    .register_dependencies(environment.dependencies());
+        let text = match params.content_changes.into_iter().last() {
+            Some(cc) => cc.text,
+            None => {
+                error!("failed to extract changes of document {:?}!", url);
+                return None;
             }
-
-            let diags = get_diagnostics(&doc_data);
-            self.documents.0.insert(url.clone(), doc_data);
-            self.client.publish_diagnostics(url, diags, None).await;
+        };
+        let doc_data = DocumentData::new_without_recovery(&text, &url);
+        let is_parsed = matches!(doc_data, DocumentData::Parsed { .. });
+        let keys = self
+            .documents
+            .defined_keys(&url, &self.config.cross_reference);
+        self.store_document(&url, doc_data);
+        self.update_diagnostics(&url).await;
+        // 定義しているキーが変わったときのみ、同じ文書に属する他のファイルの診断も更新する
+        if self
+            .documents
+            .defined_keys(&url, &self.config.cross_reference)
+            != keys
+        {
+            self.update_sibling_diagnostics(&url).await;
+        }
+        if is_parsed {
+            None
         } else {
-            error!("failed to extract changes of document {:?}!", url);
+            Some(text)
         }
     }
 
+    /// エラーからの回復で見つかった構文エラーを、最新のテキストが text のままであれば反映する。
+    async fn set_syntax_errors(&mut self, url: &Url, text: &str, errors: Vec<SyntaxError>) {
+        let doc_data = match self.unparsed.get_mut(url) {
+            Some(unparsed) => &mut unparsed.doc_data,
+            None => match self.documents.0.get_mut(url) {
+                Some(doc_data) => doc_data,
+                None => return,
+            },
+        };
+        match doc_data {
+            DocumentData::NotParsed {
+                text: current,
+                errors: current_errors,
+            } if current.as_str() == text => *current_errors = errors,
+            _ => return,
+        }
+        self.update_diagnostics(url).await;
+    }
+
     async fn did_open(&mut self, params: DidOpenTextDocumentParams) {
         let url = params.text_document.uri;
         let text = params.text_document.text;
         let doc_data = DocumentData::new(&text, &url);
+        self.store_document(&url, doc_data);
+        self.update_diagnostics(&url).await;
+    }
 
+    /// 新しく作成した DocumentData を格納する。
+    /// パースに失敗した場合、以前にパースに成功したデータがあればそれを残しておく。
+    fn store_document(&mut self, url: &Url, doc_data: DocumentData) {
         if let DocumentData::Parsed { environment, .. } = &doc_data {
             self.documents
                 .register_dependencies(environment.dependencies());
         }
 
-        let diags = get_diagnostics(&doc_data);
-        self.documents.0.insert(url.clone(), doc_data);
-        self.client.publish_diagnostics(url, diags, None).await;
+        let analyzed = match (&doc_data, self.documents.get_doc_info(url)) {
+            (DocumentData::NotParsed { .. }, Some((program_text, _))) => Some(program_text),
+            _ => None,
+        };
+        if let Some(program_text) = analyzed {
+            let unparsed = Unparsed::new(doc_data, program_text);
+            self.unparsed.insert(url.clone(), unparsed);
+        } else {
+            self.unparsed.remove(url);
+            self.documents.0.insert(url.clone(), doc_data);
+        }
+    }
+
+    /// 最新のテキストに対応する DocumentData。
+    fn current_document(&self, url: &Url) -> Option<&DocumentData> {
+        self.unparsed
+            .get(url)
+            .map(|unparsed| &unparsed.doc_data)
+            .or_else(|| self.documents.0.get(url))
+    }
+
+    /// 最新のテキスト上の位置を、解析結果のテキスト上の位置に変換する。
+    /// パースに成功しているファイルではそのまま返す。
+    fn analyzed_position(&self, curpos: &UrlPos) -> Option<UrlPos> {
+        match self.unparsed.get(&curpos.url) {
+            Some(unparsed) => {
+                let (program_text, _) = self.documents.get_doc_info(&curpos.url)?;
+                let pos = unparsed.to_analyzed(program_text, &curpos.pos)?;
+                Some(UrlPos {
+                    url: curpos.url.clone(),
+                    pos,
+                })
+            }
+            None => Some(curpos.clone()),
+        }
+    }
+
+    /// 解析結果のテキスト上の範囲を、最新のテキスト上の範囲に変換する。
+    fn current_range(&self, url: &Url, range: Range) -> Option<Range> {
+        match self.unparsed.get(url) {
+            Some(unparsed) => {
+                let (program_text, _) = self.documents.get_doc_info(url)?;
+                unparsed.to_current(program_text, &range)
+            }
+            None => Some(range),
+        }
     }
 
-    async fn did_save(&mut self, params: DidSaveTextDocumentParams) {
+    /// 保存されたファイルの診断を更新する。
+    /// コンパイラによる診断が有効であれば、実行すべきコンパイルを返す。
+    async fn did_save(&mut self, params: DidSaveTextDocumentParams) -> Option<CompileTask> {
         let url = params.text_document.uri;
-        let doc_data = self.documents.0.get(&url);
+        self.update_diagnostics(&url).await;
+        self.update_sibling_diagnostics(&url).await;
+
+        if let Some(doc_data) = self.documents.0.get(&url) {
+            doc_data.show_envs_debug();
+        }
+
+        if !self.config.compiler.enable {
+            return None;
+        }
+        let root = self.documents.document_root(&url)?.to_file_path().ok()?;
+        Some(self.compile_task(root))
+    }
+
+    /// root をコンパイルするタスクを作り、そのファイルに対する最新のコンパイルとして記録する。
+    fn compile_task(&mut self, root: PathBuf) -> CompileTask {
+        let generation = self.compile_generations.entry(root.clone()).or_default();
+        *generation += 1;
+        CompileTask::new(&self.config.compiler, root, *generation)
+    }
+
+    /// コンパイラの出力から診断を作り、前回の結果を置き換えて client に送る。
+    /// 同じファイルに対してより新しいコンパイルが要求されていれば、結果を捨てる。
+    async fn publish_compiler_result(
+        &mut self,
+        task: &CompileTask,
+        result: anyhow::Result<String>,
+    ) {
+        if self.compile_generations.get(&task.root) != Some(&task.generation) {
+            info!(
+                "discard the result of a superseded compilation of {:?}",
+                task.root
+            );
+            return;
+        }
+        let mut urls = self.diagnostics.clear(DiagnosticSource::External);
+        let mut diagnostics: HashMap<Url, Vec<Diagnostic>> = HashMap::new();
+        match result {
+            Ok(output) => {
+                for message in parse_compiler_output(&output) {
+                    let url = match message.url(&task.root) {
+                        Some(url) => url,
+                        None => continue,
+                    };
+                    let text = self.current_document(&url).map(|doc| doc.text());
+                    let diagnostic = message.to_diagnostic(text);
+                    diagnostics.entry(url).or_default().push(diagnostic);
+                }
+            }
+            Err(err) => {
+                // 実行そのものに失敗した場合はコンパイルしたファイルの先頭に報告する
+                error!("failed to run the compiler: {}", err);
+                if let Ok(url) = Url::from_file_path(&task.root) {
+                    let diagnostic = Diagnostic {
+                        severity: Some(DiagnosticSeverity::WARNING),
+                        source: Some("satysfi".to_owned()),
+                        message: format!("failed to run the compiler: {}", err),
+                        ..Default::default()
+                    };
+                    diagnostics.insert(url, vec![diagnostic]);
+                }
+            }
+        }
+        for (url, diags) in diagnostics {
+            urls.push(url.clone());
+            self.diagnostics.set(url, DiagnosticSource::External, diags);
+        }
+        for url in urls.into_iter().unique() {
+            self.publish_diagnostics(&url).await;
+        }
+    }
 
+    async fn did_change_configuration(&mut self, params: DidChangeConfigurationParams) {
+        if let Some(config) = Config::from_value(params.settings) {
+            self.config = config;
+        } else {
+            error!("failed to read the configuration.");
+            return;
+        }
+        self.load_resources();
+        // lint の重要度が変わりうるので、診断を出しているファイルをすべて更新する
+        for url in self.diagnostics.urls() {
+            self.update_diagnostics(&url).await;
+        }
+    }
+
+    /// 設定で指定された補完候補のリソースファイルのうち、変更されたものを読み込む。
+    /// 更新時刻は一定の間隔をおいてしか調べないので、補完のたびに呼んでよい。
+    fn load_resources(&mut self) {
+        let paths = self
+            .config
+            .completion
+            .resource_paths(self.workspace_root.as_deref());
+        self.resources.load(&paths);
+    }
+
+    /// そのファイルから参照されるフォントハッシュファイルに登録されているフォント名。
+    fn font_names(&mut self, url: &Url) -> FontNames {
+        let roots = library_roots(url);
+        self.fonts.load(&roots);
+        self.fonts.fonts(&roots)
+    }
+
+    /// そのファイルの構文エラーと lint の結果を更新し、 client に送る。
+    async fn update_diagnostics(&mut self, url: &Url) {
+        let fonts = self.font_names(url);
+        let references = self
+            .documents
+            .cross_references(url, &self.config.cross_reference);
+        let doc_data = self
+            .unparsed
+            .get(url)
+            .map(|unparsed| &unparsed.doc_data)
+            .or_else(|| self.documents.0.get(url));
         if let Some(doc_data) = doc_data {
-            let diags = get_diagnostics(&doc_data);
-            self.client.publish_diagnostics(url, diags, None).await;
+            self.diagnostics.set(
+                url.clone(),
+                DiagnosticSource::Parser,
+                get_syntax_diagnostics(doc_data, &self.config),
+            );
+            self.diagnostics.set(
+                url.clone(),
+                DiagnosticSource::Lint,
+                get_lint_diagnostics(doc_data, &self.config, &fonts, &references),
+            );
+        }
+        self.publish_diagnostics(url).await;
+    }
 
-            doc_data.show_envs_debug();
+    /// 相互参照のキーは文書全体で定義されるので、同じ文書に属する他のファイルの診断を更新する。
+    async fn update_sibling_diagnostics(&mut self, url: &Url) {
+        let root = match self.documents.document_root(url) {
+            Some(root) => root,
+            None => return,
+        };
+        for other in self.diagnostics.urls() {
+            if &other != url && self.documents.document_root(&other).as_ref() == Some(&root) {
+                self.update_diagnostics(&other).await;
+            }
         }
     }
 
+    /// すべての出どころの診断をまとめて client に送る。
+    async fn publish_diagnostics(&self, url: &Url) {
+        let diags = self.diagnostics.get(url);
+        self.client
+            .publish_diagnostics(url.clone(), diags, None)
+            .await;
+    }
+
     async fn goto_definition(
         &mut self,
         params: GotoDefinitionParams,
@@ -209,20 +636,134 @@ impl Inner {
         let pos = params.text_document_position_params.position;
 
         if self.documents.0.get(&url).is_some() {
-            let curpos = UrlPos { url, pos };
-            Ok(self.documents.get_definition_list(&curpos))
+            let curpos = match self.analyzed_position(&UrlPos { url, pos }) {
+                Some(curpos) => curpos,
+                None => return Ok(None),
+            };
+            let resp = self
+                .documents
+                .get_key_definition(&curpos, &self.config.cross_reference)
+                .or_else(|| self.documents.get_definition_list(&curpos));
+            // 定義がパースに失敗しているファイル内にあれば、位置を最新のテキストに合わせる
+            let current_location = |loc: Location| {
+                let range = self.current_range(&loc.uri, loc.range)?;
+                Some(Location { range, ..loc })
+            };
+            Ok(resp.and_then(|resp| match resp {
+                GotoDefinitionResponse::Scalar(loc) => {
+                    current_location(loc).map(GotoDefinitionResponse::Scalar)
+                }
+                GotoDefinitionResponse::Array(locs) => Some(GotoDefinitionResponse::Array(
+                    locs.into_iter().filter_map(current_location).collect(),
+                )),
+                GotoDefinitionResponse::Link(links) => Some(GotoDefinitionResponse::Link(
+                    links
+                        .into_iter()
+                        .filter_map(|link| {
+                            let uri = link.target_uri.clone();
+                            let origin_selection_range = match link.origin_selection_range {
+                                Some(range) => Some(self.current_range(&curpos.url, range)?),
+                                None => None,
+                            };
+                            Some(LocationLink {
+                                origin_selection_range,
+                                target_range: self.current_range(&uri, link.target_range)?,
+                                target_selection_range: self
+                                    .current_range(&uri, link.target_selection_range)?,
+                                ..link
+                            })
+                        })
+                        .collect(),
+                )),
+            }))
         } else {
             Ok(None)
         }
     }
 
+    async fn code_action(
+        &mut self,
+        params: CodeActionParams,
+    ) -> LspResult<Option<CodeActionResponse>> {
+        let url = &params.text_document.uri;
+        // 古い解析結果に基づく編集は適用できないので、最新のテキストがパースできていなければ
+        // そのテキストから分かる構文エラーの修正のみを提示する
+        match self.current_document(url) {
+            Some(DocumentData::Parsed { .. }) => {
+                let fonts = self.font_names(url);
+                Ok(self.documents.get_code_actions(
+                    &params,
+                    &self.config,
+                    &self.packages,
+                    &fonts,
+                    self.snippet_text_edit,
+                ))
+            }
+            Some(DocumentData::NotParsed { text, errors }) => Ok(Some(get_unparsed_code_actions(
+                &params,
+                text,
+                errors,
+                &self.config,
+            ))),
+            None => Ok(None),
+        }
+    }
+
+    async fn code_lens(&self, params: CodeLensParams) -> LspResult<Option<Vec<CodeLens>>> {
+        let url = params.text_document.uri;
+        if self.documents.0.get(&url).is_none() {
+            return Ok(None);
+        }
+        let lenses = self
+            .documents
+            .get_code_lenses(&url)
+            .into_iter()
+            .filter_map(|lens| {
+                let range = self.current_range(&url, lens.range)?;
+                Some(CodeLens { range, ..lens })
+            })
+            .collect();
+        Ok(Some(lenses))
+    }
+
+    /// サーバ側のコマンドを受け付け、実行すべきコンパイルを返す。
+    /// 設定でコンパイラによる診断が無効になっていても、明示的な要求であればコンパイルする。
+    fn execute_command(&mut self, params: ExecuteCommandParams) -> LspResult<CompileTask> {
+        if params.command != COMPILE_COMMAND {
+            return Err(Error::invalid_params(format!(
+                "unknown command: {}",
+                params.command
+            )));
+        }
+        let url = params
+            .arguments
+            .first()
+            .and_then(|arg| serde_json::from_value::<Url>(arg.clone()).ok())
+            .ok_or_else(|| Error::invalid_params("expected the URI of a document"))?;
+        let root = self
+            .documents
+            .document_root(&url)
+            .and_then(|root| root.to_file_path().ok())
+            .ok_or_else(|| Error::invalid_params(format!("no document to compile for {}", url)))?;
+        Ok(self.compile_task(root))
+    }
+
     async fn hover(&mut self, params: HoverParams) -> LspResult<Option<Hover>> {
         let url = params.text_document_position_params.text_document.uri;
         let pos = params.text_document_position_params.position;
 
         if self.documents.0.get(&url).is_some() {
-            let curpos = UrlPos { url, pos };
-            Ok(self.documents.get_hover(&curpos))
+            let curpos = match self.analyzed_position(&UrlPos { url, pos }) {
+                Some(curpos) => curpos,
+                None => return Ok(None),
+            };
+            let hover = self.documents.get_hover(&curpos);
+            Ok(hover.map(|hover| Hover {
+                range: hover
+                    .range
+                    .and_then(|range| self.current_range(&curpos.url, range)),
+                ..hover
+            }))
         } else {
             Ok(None)
         }
@@ -1,8 +1,11 @@
 use lspower::lsp::{
-    ClientCapabilities, CompletionOptions, HoverProviderCapability, OneOf, ServerCapabilities,
-    TextDocumentSyncCapability, TextDocumentSyncKind, DocumentFormattingOptions, WorkDoneProgressOptions,
+    ClientCapabilities, CodeActionKind, CodeActionOptions, CodeActionProviderCapability,
+    CodeLensOptions, CompletionOptions, ExecuteCommandOptions, HoverProviderCapability, OneOf,
+    ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind,
 };
 
+use super::code_lens::COMPILE_COMMAND;
+
 /// Client の capabilities に合わせて Server 側の capabilities を返す。
 /// 現在は Client 側の capabilities を一切見ずに固定の値を返す。
 pub fn server_capabilities(_client_capabilities: &ClientCapabilities) -> ServerCapabilities {
@@ -20,6 +23,7 @@ pub fn server_capabilities(_client_capabilities: &ClientCapabilities) -> ServerC
                 "@".to_owned(),
                 ".".to_owned(),
             ]),
+            resolve_provider: Some(true),
             ..Default::default()
         }),
         signature_help_provider: None,
@@ -30,8 +34,20 @@ pub fn server_capabilities(_client_capabilities: &ClientCapabilities) -> ServerC
         document_highlight_provider: None,
         document_symbol_provider: None,
         workspace_symbol_provider: None,
-        code_action_provider: None,
-        code_lens_provider: None,
+        code_action_provider: Some(CodeActionProviderCapability::Options(CodeActionOptions {
+            code_action_kinds: Some(vec![
+                CodeActionKind::QUICKFIX,
+                CodeActionKind::REFACTOR_EXTRACT,
+                CodeActionKind::REFACTOR_INLINE,
+                CodeActionKind::REFACTOR_REWRITE,
+                CodeActionKind::SOURCE_ORGANIZE_IMPORTS,
+                CodeActionKind::SOURCE_FIX_ALL,
+            ]),
+            ..Default::default()
+        })),
+        code_lens_provider: Some(CodeLensOptions {
+            resolve_provider: Some(true),
+        }),
         document_formatting_provider: Some(OneOf::Left(true)),
         document_range_formatting_provider: None,
         document_on_type_formatting_provider: None,
@@ -40,7 +56,10 @@ pub fn server_capabilities(_client_capabilities: &ClientCapabilities) -> ServerC
         color_provider: None,
         folding_range_provider: None,
         declaration_provider: None,
-        execute_command_provider: None,
+        execute_command_provider: Some(ExecuteCommandOptions {
+            commands: vec![COMPILE_COMMAND.to_owned()],
+            ..Default::default()
+        }),
         workspace: None,
         call_hierarchy_provider: None,
         semantic_tokens_provider: None,
@@ -0,0 +1,303 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use lspower::lsp::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams, CodeActionResponse,
+    TextEdit, Url, WorkspaceEdit,
+};
+use satysfi_parser::{structure::ProgramText, Cst, Rule, Span};
+
+use crate::{
+    config::Config,
+    documents::{DocumentCache, FontNames, PackageIndex, SyntaxError},
+    util::{line_start, ConvertPosition, UrlPos},
+};
+
+pub use self::edit::{
+    command_stub, doc_comment, insert_before_end, require_header_insertion, statement_end,
+};
+
+use self::syntax_fix::get_unterminated_block_actions;
+use super::diagnostics::{run_lints, Fix, Problem};
+
+mod auto_import;
+mod compact;
+mod define_command;
+mod edit;
+mod extract;
+mod inline;
+mod organize_imports;
+mod signature;
+mod syntax_fix;
+mod wrap;
+
+impl DocumentCache {
+    /// カーソル範囲に対する code action の一覧。
+    /// only が指定されていれば、その種類（およびその下位の種類）のものに絞る。
+    /// snippet はクライアントが編集に含まれるスニペットを扱えるかどうか。
+    pub fn get_code_actions(
+        &self,
+        params: &CodeActionParams,
+        config: &Config,
+        packages: &PackageIndex,
+        fonts: &FontNames,
+        snippet: bool,
+    ) -> Option<CodeActionResponse> {
+        let url = &params.text_document.uri;
+        let range = &params.range;
+        let only = params.context.only.as_deref();
+        let (program_text, environment) = self.get_doc_info(url)?;
+        let start = program_text.from_position(&range.start)?;
+        let end = program_text.from_position(&range.end)?;
+
+        let references = self.cross_references(url, &config.cross_reference);
+
+        let mut actions = vec![];
+        // カーソル範囲と重なる問題について、その修正候補を quick fix として提示する
+        for problem in run_lints(program_text, environment, config, fonts, &references) {
+            if problem.span.start > end || problem.span.end < start {
+                continue;
+            }
+            let diagnostic = match problem.to_diagnostic(program_text, config) {
+                Some(diagnostic) => diagnostic,
+                None => continue,
+            };
+            let suppress = suppression_fix(&problem, program_text);
+            for fix in problem.fixes.into_iter().chain(std::iter::once(suppress)) {
+                let mut action = fix_to_code_action(fix, url, program_text);
+                action.kind = Some(CodeActionKind::QUICKFIX);
+                action.diagnostics = Some(vec![diagnostic.clone()]);
+                actions.push(action);
+            }
+        }
+
+        actions.extend(self.get_syntax_fix_actions(url, start, end, config));
+
+        let curpos = UrlPos {
+            url: url.clone(),
+            pos: range.start,
+        };
+        actions.extend(self.get_auto_import_actions(&curpos, packages));
+        actions.extend(self.get_define_command_actions(&curpos));
+        actions.extend(self.get_extract_action(url, range));
+        actions.extend(self.get_inline_action(&curpos));
+        actions.extend(self.get_signature_actions(url, start));
+        actions.extend(self.get_wrap_actions(url, range, snippet));
+        actions.extend(self.get_compact_actions(&curpos));
+        actions.extend(self.get_organize_imports_action(url));
+        actions.extend(self.get_syntax_fix_all_action(url));
+
+        Some(filter_by_kind(actions, only))
+    }
+
+    /// カーソル下にある、どこにも定義の見つからないコマンド名。
+    fn find_unresolved_command(&self, curpos: &UrlPos) -> Option<&Cst> {
+        let (program_text, environment) = self.get_doc_info(&curpos.url)?;
+        let pos = program_text.from_position(&curpos.pos)?;
+        let cst = program_text.cst.dig(pos).into_iter().find(|cst| {
+            [
+                Rule::inline_cmd_name,
+                Rule::block_cmd_name,
+                Rule::math_cmd_name,
+            ]
+            .contains(&cst.rule)
+        })?;
+        let name = program_text.get_text(cst);
+        // module 名で修飾されたコマンドは対象外
+        if name.contains('.') || self.find_component_under_cursor(curpos).is_some() {
+            return None;
+        }
+        // 間接的に読み込まれているパッケージで定義されているものも除く
+        let defined_in_deps = self
+            .get_dependencies_recursive(environment.dependencies())
+            .into_iter()
+            .filter_map(|dep| dep.url.as_ref().and_then(|url| self.get_doc_info(url)))
+            .any(|(_, env)| {
+                let cmds = match cst.rule {
+                    Rule::inline_cmd_name => env.inline_cmds_external(&[]),
+                    Rule::block_cmd_name => env.block_cmds_external(&[]),
+                    _ => env.math_cmds_external(&[]),
+                };
+                cmds.iter().any(|cmd| cmd.name == name)
+            });
+        if defined_in_deps {
+            None
+        } else {
+            Some(cst)
+        }
+    }
+}
+
+/// パースできなかったファイルに対する code action の一覧。
+/// 解析結果がないため、最新のテキストから分かる構文エラーの修正のみを提示する。
+pub fn get_unparsed_code_actions(
+    params: &CodeActionParams,
+    text: &str,
+    errors: &[SyntaxError],
+    config: &Config,
+) -> CodeActionResponse {
+    let url = &params.text_document.uri;
+    let actions = get_unterminated_block_actions(url, &params.range, text, errors, config);
+    filter_by_kind(actions, params.context.only.as_deref())
+}
+
+/// only が指定されていれば、その種類（およびその下位の種類）の code action に絞る。
+fn filter_by_kind(actions: Vec<CodeAction>, only: Option<&[CodeActionKind]>) -> CodeActionResponse {
+    actions
+        .into_iter()
+        .filter(|action| match (only, &action.kind) {
+            (Some(only), Some(kind)) => only.iter().any(|o| is_kind_of(kind, o)),
+            _ => true,
+        })
+        .map(CodeActionOrCommand::CodeAction)
+        .collect()
+}
+
+/// kind が base と同じか、その下位の種類（`source` に対する `source.organizeImports` など）か。
+fn is_kind_of(kind: &CodeActionKind, base: &CodeActionKind) -> bool {
+    let (kind, base) = (kind.as_str(), base.as_str());
+    kind == base || kind.starts_with(&format!("{}.", base))
+}
+
+/// 問題のある行の直前に抑制コメントを挿入する修正。
+fn suppression_fix(problem: &Problem, program_text: &ProgramText) -> Fix {
+    let text = program_text.text.as_str();
+    let start = line_start(text, problem.span.start);
+    let indent: String = text[start..]
+        .chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .collect();
+    Fix {
+        title: format!("Disable `{}` for this line", problem.code),
+        edits: vec![(
+            Span { start, end: start },
+            format!(
+                "{}% satysfi-ls: disable-next-line {}\n",
+                indent, problem.code
+            ),
+        )],
+    }
+}
+
+/// Fix を code action の形に変換する。
+pub fn fix_to_code_action(fix: Fix, url: &Url, program_text: &ProgramText) -> CodeAction {
+    CodeAction {
+        title: fix.title,
+        edit: Some(workspace_edit(url, program_text, fix.edits)),
+        ..Default::default()
+    }
+}
+
+/// Fix を refactor.rewrite の code action に変換する。
+fn to_rewrite(fix: Fix, url: &Url, program_text: &ProgramText) -> CodeAction {
+    let mut action = fix_to_code_action(fix, url, program_text);
+    action.kind = Some(CodeActionKind::REFACTOR_REWRITE);
+    action
+}
+
+/// コマンドの呼び出しに与えられた引数一つ分。
+#[derive(Debug, Clone, Copy)]
+struct CallArg<'a> {
+    /// 引数の CST。
+    cst: &'a Cst,
+    /// `?:` や `!`、括弧を含む引数全体の範囲。
+    span: Span,
+    /// 開き括弧。 `(`, `[`, `{`, `<` のいずれか。
+    open: char,
+    /// `?:` で与えられたオプション引数か。
+    optional: bool,
+    /// 数式コマンドの引数で、 `!` が付いているか。
+    bang: bool,
+}
+
+/// コマンド名 name の呼び出しの CST から引数を読み取る。末尾のセミコロンがあればその位置も返す。
+/// 引数のノードが括弧を含まない場合、その前後の括弧も引数の範囲に含める。
+fn call_arguments<'a>(
+    program_text: &'a ProgramText,
+    name: &Cst,
+) -> (Vec<CallArg<'a>>, Option<Span>) {
+    let text = program_text.text.as_str();
+    let nodes = match program_text.cst.get_parent(name) {
+        Some(call) => call
+            .inner
+            .iter()
+            .filter(|node| node.span.start >= name.span.end)
+            .collect_vec(),
+        None => vec![],
+    };
+    let mut args = vec![];
+    for cst in nodes {
+        let mut start = cst.span.start;
+        let mut end = cst.span.end;
+        let is_bare = !program_text
+            .get_text(cst)
+            .starts_with(&['(', '[', '{', '<', '?', '!'][..]);
+        if is_bare {
+            start = text[..start].trim_end().len().saturating_sub(1);
+            let after = &text[end..];
+            end = (end + after.len() - after.trim_start().len() + 1).min(text.len());
+        }
+        // 直前の `?:` や `!` も引数に含める
+        let before = text[..start].trim_end();
+        if before.ends_with("?:") {
+            start = before.len() - 2;
+        } else if before.ends_with('!') {
+            start = before.len() - 1;
+        }
+        let written = &text[start..end];
+        let (optional, written) = match written.strip_prefix("?:") {
+            Some(rest) => (true, rest.trim_start()),
+            None => (false, written),
+        };
+        let (bang, written) = match written.strip_prefix('!') {
+            Some(rest) => (true, rest.trim_start()),
+            None => (false, written),
+        };
+        let open = match written.chars().next() {
+            Some(c @ ('(' | '[' | '{' | '<')) => c,
+            _ => continue,
+        };
+        args.push(CallArg {
+            cst,
+            span: Span { start, end },
+            open,
+            optional,
+            bang,
+        });
+    }
+
+    let last = args.last().map_or(name.span.end, |arg| arg.span.end);
+    let after = &text[last..];
+    let semicolon = after.trim_start().starts_with(';').then(|| {
+        let start = last + after.len() - after.trim_start().len();
+        Span {
+            start,
+            end: start + 1,
+        }
+    });
+    (args, semicolon)
+}
+
+/// 一つのファイルに対する編集から WorkspaceEdit を作る。
+/// 範囲がテキストの外にある編集は含めない。
+pub fn workspace_edit(
+    url: &Url,
+    program_text: &ProgramText,
+    edits: Vec<(Span, String)>,
+) -> WorkspaceEdit {
+    let edits = edits
+        .into_iter()
+        .filter_map(|(span, new_text)| {
+            Some(TextEdit {
+                range: program_text.get_range(span)?,
+                new_text,
+            })
+        })
+        .collect();
+    let mut changes = HashMap::new();
+    changes.insert(url.clone(), edits);
+    WorkspaceEdit {
+        changes: Some(changes),
+        ..Default::default()
+    }
+}
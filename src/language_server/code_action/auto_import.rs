@@ -0,0 +1,50 @@
+//! 定義の見つからないコマンドについて、それを定義しているパッケージを
+//! `@require:` する code action。
+
+use lspower::lsp::{CodeAction, CodeActionKind};
+
+use crate::{
+    documents::{package_dirs, DocumentCache, PackageIndex},
+    util::UrlPos,
+};
+
+use super::super::diagnostics::Fix;
+use super::edit::require_header_insertion;
+use super::fix_to_code_action;
+
+impl DocumentCache {
+    /// カーソル下の未定義のコマンドを公開しているパッケージごとに、
+    /// そのパッケージを `@require:` する code action を返す。
+    pub(super) fn get_auto_import_actions(
+        &self,
+        curpos: &UrlPos,
+        packages: &PackageIndex,
+    ) -> Vec<CodeAction> {
+        let cst = match self.find_unresolved_command(curpos) {
+            Some(cst) => cst,
+            None => return vec![],
+        };
+        let (program_text, _) = self.get_doc_info(&curpos.url).unwrap();
+        let name = program_text.get_text(cst);
+
+        let candidates: Vec<_> = packages
+            .packages(&package_dirs(&curpos.url))
+            .into_iter()
+            .filter(|pkg| pkg.url != curpos.url && pkg.find_command(cst.rule, name).is_some())
+            .collect();
+        let is_preferred = candidates.len() == 1;
+        candidates
+            .into_iter()
+            .map(|pkg| {
+                let fix = Fix {
+                    title: format!("Add `@require: {}`", pkg.name),
+                    edits: vec![require_header_insertion(&program_text.text, &pkg.name)],
+                };
+                let mut action = fix_to_code_action(fix, &curpos.url, program_text);
+                action.kind = Some(CodeActionKind::QUICKFIX);
+                action.is_preferred = Some(is_preferred);
+                action
+            })
+            .collect()
+    }
+}
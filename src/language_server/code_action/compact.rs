@@ -0,0 +1,195 @@
+//! コマンドの引数の省略形（`\cmd{foo}`）と括弧つきの形（`\cmd({foo});`）を相互に変換する
+//! code action。
+
+use itertools::Itertools;
+use lspower::lsp::CodeAction;
+use satysfi_parser::{structure::ProgramText, Cst, Rule, Span};
+
+use crate::{
+    documents::{ComponentBody, DocumentCache},
+    util::{ConvertPosition, UrlPos},
+};
+
+use super::super::completion::is_compactible_type;
+use super::super::diagnostics::Fix;
+use super::{call_arguments, to_rewrite, CallArg};
+
+impl DocumentCache {
+    /// カーソル下のコマンドの適用について、末尾のテキスト引数を省略形にするものと、
+    /// 省略形の引数を括弧つきの形にするものを返す。
+    pub(super) fn get_compact_actions(&self, curpos: &UrlPos) -> Vec<CodeAction> {
+        let (program_text, _) = match self.get_doc_info(&curpos.url) {
+            Some(info) => info,
+            None => return vec![],
+        };
+        let pos = match program_text.from_position(&curpos.pos) {
+            Some(pos) => pos,
+            None => return vec![],
+        };
+        let cmd = program_text
+            .cst
+            .dig(pos)
+            .into_iter()
+            .find(|cst| [Rule::inline_cmd_name, Rule::block_cmd_name].contains(&cst.rule));
+        let cmd = match cmd {
+            Some(cmd) => cmd,
+            None => return vec![],
+        };
+        // 定義側のコマンド名は対象外
+        let is_definition = program_text.cst.get_parent(cmd).map_or(false, |parent| {
+            [
+                Rule::let_inline_stmt_ctx,
+                Rule::let_inline_stmt_noctx,
+                Rule::let_block_stmt_ctx,
+                Rule::let_block_stmt_noctx,
+            ]
+            .contains(&parent.rule)
+        });
+        if is_definition {
+            return vec![];
+        }
+        let (args, semicolon) = call_arguments(program_text, cmd);
+        // 型が分かれば、省略形で書けない型の必須引数を省略形にしない
+        let type_args = self
+            .find_component_under_cursor(curpos)
+            .and_then(|(_, component)| match &component.body {
+                ComponentBody::InlineCmd { type_args, .. }
+                | ComponentBody::BlockCmd { type_args, .. } => Some(type_args),
+                _ => None,
+            })
+            .map(|type_args| {
+                type_args
+                    .iter()
+                    .filter(|ty| !ty.trim().ends_with('?'))
+                    .map(|ty| is_compactible_type(ty))
+                    .collect_vec()
+            });
+
+        vec![
+            to_compact(&args, semicolon, type_args.as_deref(), program_text),
+            to_parenthesized(&args, semicolon),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|fix| to_rewrite(fix, &curpos.url, program_text))
+        .collect()
+    }
+}
+
+/// 末尾に並ぶテキストリテラルの括弧つき引数を省略形に直し、セミコロンを取り除く。
+/// compactible は必須引数のそれぞれについて、その型が省略形で書けるものか。
+fn to_compact(
+    args: &[CallArg],
+    semicolon: Option<Span>,
+    compactible: Option<&[bool]>,
+    program_text: &ProgramText,
+) -> Option<Fix> {
+    let required = args.iter().filter(|arg| !arg.optional).collect_vec();
+    let compacted = |idx: usize, arg: &CallArg| -> Option<String> {
+        if arg.open != '(' || compactible.and_then(|c| c.get(idx)) == Some(&false) {
+            return None;
+        }
+        text_literal(arg, program_text).map(ToOwned::to_owned)
+    };
+    // 省略形にできるのは末尾に連続するテキスト引数のみ
+    let compact_from = required
+        .iter()
+        .enumerate()
+        .rposition(|(idx, arg)| arg.open != '{' && arg.open != '<' && compacted(idx, arg).is_none())
+        .map(|idx| idx + 1)
+        .unwrap_or(0);
+    let mut edits = required
+        .iter()
+        .enumerate()
+        .skip(compact_from)
+        .filter_map(|(idx, arg)| Some((arg.span, compacted(idx, arg)?)))
+        .collect_vec();
+    if edits.is_empty() {
+        return None;
+    }
+    if let Some(semicolon) = semicolon {
+        let last = args.last()?.span.end;
+        edits.push((
+            Span {
+                start: last,
+                end: semicolon.end,
+            },
+            String::new(),
+        ));
+    }
+    Some(Fix {
+        title: "Use compact form for text arguments".to_owned(),
+        edits,
+    })
+}
+
+/// 括弧つきの引数 `({...})` や `('<...>)` の中身が一つのテキストリテラルであれば、
+/// それを省略形にしたもの（`{...}` や `<...>`）。
+/// 括弧の中身全体にわたる最も深いノードが、その内側にのみ子を持つものをリテラルとみなす。
+fn text_literal<'a>(arg: &CallArg, program_text: &'a ProgramText) -> Option<&'a str> {
+    let text = program_text.text.as_str();
+    let written = &text[arg.span.start + 1..arg.span.end - 1];
+    let start = arg.span.start + 1 + (written.len() - written.trim_start().len());
+    let inner = Span {
+        start,
+        end: start + written.trim().len(),
+    };
+    let literal = &text[inner.start..inner.end];
+    let compact = if literal.starts_with('{') && literal.ends_with('}') {
+        literal
+    } else if literal.starts_with("'<") && literal.ends_with('>') {
+        &literal[1..]
+    } else {
+        return None;
+    };
+
+    let mut deepest: Option<&Cst> = None;
+    let mut stack = vec![arg.cst];
+    while let Some(node) = stack.pop() {
+        if node.span == inner {
+            deepest = Some(node);
+        }
+        stack.extend(
+            node.inner
+                .iter()
+                .filter(|child| child.span.start <= inner.start && inner.end <= child.span.end),
+        );
+    }
+    let is_literal = deepest?
+        .inner
+        .iter()
+        .all(|child| inner.start < child.span.start && child.span.end < inner.end);
+    if is_literal {
+        Some(compact)
+    } else {
+        None
+    }
+}
+
+/// 省略形の引数を括弧つきの形に直し、末尾にセミコロンを付ける。
+fn to_parenthesized(args: &[CallArg], semicolon: Option<Span>) -> Option<Fix> {
+    let at = |pos: usize| Span {
+        start: pos,
+        end: pos,
+    };
+    let mut edits = vec![];
+    for arg in args {
+        let open = match arg.open {
+            '{' => "(",
+            '<' => "('",
+            _ => continue,
+        };
+        edits.push((at(arg.span.start), open.to_owned()));
+        edits.push((at(arg.span.end), ")".to_owned()));
+    }
+    if edits.is_empty() {
+        return None;
+    }
+    if semicolon.is_none() {
+        edits.push((at(args.last()?.span.end), ";".to_owned()));
+    }
+    Some(Fix {
+        title: "Use parenthesized form for text arguments".to_owned(),
+        edits,
+    })
+}
@@ -0,0 +1,310 @@
+//! 定義の見つからないコマンドについて、その定義の雛形を挿入する code action。
+
+use itertools::Itertools;
+use lspower::lsp::{CodeAction, CodeActionKind, Url};
+use satysfi_parser::{
+    structure::{Program, ProgramText, Statement},
+    Cst, Rule, Span,
+};
+
+use crate::{documents::DocumentCache, util::UrlPos};
+
+use super::super::diagnostics::Fix;
+use super::edit::{command_stub, insert_before_end, statement_end};
+use super::signature::signature_span;
+use super::{call_arguments, fix_to_code_action};
+
+/// 呼び出し箇所から推定したコマンドの引数の種類。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArgKind {
+    /// `(...)` で与えられる式。
+    Expr,
+    /// `{...}` で与えられるインラインテキスト。
+    InlineText,
+    /// `{|...|...|}` で与えられるインラインテキストのリスト。
+    InlineTextList,
+    /// `<...>` で与えられるブロックテキスト。
+    BlockText,
+    /// 数式コマンドに `{...}` で与えられる数式。
+    Math,
+}
+
+impl ArgKind {
+    fn param_name(self) -> &'static str {
+        match self {
+            ArgKind::Expr => "x",
+            ArgKind::InlineText => "it",
+            ArgKind::InlineTextList => "its",
+            ArgKind::BlockText => "bt",
+            ArgKind::Math => "m",
+        }
+    }
+}
+
+/// 定義するコマンドの種類。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CommandKind {
+    Inline,
+    Block,
+    Math,
+}
+
+impl DocumentCache {
+    /// カーソル下の未定義のコマンドについて、プリアンブルまたは各 module に
+    /// その定義を追加する code action を返す。
+    pub(super) fn get_define_command_actions(&self, curpos: &UrlPos) -> Vec<CodeAction> {
+        let cst = match self.find_unresolved_command(curpos) {
+            Some(cst) => cst,
+            None => return vec![],
+        };
+        let (program_text, _) = self.get_doc_info(&curpos.url).unwrap();
+        let name = program_text.get_text(cst);
+        let kind = match cst.rule {
+            Rule::inline_cmd_name => CommandKind::Inline,
+            Rule::block_cmd_name => CommandKind::Block,
+            _ => CommandKind::Math,
+        };
+        let args = call_site_args(program_text, cst, kind == CommandKind::Math);
+
+        let mut fixes = vec![];
+        fixes.extend(define_in_preamble(
+            name,
+            kind,
+            &args,
+            cst.span.start,
+            program_text,
+        ));
+        let preamble = match &program_text.structure {
+            Ok(Program::Saty { preamble, .. }) => preamble,
+            Ok(Program::Satyh { preamble, .. }) => preamble,
+            Err(_) => return vec![],
+        };
+        for stmt in preamble {
+            if let Statement::Module { name: mod_name, .. } = stmt {
+                fixes.extend(define_in_module(name, kind, &args, mod_name, program_text));
+            }
+        }
+
+        fixes
+            .into_iter()
+            .map(|fix| to_quickfix(fix, &curpos.url, program_text))
+            .collect()
+    }
+}
+
+fn to_quickfix(fix: Fix, url: &Url, program_text: &ProgramText) -> CodeAction {
+    let mut action = fix_to_code_action(fix, url, program_text);
+    action.kind = Some(CodeActionKind::QUICKFIX);
+    action
+}
+
+/// 呼び出しの CST の引数のノードから、引数の種類を推定する。
+/// `?:` で与えられるオプション引数は定義に含めない。
+fn call_site_args(program_text: &ProgramText, name: &Cst, is_math: bool) -> Vec<ArgKind> {
+    let text = program_text.text.as_str();
+    let (args, _) = call_arguments(program_text, name);
+    args.into_iter()
+        .filter(|arg| !arg.optional)
+        .map(|arg| {
+            let written = text[arg.span.start..arg.span.end].trim_start_matches('!');
+            match arg.open {
+                '{' if written
+                    .trim_start_matches('{')
+                    .trim_start()
+                    .starts_with('|') =>
+                {
+                    ArgKind::InlineTextList
+                }
+                // 数式コマンドでは `!` の付いた引数が数式以外のものになる
+                '{' if is_math && !arg.bang => ArgKind::Math,
+                '{' => ArgKind::InlineText,
+                '<' => ArgKind::BlockText,
+                _ => ArgKind::Expr,
+            }
+        })
+        .collect()
+}
+
+/// open の位置にある開き括弧に対応する閉じ括弧の位置。
+/// バックスラッシュによるエスケープと `%` から行末までのコメントは読み飛ばす。
+pub(super) fn matching_close(
+    text: &str,
+    open: usize,
+    open_char: char,
+    close_char: char,
+) -> Option<usize> {
+    let mut depth = 0;
+    let mut chars = text[open..].char_indices();
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '%' => {
+                for (_, c) in &mut chars {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            c if c == open_char => depth += 1,
+            c if c == close_char => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + idx);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// 引数の種類から仮引数名の列を作る。同じ種類が複数あれば番号を振る。
+fn param_names(args: &[ArgKind]) -> Vec<String> {
+    args.iter()
+        .enumerate()
+        .map(|(idx, &arg)| {
+            let count = args.iter().filter(|&&a| a == arg).count();
+            if count > 1 {
+                let nth = args[..idx].iter().filter(|&&a| a == arg).count() + 1;
+                format!("{}{}", arg.param_name(), nth)
+            } else {
+                arg.param_name().to_owned()
+            }
+        })
+        .collect()
+}
+
+/// `let-inline ctx \cmd it = inline-nil` のような定義の雛形。
+fn stub_definition(name: &str, kind: CommandKind, args: &[ArgKind]) -> String {
+    let keyword = match kind {
+        CommandKind::Inline => "let-inline",
+        CommandKind::Block => "let-block",
+        CommandKind::Math => "let-math",
+    };
+    command_stub(keyword, name, &param_names(args))
+}
+
+/// `direct \cmd : [inline-text] inline-cmd` のような signature の宣言。
+/// 式の引数の型は分からないので型変数とする。
+fn direct_declaration(name: &str, kind: CommandKind, args: &[ArgKind]) -> String {
+    let mut type_vars = (b'a'..=b'z').map(|c| format!("'{}", c as char));
+    let types = args
+        .iter()
+        .map(|arg| match arg {
+            ArgKind::Expr => type_vars.next().unwrap_or_else(|| "'a".to_owned()),
+            ArgKind::InlineText => "inline-text".to_owned(),
+            ArgKind::InlineTextList => "inline-text list".to_owned(),
+            ArgKind::BlockText => "block-text".to_owned(),
+            ArgKind::Math => "math".to_owned(),
+        })
+        .join("; ");
+    let cmd_type = match kind {
+        CommandKind::Inline => "inline-cmd",
+        CommandKind::Block => "block-cmd",
+        CommandKind::Math => "math-cmd",
+    };
+    format!("direct {} : [{}] {}", name, types, cmd_type)
+}
+
+/// プリアンブルに定義を追加する。
+fn define_in_preamble(
+    name: &str,
+    kind: CommandKind,
+    args: &[ArgKind],
+    used_at: usize,
+    program_text: &ProgramText,
+) -> Option<Fix> {
+    let stub = stub_definition(name, kind, args);
+    Some(Fix {
+        title: format!("Define `{}` in the preamble", name),
+        edits: vec![preamble_insertion(&stub, used_at, program_text)?],
+    })
+}
+
+/// プリアンブルに文を挿入する位置と挿入するテキスト。
+/// used_at を含む文がプリアンブルにあればその直前に、なければプリアンブルの末尾に挿入する。
+pub(super) fn preamble_insertion(
+    stmt: &str,
+    used_at: usize,
+    program_text: &ProgramText,
+) -> Option<(Span, String)> {
+    let text = program_text.text.as_str();
+    let (is_saty, preamble) = match &program_text.structure {
+        Ok(Program::Saty { preamble, .. }) => (true, preamble),
+        Ok(Program::Satyh { preamble, .. }) => (false, preamble),
+        Err(_) => return None,
+    };
+    let ends = preamble
+        .iter()
+        .map(|stmt| statement_end(stmt, program_text))
+        .collect::<Option<Vec<_>>>()?;
+    let idx = ends
+        .iter()
+        .position(|&end| end > used_at)
+        .unwrap_or(ends.len());
+
+    let (pos, new_text) = if idx > 0 {
+        (ends[idx - 1], format!("\n\n{}", stmt))
+    } else if idx < ends.len() || is_saty {
+        // ヘッダの直後、最初の文（プリアンブルがなければ文書本体）の前に挿入する
+        let mut offset = 0;
+        let body_start = text
+            .split('\n')
+            .map(|line| {
+                let start = offset;
+                offset += line.len() + 1;
+                (start, line.trim())
+            })
+            .find(|(_, line)| !(line.is_empty() || line.starts_with('@') || line.starts_with('%')))
+            .map(|(start, _)| start)?;
+        if idx < ends.len() {
+            (body_start, format!("{}\n\n", stmt))
+        } else {
+            (body_start, format!("{}\nin\n\n", stmt))
+        }
+    } else {
+        let sep = if text.ends_with('\n') { "\n" } else { "\n\n" };
+        (text.len(), format!("{}{}\n", sep, stmt))
+    };
+    Some((
+        Span {
+            start: pos,
+            end: pos,
+        },
+        new_text,
+    ))
+}
+
+/// module の struct の末尾に定義を、 sig の末尾に `direct` の宣言を追加する。
+/// sig を持たない module には追加しない。
+fn define_in_module(
+    name: &str,
+    kind: CommandKind,
+    args: &[ArgKind],
+    mod_name: &Cst,
+    program_text: &ProgramText,
+) -> Option<Fix> {
+    let text = program_text.text.as_str();
+    let module_span = program_text.cst.get_parent(mod_name)?.span;
+    let struct_end = module_span.end.checked_sub(3)?;
+    if text.get(struct_end..module_span.end) != Some("end") {
+        return None;
+    }
+    let sig_end = signature_span(mod_name, program_text)?.end - 3;
+
+    let stub = stub_definition(name, kind, args);
+    let decl = direct_declaration(name, kind, args);
+    Some(Fix {
+        title: format!(
+            "Define `{}` in module `{}`",
+            name,
+            program_text.get_text(mod_name)
+        ),
+        edits: vec![
+            insert_before_end(text, sig_end, &decl),
+            insert_before_end(text, struct_end, &stub),
+        ],
+    })
+}
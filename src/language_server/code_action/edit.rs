@@ -0,0 +1,131 @@
+//! 複数の code action や補完で共有する、編集内容を組み立てる関数。
+
+use itertools::Itertools;
+use satysfi_parser::{
+    structure::{LetRecInner, ProgramText, Statement, TypeInner},
+    Span,
+};
+
+use crate::util::{line_end, line_start};
+
+/// `@require: pkg` のヘッダを挿入する位置と挿入するテキスト。
+/// 既存の `@require:` の中で名前順になる位置に挿入する。
+/// `@require:` がなければ `@import:` の前、それもなければ他のヘッダの後に挿入する。
+pub fn require_header_insertion(text: &str, pkg: &str) -> (Span, String) {
+    let mut requires = vec![];
+    let mut first_import = None;
+    let mut headers_end = None;
+    let mut offset = 0;
+    for line in text.split('\n') {
+        let start = offset;
+        offset += line.len() + 1;
+        let trimmed = line.trim();
+        if let Some(name) = trimmed.strip_prefix("@require:") {
+            requires.push((start, name.trim()));
+        } else if trimmed.starts_with("@import:") {
+            first_import = first_import.or(Some(start));
+        } else if !(trimmed.starts_with('@') || trimmed.is_empty() || trimmed.starts_with('%')) {
+            break;
+        }
+        if trimmed.starts_with('@') {
+            headers_end = Some(line_end(text, start));
+        }
+    }
+
+    let header = format!("@require: {}\n", pkg);
+    let at = |pos: usize| Span {
+        start: pos,
+        end: pos,
+    };
+    if let Some(&(start, _)) = requires.iter().find(|&&(_, name)| name > pkg) {
+        return (at(start), header);
+    }
+    let pos = match (requires.last(), first_import, headers_end) {
+        (Some(&(start, _)), _, _) => line_end(text, start),
+        (None, Some(start), _) => return (at(start), header),
+        (None, None, Some(end)) => end,
+        (None, None, None) => return (at(0), format!("{}\n", header)),
+    };
+    if pos == text.len() && !text.ends_with('\n') {
+        (at(pos), format!("\n{}", header.trim_end()))
+    } else {
+        (at(pos), header)
+    }
+}
+
+/// 文の終わりの位置。
+pub fn statement_end(stmt: &Statement, program_text: &ProgramText) -> Option<usize> {
+    let end = match stmt {
+        Statement::Let { expr, .. }
+        | Statement::LetInline { expr, .. }
+        | Statement::LetBlock { expr, .. }
+        | Statement::LetMath { expr, .. }
+        | Statement::LetMutable { expr, .. } => expr.span.end,
+        Statement::LetRec(inners) => {
+            let LetRecInner { pattern, .. } = inners.last()?;
+            program_text.cst.get_parent(pattern)?.span.end
+        }
+        Statement::Type(inners) => {
+            let TypeInner { name, .. } = inners.last()?;
+            program_text.cst.get_parent(name)?.span.end
+        }
+        Statement::Module { name, .. } => program_text.cst.get_parent(name)?.span.end,
+        Statement::Open(cst) => cst.span.end,
+    };
+    Some(end)
+}
+
+/// `let-inline ctx \cmd it = inline-nil` のような、コマンドの定義の雛形。
+/// keyword には `let-inline`, `let-block`, `let-math` のいずれかを指定する。
+pub fn command_stub(keyword: &str, name: &str, params: &[String]) -> String {
+    let params = params.iter().map(|param| format!(" {}", param)).join("");
+    match keyword {
+        "let-inline" => format!("let-inline ctx {}{} = inline-nil", name, params),
+        "let-block" => format!("let-block ctx {}{} = block-nil", name, params),
+        _ => format!("let-math {}{} = ${{}}", name, params),
+    }
+}
+
+/// `end` キーワードの直前に一行追加する。
+/// `end` が行頭にあればその一段深いインデントで、そうでなければ同じ行に挿入する。
+pub fn insert_before_end(text: &str, end_kw: usize, content: &str) -> (Span, String) {
+    let start = line_start(text, end_kw);
+    let indent = &text[start..end_kw];
+    if indent.trim().is_empty() {
+        (
+            Span { start, end: start },
+            format!("{}  {}\n", indent, content),
+        )
+    } else {
+        (
+            Span {
+                start: end_kw,
+                end: end_kw,
+            },
+            format!("{} ", content),
+        )
+    }
+}
+
+/// pos を含む行の直前に続く `%` コメントの行を、ドキュメントコメントとして取り出す。
+/// `% satysfi-ls:` で始まる指示コメントは含めない。
+pub fn doc_comment(text: &str, pos: usize) -> Option<String> {
+    let mut lines = vec![];
+    let mut start = line_start(text, pos);
+    while start > 0 {
+        let prev = line_start(text, start - 1);
+        let line = text[prev..start].trim();
+        match line.strip_prefix('%') {
+            Some(comment) if !line.starts_with("% satysfi-ls:") => {
+                lines.push(comment.trim_start_matches('%').trim());
+                start = prev;
+            }
+            _ => break,
+        }
+    }
+    if lines.is_empty() {
+        return None;
+    }
+    lines.reverse();
+    Some(lines.join("\n"))
+}
@@ -0,0 +1,278 @@
+//! 選択範囲のテキストを新しいコマンドとして切り出す code action。
+
+use std::collections::HashSet;
+
+use itertools::Itertools;
+use lspower::lsp::{CodeAction, CodeActionKind, Range, Url};
+use satysfi_parser::{
+    structure::{Program, ProgramText, Statement},
+    Cst, Mode, Rule, Span,
+};
+
+use crate::{
+    documents::{Component, ComponentBody, DocumentCache, DocumentData, Environment},
+    util::{ConvertPosition, UrlPos},
+};
+
+use super::super::diagnostics::Fix;
+use super::define_command::preamble_insertion;
+use super::fix_to_code_action;
+
+impl DocumentCache {
+    /// 選択されたインラインテキスト（ブロックテキスト）を `let-inline`（`let-block`）
+    /// として切り出し、選択範囲をその呼び出しに置き換える。
+    /// 選択範囲内で使われているローカルな変数はコマンドの引数にする。
+    /// module の中から切り出す場合、 module 内で定義された変数も引数にする。
+    /// module 内で定義されたコマンドを使っていれば、プリアンブルからは呼べないので切り出さない。
+    pub(super) fn get_extract_action(&self, url: &Url, range: &Range) -> Option<CodeAction> {
+        let (program_text, environment) = self.get_doc_info(url)?;
+        let (span, is_inline) = text_selection(program_text, range)?;
+
+        let module = enclosing_module(program_text, span.start);
+        let is_module_local = |component: &Component| match module {
+            Some(module) => {
+                &component.url == url
+                    && module.start <= component.pos_definition.start
+                    && component.pos_definition.end <= module.end
+            }
+            None => false,
+        };
+        let uses_local_command = program_text
+            .cst
+            .pickup(Rule::inline_cmd_name)
+            .into_iter()
+            .chain(program_text.cst.pickup(Rule::block_cmd_name))
+            .chain(program_text.cst.pickup(Rule::math_cmd_name))
+            .filter(|cmd| span.start <= cmd.span.start && cmd.span.end <= span.end)
+            .filter_map(|cmd| self.component_at_span(url, cmd.span, program_text))
+            .any(&is_module_local);
+        if uses_local_command {
+            return None;
+        }
+
+        let params = self.free_variables(url, span, program_text, &is_module_local);
+        let name = self.fresh_command_name(environment, is_inline);
+        let selected = program_text.get_text_from_span(span);
+        let param_list = params.iter().map(|p| format!(" {}", p)).join("");
+        let arg_list = params.iter().map(|p| format!("({})", p)).join("");
+        let definition = if is_inline {
+            format!(
+                "let-inline ctx {}{} = read-inline ctx {{{}}}",
+                name, param_list, selected
+            )
+        } else {
+            format!(
+                "let-block ctx {}{} = read-block ctx '<{}>",
+                name, param_list, selected
+            )
+        };
+        let call = format!("{}{};", name, arg_list);
+
+        let fix = Fix {
+            title: if is_inline {
+                format!("Extract into inline command `{}`", name)
+            } else {
+                format!("Extract into block command `{}`", name)
+            },
+            edits: vec![
+                preamble_insertion(&definition, span.start, program_text)?,
+                (span, call),
+            ],
+        };
+        let mut action = fix_to_code_action(fix, url, program_text);
+        action.kind = Some(CodeActionKind::REFACTOR_EXTRACT);
+        Some(action)
+    }
+
+    /// 範囲内で使われている変数のうち、範囲の外にあるローカルな束縛を指すもの。
+    /// is_local が真となるコンポーネントを指すものも含める。
+    /// トップレベルや依存パッケージで定義されたもの、プリミティブ、範囲内で束縛されたものは除く。
+    /// 出現順に重複なく返す。
+    fn free_variables<'a>(
+        &self,
+        url: &Url,
+        span: Span,
+        program_text: &'a ProgramText,
+        is_local: impl Fn(&Component) -> bool,
+    ) -> Vec<&'a str> {
+        program_text
+            .cst
+            .pickup(Rule::var)
+            .into_iter()
+            .filter(|var| span.start <= var.span.start && var.span.end <= span.end)
+            .filter(|var| binding_scope(&program_text.cst, var).is_none())
+            .filter(|var| match local_binding(program_text, var) {
+                Some(binding) => !span.includes(binding.span.start),
+                None => self
+                    .component_at_span(url, var.span, program_text)
+                    .map_or(false, &is_local),
+            })
+            .map(|var| program_text.get_text(var))
+            .unique()
+            .collect()
+    }
+
+    /// span の位置にある変数やコマンドが指すコンポーネント。
+    fn component_at_span(
+        &self,
+        url: &Url,
+        span: Span,
+        program_text: &ProgramText,
+    ) -> Option<&Component> {
+        let curpos = UrlPos {
+            url: url.clone(),
+            pos: program_text.get_position(span.start)?,
+        };
+        self.find_component_under_cursor(&curpos)
+            .map(|(_, component)| component)
+    }
+
+    /// 既存のコマンドと重ならない `\new-cmd`（`+new-cmd`）の形の名前。
+    /// そのファイルと、その依存先のすべてのファイルで定義されている名前を避ける。
+    fn fresh_command_name(&self, environment: &Environment, is_inline: bool) -> String {
+        let sigil = if is_inline { "\\" } else { "+" };
+        let mut existing = HashSet::new();
+        component_names(environment.components(), &mut existing);
+        for dep in self.get_dependencies_recursive(environment.dependencies()) {
+            if let Some(DocumentData::Parsed { environment, .. }) =
+                dep.url.as_ref().and_then(|url| self.get(url))
+            {
+                component_names(environment.components(), &mut existing);
+            }
+        }
+        (0..)
+            .map(|idx| {
+                if idx == 0 {
+                    format!("{}new-cmd", sigil)
+                } else {
+                    format!("{}new-cmd{}", sigil, idx)
+                }
+            })
+            .find(|name| !existing.contains(name.as_str()))
+            .unwrap()
+    }
+}
+
+/// 束縛される変数が直接置かれる文。
+const LET_RULES: &[Rule] = &[
+    Rule::let_stmt,
+    Rule::let_inline_stmt_ctx,
+    Rule::let_inline_stmt_noctx,
+    Rule::let_block_stmt_ctx,
+    Rule::let_block_stmt_noctx,
+    Rule::let_math_stmt,
+];
+
+/// 変数の使用箇所 var を束縛している変数。
+/// var より前にある同じ名前の束縛のうち、その有効範囲が var を含む最も内側のもの。
+/// ローカルな束縛が見つからなければ None。
+fn local_binding<'a>(program_text: &'a ProgramText, var: &Cst) -> Option<&'a Cst> {
+    let name = program_text.get_text(var);
+    program_text
+        .cst
+        .pickup(Rule::var)
+        .into_iter()
+        .filter(|binding| binding.span.end <= var.span.start)
+        .filter(|binding| program_text.get_text(binding) == name)
+        .filter(|binding| {
+            binding_scope(&program_text.cst, binding)
+                .map_or(false, |scope| scope.includes(var.span.start))
+        })
+        .max_by_key(|binding| binding.span.start)
+}
+
+/// var が束縛の位置にあれば、その束縛が有効な範囲（を含む範囲）。
+/// パターン中の変数と、 `let-inline ctx \cmd x` の ctx や x のように
+/// let 文に直接置かれた変数を束縛とみなす。
+/// 有効範囲は束縛を含む構文（let 文、 fun 式、 match の節など）の親全体で近似する。
+fn binding_scope(cst: &Cst, var: &Cst) -> Option<Span> {
+    let parent = cst.get_parent(var)?;
+    let construct = if LET_RULES.contains(&parent.rule) {
+        // 定義の本体そのものが変数である場合は束縛ではない
+        if parent.inner.last().map(|body| body.span) == Some(var.span) {
+            return None;
+        }
+        parent
+    } else {
+        let mut pattern = std::iter::successors(Some(parent), |node| cst.get_parent(node))
+            .find(|node| node.rule == Rule::pattern)?;
+        while let Some(outer) = cst.get_parent(pattern).filter(|p| p.rule == Rule::pattern) {
+            pattern = outer;
+        }
+        cst.get_parent(pattern)?
+    };
+    Some(cst.get_parent(construct).unwrap_or(construct).span)
+}
+
+/// module の中で定義されているものも含めた、コンポーネントの名前。
+fn component_names<'a>(components: &'a [Component], names: &mut HashSet<&'a str>) {
+    for component in components {
+        names.insert(component.name.as_str());
+        if let ComponentBody::Module { components } = &component.body {
+            component_names(components, names);
+        }
+    }
+}
+
+/// pos を含む module 全体の範囲。
+fn enclosing_module(program_text: &ProgramText, pos: usize) -> Option<Span> {
+    let preamble = match &program_text.structure {
+        Ok(Program::Saty { preamble, .. }) => preamble,
+        Ok(Program::Satyh { preamble, .. }) => preamble,
+        Err(_) => return None,
+    };
+    preamble.iter().find_map(|stmt| match stmt {
+        Statement::Module { name, .. } => program_text
+            .cst
+            .get_parent(name)
+            .map(|module| module.span)
+            .filter(|span| span.start <= pos && pos < span.end),
+        _ => None,
+    })
+}
+
+/// 選択範囲の前後の空白を除いた範囲と、それがインラインテキストかどうか。
+/// 選択範囲がインラインテキストまたはブロックテキストとしてまとまっていなければ None。
+pub(super) fn text_selection(program_text: &ProgramText, range: &Range) -> Option<(Span, bool)> {
+    let text = program_text.text.as_str();
+    let span = {
+        let start = program_text.from_position(&range.start)?;
+        let end = program_text.from_position(&range.end)?;
+        let selected = &text[start..end];
+        let start = start + (selected.len() - selected.trim_start().len());
+        Span {
+            start,
+            end: start + selected.trim().len(),
+        }
+    };
+    if span.start >= span.end {
+        return None;
+    }
+
+    let last_char = text[..span.end].char_indices().last()?.0;
+    let is_inline = match (
+        program_text.cst.mode(span.start),
+        program_text.cst.mode(last_char),
+    ) {
+        (Mode::Horizontal, Mode::Horizontal) => true,
+        (Mode::Vertical, Mode::Vertical) => false,
+        _ => return None,
+    };
+    // 構文上のまとまりを途中で切るような選択範囲は対象外
+    if cuts_through(&program_text.cst, span) {
+        return None;
+    }
+    Some((span, is_inline))
+}
+
+/// span の境界がいずれかの CST の途中にあるか。
+fn cuts_through(cst: &Cst, span: Span) -> bool {
+    let crosses = |c: &Cst| {
+        (c.span.start < span.start && span.start < c.span.end && c.span.end < span.end)
+            || (span.start < c.span.start && c.span.start < span.end && span.end < c.span.end)
+    };
+    cst.dig(span.start)
+        .into_iter()
+        .chain(cst.dig(span.end))
+        .any(crosses)
+}
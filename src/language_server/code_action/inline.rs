@@ -0,0 +1,233 @@
+//! 変数や引数のないコマンドの定義を使用箇所に展開する code action。
+
+use lspower::lsp::{CodeAction, CodeActionKind, Url};
+use satysfi_parser::{
+    structure::{Program, ProgramText},
+    Cst, Rule, Span,
+};
+
+use crate::{
+    documents::{Component, ComponentBody, DocumentCache, Environment, Visibility},
+    util::{expand_to_lines, ConvertPosition, UrlPos},
+};
+
+use super::super::diagnostics::Fix;
+use super::define_command::matching_close;
+use super::fix_to_code_action;
+
+impl DocumentCache {
+    /// カーソル下の `let` 変数または引数のない `let-inline` コマンドについて、
+    /// その使用箇所を定義の中身で置き換える。すべて置き換えられたら定義を削除する。
+    /// ただし、他のファイルや sig から参照されうる定義は残す。
+    pub(super) fn get_inline_action(&self, curpos: &UrlPos) -> Option<CodeAction> {
+        let (program_text, environment) = self.get_doc_info(&curpos.url)?;
+        let pos = program_text.from_position(&curpos.pos)?;
+        // 使用箇所でも定義箇所でも実行できるようにする
+        let component = self
+            .find_component_under_cursor(curpos)
+            .map(|(_, component)| component)
+            .or_else(|| {
+                environment
+                    .components()
+                    .iter()
+                    .find(|c| c.pos_definition.includes(pos))
+            })
+            .filter(|c| c.url == curpos.url)?;
+
+        let (stmt, replacement) = match component.body {
+            ComponentBody::Variable { .. } => variable_definition(component, program_text)?,
+            ComponentBody::InlineCmd { .. } => inline_cmd_definition(component, program_text)?,
+            _ => return None,
+        };
+
+        let mut edits = vec![];
+        let mut remaining = 0;
+        for reference in self.resolved_references(component, &curpos.url, program_text) {
+            let edit = match component.body {
+                ComponentBody::Variable { .. } => {
+                    replace_variable(reference, &replacement, program_text)
+                }
+                _ => replace_command_call(reference, &replacement, program_text),
+            };
+            match edit {
+                Some(edit) => edits.push(edit),
+                None => remaining += 1,
+            }
+        }
+        if edits.is_empty() {
+            return None;
+        }
+        if remaining == 0 && is_removable(component, &curpos.url, environment) {
+            edits.push((statement_removal(stmt, program_text), String::new()));
+        }
+
+        let fix = Fix {
+            title: format!("Inline `{}`", component.name),
+            edits,
+        };
+        let mut action = fix_to_code_action(fix, &curpos.url, program_text);
+        action.kind = Some(CodeActionKind::REFACTOR_INLINE);
+        Some(action)
+    }
+}
+
+/// 使用箇所をすべて置き換えたとき、定義を削除してよいか。
+/// `.saty` ファイルのトップレベルか private な module の要素であって、
+/// sig で宣言されていないものに限る。 module 名で修飾した参照も置き換えの対象に含まれる。
+fn is_removable(component: &Component, url: &Url, environment: &Environment) -> bool {
+    let is_top_level = environment
+        .components()
+        .iter()
+        .any(|c| c.pos_definition == component.pos_definition);
+    url.path().ends_with(".saty")
+        && (is_top_level || matches!(component.visibility, Visibility::Private))
+        && component.pos_declaration.is_none()
+}
+
+/// `let x = expr` の形の定義の文と、置き換えに用いる式のテキスト。
+/// 引数をとる関数の定義は対象外。
+fn variable_definition<'a>(
+    component: &Component,
+    program_text: &'a ProgramText,
+) -> Option<(&'a Cst, String)> {
+    let stmt = program_text
+        .cst
+        .pickup(Rule::let_stmt)
+        .into_iter()
+        .find(|stmt| stmt.span.includes(component.pos_definition.start))?;
+    let pattern = stmt.inner.first()?;
+    let expr = stmt.inner.last()?;
+    // パターンが変数一つだけのもの
+    if program_text.get_text(pattern).trim() != component.name {
+        return None;
+    }
+    let between = program_text
+        .get_text_from_span(Span {
+            start: pattern.span.end,
+            end: expr.span.start,
+        })
+        .trim();
+    if !(between == "=" || between.starts_with(':')) {
+        return None;
+    }
+    let expr_text = program_text.get_text(expr).trim();
+    let replacement = if needs_parens(expr_text) {
+        format!("({})", expr_text)
+    } else {
+        expr_text.to_owned()
+    };
+    Some((stmt, replacement))
+}
+
+/// `let-inline ctx \cmd = read-inline ctx {...}` の形の定義の文と、
+/// 置き換えに用いるインラインテキストの中身。
+fn inline_cmd_definition<'a>(
+    component: &Component,
+    program_text: &'a ProgramText,
+) -> Option<(&'a Cst, String)> {
+    let stmt = program_text
+        .cst
+        .pickup(Rule::let_inline_stmt_ctx)
+        .into_iter()
+        .find(|stmt| stmt.span.includes(component.pos_definition.start))?;
+    let ctx = program_text.get_text(stmt.inner.get(0)?);
+    let cmd = stmt.inner.get(1)?;
+    let expr = stmt.inner.last()?;
+    let between = program_text.get_text_from_span(Span {
+        start: cmd.span.end,
+        end: expr.span.start,
+    });
+    if between.trim() != "=" {
+        return None;
+    }
+
+    let body = program_text.get_text(expr).trim();
+    let rest = body.strip_prefix("read-inline")?.trim_start();
+    let rest = rest.strip_prefix(ctx)?.trim_start();
+    if !rest.starts_with('{') || rest.starts_with("{|") {
+        return None;
+    }
+    // インラインテキストが式全体であること
+    if matching_close(rest, 0, '{', '}')? != rest.len() - 1 {
+        return None;
+    }
+    Some((stmt, rest[1..rest.len() - 1].trim().to_owned()))
+}
+
+/// 変数の使用箇所を式で置き換える。
+fn replace_variable(
+    reference: &Cst,
+    replacement: &str,
+    program_text: &ProgramText,
+) -> Option<(Span, String)> {
+    let text = program_text.text.as_str();
+    // `#x` のような埋め込みには括弧つきの式を書けない
+    if text[..reference.span.start].ends_with('#') && replacement.starts_with('(') {
+        return None;
+    }
+    Some((reference.span, replacement.to_owned()))
+}
+
+/// `\cmd;` の形の呼び出しをインラインテキストの中身で置き換える。
+fn replace_command_call(
+    reference: &Cst,
+    replacement: &str,
+    program_text: &ProgramText,
+) -> Option<(Span, String)> {
+    let text = program_text.text.as_str();
+    let rest = &text[reference.span.end..];
+    let semicolon = reference.span.end + (rest.len() - rest.trim_start().len());
+    if !text[semicolon..].starts_with(';') {
+        return None;
+    }
+    Some((
+        Span {
+            start: reference.span.start,
+            end: semicolon + 1,
+        },
+        replacement.to_owned(),
+    ))
+}
+
+/// 定義の文を削除する範囲。
+/// `.saty` ファイルでプリアンブルの唯一の文であれば、続く `in` も削除する。
+fn statement_removal(stmt: &Cst, program_text: &ProgramText) -> Span {
+    let text = program_text.text.as_str();
+    let mut span = expand_to_lines(text, stmt.span);
+    if let Ok(Program::Saty { preamble, .. }) = &program_text.structure {
+        let rest = &text[stmt.span.end..];
+        let in_kw = stmt.span.end + (rest.len() - rest.trim_start().len());
+        let is_in = text[in_kw..].starts_with("in")
+            && !text[in_kw + 2..].starts_with(|c: char| c.is_ascii_alphanumeric() || c == '-');
+        if preamble.len() == 1 && is_in {
+            span = expand_to_lines(
+                text,
+                Span {
+                    start: stmt.span.start,
+                    end: in_kw + 2,
+                },
+            );
+        }
+    }
+    span
+}
+
+/// 式を別の式の中に埋め込むときに括弧が必要か。
+/// 識別子、リテラル、括弧で囲まれた式などはそのまま埋め込める。
+fn needs_parens(expr: &str) -> bool {
+    let is_ident = expr
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.');
+    if is_ident && !expr.is_empty() {
+        return false;
+    }
+    if expr.starts_with('`') && expr.ends_with('`') && expr.len() > 1 {
+        return false;
+    }
+    let enclosed = |open: char, close: char| {
+        expr.starts_with(open) && matching_close(expr, 0, open, close) == Some(expr.len() - 1)
+    };
+    let is_block_text =
+        expr.starts_with("'<") && matching_close(expr, 1, '<', '>') == Some(expr.len() - 1);
+    !(enclosed('(', ')') || enclosed('{', '}') || enclosed('[', ']') || is_block_text)
+}
@@ -0,0 +1,182 @@
+//! `@require:` と `@import:` のヘッダを整理する code action。
+
+use std::collections::HashSet;
+
+use itertools::Itertools;
+use lspower::lsp::{CodeAction, CodeActionKind, Url};
+use satysfi_parser::{structure::ProgramText, Rule, Span};
+
+use crate::{
+    documents::{
+        ComponentBody, Dependency, DependencyKind, DocumentCache, Environment, Visibility,
+    },
+    util::{line_end, line_start, unqualified},
+};
+
+use super::super::diagnostics::Fix;
+use super::fix_to_code_action;
+
+/// ヘッダ一行分。
+struct HeaderLine<'a> {
+    dep: &'a Dependency,
+    /// 行頭の位置。
+    start: usize,
+    /// 改行を除いた行の内容。
+    content: &'a str,
+}
+
+impl DocumentCache {
+    /// 使われていないヘッダと重複したヘッダを取り除き、
+    /// `@require:` を `@import:` より前にまとめてそれぞれ名前順に並べる。
+    /// ヘッダのあった行に並べ直したヘッダを順に書き込むので、間にあるコメントは動かない。
+    pub(super) fn get_organize_imports_action(&self, url: &Url) -> Option<CodeAction> {
+        let (program_text, environment) = self.get_doc_info(url)?;
+        let text = program_text.text.as_str();
+
+        let lines = environment
+            .dependencies()
+            .iter()
+            .map(|dep| {
+                let start = line_start(text, dep.definition.start);
+                let end = line_end(text, dep.definition.start);
+                let content = text[start..end].trim_end_matches(|c| c == '\n' || c == '\r');
+                HeaderLine {
+                    dep,
+                    start,
+                    content,
+                }
+            })
+            .sorted_by_key(|line| line.start)
+            .collect_vec();
+
+        let used_names = used_names(program_text);
+        let mut seen = HashSet::new();
+        let organized = lines
+            .iter()
+            .filter(|line| seen.insert((line.dep.kind, line.dep.name.as_str())))
+            .filter(|line| self.is_dependency_used(line.dep, &used_names))
+            .sorted_by_key(|line| {
+                (
+                    line.dep.kind == DependencyKind::Import,
+                    line.dep.name.clone(),
+                )
+            })
+            .collect_vec();
+
+        let edits = lines
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, line)| match organized.get(idx) {
+                Some(new) if new.content == line.content => None,
+                Some(new) => Some((
+                    Span {
+                        start: line.start,
+                        end: line.start + line.content.len(),
+                    },
+                    new.content.to_owned(),
+                )),
+                None => Some((
+                    Span {
+                        start: line.start,
+                        end: line_end(text, line.start),
+                    },
+                    String::new(),
+                )),
+            })
+            .collect_vec();
+        if edits.is_empty() {
+            return None;
+        }
+
+        let fix = Fix {
+            title: "Organize imports".to_owned(),
+            edits,
+        };
+        let mut action = fix_to_code_action(fix, url, program_text);
+        action.kind = Some(CodeActionKind::SOURCE_ORGANIZE_IMPORTS);
+        Some(action)
+    }
+
+    /// そのパッケージ（およびそれが読み込むパッケージ）で定義されたものが使われているか。
+    /// パッケージの中身が分からない場合は使われているものとみなす。
+    fn is_dependency_used(&self, dep: &Dependency, used_names: &HashSet<String>) -> bool {
+        let url = match &dep.url {
+            Some(url) => url,
+            None => return true,
+        };
+        let environment = match self.get_doc_info(url) {
+            Some((_, environment)) => environment,
+            None => return true,
+        };
+        let recursive = self.get_dependencies_recursive(environment.dependencies());
+        let environments = std::iter::once(environment).chain(
+            recursive
+                .iter()
+                .filter_map(|dep| dep.url.as_ref().and_then(|url| self.get_doc_info(url)))
+                .map(|(_, environment)| environment),
+        );
+        environments
+            .flat_map(exported_names)
+            .any(|name| used_names.contains(name))
+    }
+}
+
+/// ファイル内で参照されている名前（変数、型、コマンド、module など）の一覧。
+/// module で修飾されている場合は、修飾を外した名前も含める。
+/// コマンドの場合、修飾を外した名前も `\cmd` のように先頭の記号を残す。
+fn used_names(program_text: &ProgramText) -> HashSet<String> {
+    let rules = [
+        Rule::var,
+        Rule::type_name,
+        Rule::variant_name,
+        Rule::module_name,
+        Rule::inline_cmd_name,
+        Rule::block_cmd_name,
+        Rule::math_cmd_name,
+    ];
+    let mut names = HashSet::new();
+    for &rule in &rules {
+        for cst in program_text.cst.pickup(rule) {
+            let name = program_text.get_text(cst);
+            names.insert(name.to_owned());
+            names.insert(unqualified(name));
+        }
+    }
+    names
+}
+
+/// 外部から参照できる名前の一覧。
+fn exported_names(environment: &Environment) -> Vec<&str> {
+    environment
+        .components()
+        .iter()
+        .flat_map(|component| {
+            let members = match &component.body {
+                ComponentBody::Module { components } => components
+                    .iter()
+                    .filter(|c| c.visibility != Visibility::Private)
+                    .map(|c| c.name.as_str())
+                    .collect_vec(),
+                _ => vec![],
+            };
+            std::iter::once(component.name.as_str()).chain(members)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use satysfi_parser::structure::ProgramText;
+
+    use super::used_names;
+
+    #[test]
+    fn qualified_command_keeps_sigil() {
+        let text = "let-inline ctx \\foo = read-inline ctx {\\M.bar;}\n";
+        let program_text = ProgramText::parse(text).unwrap();
+        let names = used_names(&program_text);
+        assert!(names.contains("\\M.bar"));
+        assert!(names.contains("\\bar"));
+        assert!(!names.contains("bar"));
+    }
+}
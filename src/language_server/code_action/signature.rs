@@ -0,0 +1,454 @@
+//! module の struct の内容から sig を生成・更新する code action。
+
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use lspower::lsp::{CodeAction, Url};
+use regex::Regex;
+use satysfi_parser::{
+    structure::{LetRecInner, Program, ProgramText, Signature, Statement, TypeInner},
+    Cst, Rule, Span,
+};
+
+use crate::{
+    documents::{DocumentCache, DocumentData},
+    util::line_start,
+};
+
+use super::super::diagnostics::Fix;
+use super::edit::insert_before_end;
+use super::to_rewrite;
+
+/// sig の一行分の宣言。
+struct Entry {
+    /// 宣言している名前。
+    name: String,
+    /// `val x : int` のような宣言全体。
+    text: String,
+}
+
+/// 関数やコマンドの仮引数。
+struct Param {
+    name: String,
+    /// 型注釈があればその型。
+    ty: Option<String>,
+    optional: bool,
+}
+
+impl DocumentCache {
+    /// カーソルを含む（最も内側の）module について、
+    /// struct から sig を生成するものと、足りない宣言のみを追加するものを返す。
+    pub(super) fn get_signature_actions(&self, url: &Url, pos: usize) -> Vec<CodeAction> {
+        let (program_text, _) = match self.get_doc_info(url) {
+            Some(info) => info,
+            None => return vec![],
+        };
+        let preamble = match &program_text.structure {
+            Ok(Program::Saty { preamble, .. }) => preamble,
+            Ok(Program::Satyh { preamble, .. }) => preamble,
+            Err(_) => return vec![],
+        };
+        let (mod_name, signature, statements) = match innermost_module(preamble, pos, program_text)
+        {
+            Some(module) => module,
+            None => return vec![],
+        };
+        let name = program_text.get_text(mod_name);
+        let module_span = match program_text.cst.get_parent(mod_name) {
+            Some(module) => module.span,
+            None => return vec![],
+        };
+        // module の外から修飾せずに使われているコマンドは `direct` で、それ以外は `val` で宣言する
+        let used_unqualified = |cmd: &Cst| {
+            let cmd_name = program_text.get_text(cmd);
+            self.0.iter().any(|(doc_url, doc_data)| match doc_data {
+                DocumentData::Parsed { program_text, .. } => program_text
+                    .cst
+                    .pickup(cmd.rule)
+                    .into_iter()
+                    .filter(|cst| doc_url != url || !module_span.includes(cst.span.start))
+                    .any(|cst| program_text.get_text(cst) == cmd_name),
+                DocumentData::NotParsed { .. } => false,
+            })
+        };
+
+        // 既存の宣言は型を含めてそのまま使う
+        let declared: HashMap<&str, String> = signature
+            .iter()
+            .filter_map(|sig| declared_entry(sig, program_text))
+            .collect();
+        let entries = statements
+            .iter()
+            .flat_map(|stmt| definition_entries(stmt, program_text, &used_unqualified))
+            .map(|entry| match declared.get(entry.name.as_str()) {
+                Some(text) => Entry {
+                    text: text.clone(),
+                    ..entry
+                },
+                None => entry,
+            })
+            .collect_vec();
+
+        let text = program_text.text.as_str();
+        let indent: String = {
+            let start = line_start(text, mod_name.span.start);
+            text[start..]
+                .chars()
+                .take_while(|c| *c == ' ' || *c == '\t')
+                .collect()
+        };
+        let sig_span = signature_span(mod_name, program_text);
+
+        let mut fixes = vec![];
+        let body = entries
+            .iter()
+            .map(|entry| format!("{}  {}\n", indent, entry.text))
+            .join("");
+        let generated = format!("sig\n{}{}end", body, indent);
+        match sig_span {
+            Some(span) if program_text.get_text_from_span(span) != generated => fixes.push(Fix {
+                title: format!("Regenerate the signature of module `{}`", name),
+                edits: vec![(span, generated)],
+            }),
+            Some(_) => {}
+            None => fixes.push(Fix {
+                title: format!("Generate a signature for module `{}`", name),
+                edits: vec![(
+                    Span {
+                        start: mod_name.span.end,
+                        end: mod_name.span.end,
+                    },
+                    format!(" : {}", generated),
+                )],
+            }),
+        }
+
+        if let Some(span) = sig_span {
+            let missing = entries
+                .iter()
+                .filter(|entry| !declared.contains_key(entry.name.as_str()))
+                .map(|entry| insert_before_end(text, span.end - 3, &entry.text))
+                .collect_vec();
+            if !missing.is_empty() {
+                fixes.push(Fix {
+                    title: format!("Add missing signature entries to module `{}`", name),
+                    edits: missing,
+                });
+            }
+        }
+
+        fixes
+            .into_iter()
+            .map(|fix| to_rewrite(fix, url, program_text))
+            .collect()
+    }
+}
+
+/// module の sig の範囲（`sig` から対応する `end` まで）。 sig がなければ None。
+pub(super) fn signature_span(mod_name: &Cst, program_text: &ProgramText) -> Option<Span> {
+    let text = program_text.text.as_str();
+    let module_span = program_text.cst.get_parent(mod_name)?.span;
+    let after = &text[mod_name.span.end..module_span.end];
+    let after_colon = after.trim_start().strip_prefix(':')?.trim_start();
+    if !after_colon.starts_with("sig") {
+        return None;
+    }
+    let start = module_span.end - after_colon.len();
+    // sig の終わりの `end` は `= struct` の直前にある
+    let re = Regex::new(r"\bend\s*=\s*struct\b").unwrap();
+    let end_kw = start + re.find(&text[start..module_span.end])?.start();
+    Some(Span {
+        start,
+        end: end_kw + 3,
+    })
+}
+
+/// pos を含む最も内側の module の名前、 sig、 struct の中身。
+fn innermost_module<'a>(
+    statements: &'a [Statement],
+    pos: usize,
+    program_text: &ProgramText,
+) -> Option<(&'a Cst, &'a [Signature], &'a [Statement])> {
+    statements.iter().find_map(|stmt| match stmt {
+        Statement::Module {
+            name,
+            signature,
+            statements,
+        } => {
+            let module_span = program_text.cst.get_parent(name)?.span;
+            if !module_span.includes(pos) {
+                return None;
+            }
+            innermost_module(statements, pos, program_text).or(Some((
+                name,
+                signature.as_slice(),
+                statements.as_slice(),
+            )))
+        }
+        _ => None,
+    })
+}
+
+/// 既存の宣言の名前とその全体のテキスト。
+fn declared_entry<'a>(sig: &Signature, program_text: &'a ProgramText) -> Option<(&'a str, String)> {
+    let text = program_text.text.as_str();
+    match sig {
+        Signature::Val { var, signature, .. } => {
+            let name = program_text.get_text(var);
+            let ty = program_text.get_text(signature).trim();
+            Some((name, format!("val {} : {}", name, ty)))
+        }
+        Signature::Direct { var, signature, .. } => {
+            let name = program_text.get_text(var);
+            let ty = program_text.get_text(signature).trim();
+            Some((name, format!("direct {} : {}", name, ty)))
+        }
+        Signature::Type { name, .. } => {
+            // `type` から名前の終わりまで（型引数を含む）
+            let kw = text[..name.span.start].rfind("type")?;
+            Some((
+                program_text.get_text(name),
+                text[kw..name.span.end].to_owned(),
+            ))
+        }
+    }
+}
+
+/// struct 内の文で定義されているものに対する宣言の雛形。
+/// used_unqualified はコマンドを `direct` で宣言するかどうかを判定する。
+fn definition_entries(
+    stmt: &Statement,
+    program_text: &ProgramText,
+    used_unqualified: &impl Fn(&Cst) -> bool,
+) -> Vec<Entry> {
+    let text = program_text.text.as_str();
+    let mut type_vars = TypeVars::default();
+    match stmt {
+        Statement::Let { pat, .. } => {
+            let name = program_text.get_text(pat).trim();
+            let vars = pat.pickup(Rule::var);
+            if vars.len() == 1 && program_text.get_text(vars[0]) == name {
+                let (params, annot) = definition_params(pat, program_text);
+                vec![val_entry(name, &params, annot, &mut type_vars)]
+            } else {
+                // 分解されたパターンの各変数の型は分からない
+                vars.into_iter()
+                    .map(|var| {
+                        let name = program_text.get_text(var);
+                        val_entry(name, &[], None, &mut type_vars)
+                    })
+                    .collect()
+            }
+        }
+        Statement::LetRec(inners) => inners
+            .iter()
+            .map(|LetRecInner { pattern, .. }| {
+                let name = program_text.get_text(pattern).trim();
+                let (params, annot) = definition_params(pattern, program_text);
+                val_entry(name, &params, annot, &mut type_vars)
+            })
+            .collect(),
+        Statement::LetMutable { var, .. } => {
+            let name = program_text.get_text(var);
+            vec![Entry {
+                name: name.to_owned(),
+                text: format!("val {} : {} ref", name, type_vars.fresh()),
+            }]
+        }
+        Statement::LetInline { cmd, expr, .. } => vec![command_entry(
+            cmd,
+            expr,
+            "inline-cmd",
+            used_unqualified(cmd),
+            program_text,
+        )],
+        Statement::LetBlock { cmd, expr, .. } => vec![command_entry(
+            cmd,
+            expr,
+            "block-cmd",
+            used_unqualified(cmd),
+            program_text,
+        )],
+        Statement::LetMath { cmd, expr, .. } => vec![command_entry(
+            cmd,
+            expr,
+            "math-cmd",
+            used_unqualified(cmd),
+            program_text,
+        )],
+        Statement::Type(inners) => inners
+            .iter()
+            .map(|TypeInner { name, .. }| {
+                let type_name = program_text.get_text(name);
+                // 名前の直前にある型引数（`'a` など）も含める
+                let before = text[..name.span.start].trim_end();
+                let params = before
+                    .rsplit(char::is_whitespace)
+                    .take_while(|token| token.starts_with('\''))
+                    .collect_vec();
+                let params = params.into_iter().rev().map(|p| format!("{} ", p)).join("");
+                Entry {
+                    name: type_name.to_owned(),
+                    text: format!("type {}{}", params, type_name),
+                }
+            })
+            .collect(),
+        Statement::Module { .. } | Statement::Open(_) => vec![],
+    }
+}
+
+/// `val f : 'a -> 'b` のような宣言。型注釈のない部分は型変数にする。
+fn val_entry(
+    name: &str,
+    params: &[Param],
+    annot: Option<String>,
+    type_vars: &mut TypeVars,
+) -> Entry {
+    let args = params
+        .iter()
+        .map(|param| {
+            let ty = param.ty.clone().unwrap_or_else(|| type_vars.fresh());
+            if param.optional {
+                format!("{} ?-> ", ty)
+            } else {
+                format!("{} -> ", ty)
+            }
+        })
+        .join("");
+    let ret = annot.unwrap_or_else(|| type_vars.fresh());
+    Entry {
+        name: name.to_owned(),
+        text: format!("val {} : {}{}", name, args, ret),
+    }
+}
+
+/// `direct \cmd : [inline-text] inline-cmd` や `val \cmd : [inline-text] inline-cmd` のような宣言。
+/// 引数の型は注釈と本体での使われ方（`read-inline ctx it` など）から推定する。
+fn command_entry(
+    cmd: &Cst,
+    expr: &Cst,
+    cmd_type: &str,
+    is_direct: bool,
+    program_text: &ProgramText,
+) -> Entry {
+    let name = program_text.get_text(cmd);
+    let (params, _) = definition_params(cmd, program_text);
+    let mut type_vars = TypeVars::default();
+    let types = params
+        .iter()
+        .map(|param| {
+            let ty = param.ty.clone().unwrap_or_else(|| {
+                if is_applied(expr, "read-inline", &param.name, program_text) {
+                    "inline-text".to_owned()
+                } else if is_applied(expr, "read-block", &param.name, program_text) {
+                    "block-text".to_owned()
+                } else if cmd_type == "math-cmd" {
+                    "math".to_owned()
+                } else {
+                    type_vars.fresh()
+                }
+            });
+            if param.optional {
+                format!("{}?", ty)
+            } else {
+                ty
+            }
+        })
+        .join("; ");
+    let keyword = if is_direct { "direct" } else { "val" };
+    Entry {
+        name: name.to_owned(),
+        text: format!("{} {} : [{}] {}", keyword, name, types, cmd_type),
+    }
+}
+
+/// 定義の文の CST のうち、名前と本体の式の間にあるノードを仮引数と返り値の型注釈として読む。
+fn definition_params(name: &Cst, program_text: &ProgramText) -> (Vec<Param>, Option<String>) {
+    let text = program_text.text.as_str();
+    let stmt = match program_text.cst.get_parent(name) {
+        Some(stmt) => stmt,
+        None => return (vec![], None),
+    };
+    let mut nodes = stmt
+        .inner
+        .iter()
+        .filter(|node| node.span.start >= name.span.end)
+        .collect_vec();
+    // 最後のノードは本体の式
+    nodes.pop();
+
+    let mut params: Vec<Param> = vec![];
+    let mut annot = None;
+    for node in nodes {
+        let before = text[..node.span.start].trim_end();
+        let node_text = program_text.get_text(node).trim();
+        let (optional, node_text) = match node_text.strip_prefix("?:") {
+            Some(rest) => (true, rest.trim_start()),
+            None => (before.ends_with("?:"), node_text),
+        };
+        if !optional && before.ends_with(':') {
+            // `(x : ty)` の型の部分がノードとして分かれていればその仮引数の型、そうでなければ返り値の型
+            if text[node.span.end..].trim_start().starts_with(')') {
+                if let Some(param) = params.last_mut() {
+                    param.ty = Some(node_text.to_owned());
+                }
+            } else {
+                annot = Some(node_text.to_owned());
+            }
+            continue;
+        }
+        let inner = node_text
+            .strip_prefix('(')
+            .and_then(|t| t.strip_suffix(')'))
+            .unwrap_or(node_text);
+        let (name, ty) = match inner.split_once(':') {
+            Some((name, ty)) => (name.trim(), Some(ty.trim().to_owned())),
+            None => (inner.trim(), None),
+        };
+        params.push(Param {
+            name: name.to_owned(),
+            ty,
+            optional,
+        });
+    }
+    (params, annot)
+}
+
+/// 式の中で、関数 func が第二引数に変数 param を与えて適用されているか。
+/// `read-inline ctx it` の `it` のような使われ方を調べるのに用いる。
+fn is_applied(expr: &Cst, func: &str, param: &str, program_text: &ProgramText) -> bool {
+    expr.pickup(Rule::var)
+        .into_iter()
+        .filter(|var| program_text.get_text(var) == func)
+        .any(|func_var| {
+            // 関数の位置から始まる祖先のノードを遡り、関数の後ろにあるノードを引数として集める
+            let mut args = vec![];
+            let mut node = func_var;
+            while args.len() < 2 {
+                node = match program_text.cst.get_parent(node) {
+                    Some(parent) if parent.span.start == func_var.span.start => parent,
+                    _ => break,
+                };
+                args.extend(
+                    node.inner
+                        .iter()
+                        .filter(|arg| arg.span.start >= func_var.span.end),
+                );
+            }
+            args.get(1)
+                .map(|arg| program_text.get_text(arg).trim() == param)
+                .unwrap_or(false)
+        })
+}
+
+/// `'a`, `'b`, ... を順に払い出す。
+#[derive(Default)]
+struct TypeVars(u8);
+
+impl TypeVars {
+    fn fresh(&mut self) -> String {
+        let c = (b'a' + self.0 % 26) as char;
+        self.0 += 1;
+        format!("'{}", c)
+    }
+}
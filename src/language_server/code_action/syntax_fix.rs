@@ -0,0 +1,300 @@
+//! パーサが dummy ノードとして読み飛ばした構文エラーを修正する code action。
+
+use std::collections::HashMap;
+
+use lspower::lsp::{CodeAction, CodeActionKind, Range, TextEdit, Url, WorkspaceEdit};
+use satysfi_parser::{structure::ProgramText, Cst, Rule, Span};
+
+use crate::{
+    config::Config,
+    documents::{DocumentCache, SyntaxError, DUMMY_RULES},
+    util::{expand_to_lines, offset_to_position, ConvertPosition},
+};
+
+use super::super::diagnostics::{dummy_diagnostic, unexpected_diagnostic, Fix};
+use super::fix_to_code_action;
+
+impl DocumentCache {
+    /// カーソル範囲と重なる dummy ノードそれぞれについて、その修正を quick fix として返す。
+    pub(super) fn get_syntax_fix_actions(
+        &self,
+        url: &Url,
+        start: usize,
+        end: usize,
+        config: &Config,
+    ) -> Vec<CodeAction> {
+        let (program_text, _) = match self.get_doc_info(url) {
+            Some(info) => info,
+            None => return vec![],
+        };
+        dummy_csts(program_text)
+            .into_iter()
+            .filter(|cst| cst.span.start <= end && cst.span.end >= start)
+            .filter_map(|cst| Some((cst, dummy_diagnostic(program_text, cst, config)?)))
+            .flat_map(|(cst, diagnostic)| {
+                syntax_fixes(cst, program_text)
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(idx, fix)| {
+                        let mut action = fix_to_code_action(fix, url, program_text);
+                        action.kind = Some(CodeActionKind::QUICKFIX);
+                        action.diagnostics = Some(vec![diagnostic.clone()]);
+                        action.is_preferred = Some(idx == 0);
+                        action
+                    })
+            })
+            .collect()
+    }
+
+    /// ファイル中のすべての dummy ノードに最初の修正を適用する source action を返す。
+    pub(super) fn get_syntax_fix_all_action(&self, url: &Url) -> Option<CodeAction> {
+        let (program_text, _) = self.get_doc_info(url)?;
+        let mut edits: Vec<(Span, String)> = vec![];
+        for cst in dummy_csts(program_text) {
+            let fix = match syntax_fixes(cst, program_text).into_iter().next() {
+                Some(fix) => fix,
+                None => continue,
+            };
+            // 既に採用した編集と重なるものは適用しない
+            let overlaps = fix.edits.iter().any(|(span, _)| {
+                edits
+                    .iter()
+                    .any(|(other, _)| span.start < other.end && other.start < span.end)
+            });
+            if !overlaps {
+                edits.extend(fix.edits);
+            }
+        }
+        if edits.is_empty() {
+            return None;
+        }
+        let fix = Fix {
+            title: "Fix all syntax errors in this file".to_owned(),
+            edits,
+        };
+        let mut action = fix_to_code_action(fix, url, program_text);
+        action.kind = Some(CodeActionKind::SOURCE_FIX_ALL);
+        Some(action)
+    }
+}
+
+/// ファイル中の dummy ノードを出現順に並べたもの。
+fn dummy_csts(program_text: &ProgramText) -> Vec<&Cst> {
+    let mut csts: Vec<&Cst> = DUMMY_RULES
+        .iter()
+        .flat_map(|&rule| program_text.cst.pickup(rule))
+        .collect();
+    csts.sort_by_key(|cst| cst.span.start);
+    csts
+}
+
+/// dummy ノードに対する修正候補。最初のものを推奨する修正とする。
+fn syntax_fixes(cst: &Cst, program_text: &ProgramText) -> Vec<Fix> {
+    let text = program_text.text.as_str();
+    match cst.rule {
+        Rule::dummy_inline_cmd_incomplete | Rule::dummy_block_cmd_incomplete => {
+            // ノードの最後のトークンの直後にセミコロンを補う
+            let pos = cst.span.start + program_text.get_text(cst).trim_end().len();
+            let kind = if cst.rule == Rule::dummy_inline_cmd_incomplete {
+                "inline"
+            } else {
+                "block"
+            };
+            vec![Fix {
+                title: format!("Insert `;` to terminate the {} command", kind),
+                edits: vec![(
+                    Span {
+                        start: pos,
+                        end: pos,
+                    },
+                    ";".to_owned(),
+                )],
+            }]
+        }
+        Rule::dummy_header => {
+            let mut fixes = vec![];
+            // `@require pkg` のようにコロンを書き忘れたヘッダ
+            let header = program_text.get_text(cst);
+            for kwd in &["@require", "@import"] {
+                if let Some(rest) = header.strip_prefix(kwd) {
+                    if !rest.starts_with(':') && rest.starts_with(char::is_whitespace) {
+                        let pos = cst.span.start + kwd.len();
+                        fixes.push(Fix {
+                            title: format!("Insert `:` after `{}`", kwd),
+                            edits: vec![(
+                                Span {
+                                    start: pos,
+                                    end: pos,
+                                },
+                                ":".to_owned(),
+                            )],
+                        });
+                    }
+                }
+            }
+            fixes.push(delete_fix("Delete the malformed header", cst, text));
+            fixes
+        }
+        Rule::dummy_stmt => vec![delete_fix("Delete the stray statement", cst, text)],
+        Rule::dummy_sig_stmt => vec![delete_fix("Delete the stray signature entry", cst, text)],
+        _ => vec![],
+    }
+}
+
+/// ノードを（それだけで行を占めていれば行ごと）削除する修正。
+fn delete_fix(title: &str, cst: &Cst, text: &str) -> Fix {
+    Fix {
+        title: title.to_owned(),
+        edits: vec![(expand_to_lines(text, cst.span), String::new())],
+    }
+}
+
+/// パースできなかったファイルについて、カーソル範囲の行にある最初の構文エラーが
+/// 閉じられていないブロックコマンドの引数によるものであれば、それを閉じる quick fix を返す。
+/// 二つ目以降のエラーはエラーからの回復で塗りつぶした後のテキストに対するものなので扱わない。
+pub(super) fn get_unterminated_block_actions(
+    url: &Url,
+    range: &Range,
+    text: &str,
+    errors: &[SyntaxError],
+    config: &Config,
+) -> Vec<CodeAction> {
+    let (linecol, expect) = match errors.first() {
+        Some(SyntaxError::Unexpected { linecol, expect }) => (linecol, expect),
+        _ => return vec![],
+    };
+    let diagnostic = unexpected_diagnostic(text, linecol, expect, config);
+    let error_line = diagnostic.range.start.line;
+    if error_line < range.start.line || range.end.line < error_line {
+        return vec![];
+    }
+    let error_pos = match text.from_position(&diagnostic.range.start) {
+        Some(pos) => pos,
+        None => return vec![],
+    };
+    let (unclosed, last_end) = unclosed_block_argument(&text[..error_pos]);
+    let (cmd, open) = match unclosed {
+        Some(unclosed) => unclosed,
+        None => return vec![],
+    };
+    let close = if open == '{' { "}" } else { ">" };
+    let pos = offset_to_position(text, last_end);
+    let edit = TextEdit {
+        range: Range {
+            start: pos,
+            end: pos,
+        },
+        new_text: close.to_owned(),
+    };
+    let mut changes = HashMap::new();
+    changes.insert(url.clone(), vec![edit]);
+    vec![CodeAction {
+        title: format!("Insert `{}` to close the argument of `{}`", close, cmd),
+        kind: Some(CodeActionKind::QUICKFIX),
+        diagnostics: Some(vec![diagnostic]),
+        edit: Some(WorkspaceEdit {
+            changes: Some(changes),
+            ..Default::default()
+        }),
+        is_preferred: Some(true),
+        ..Default::default()
+    }]
+}
+
+/// text の末尾の時点で最も内側にある閉じられていない括弧が、ブロックコマンドの引数の
+/// `{` か `<` であれば、そのコマンド名と開き括弧。
+/// あわせて、コメントを除いた最後のトークンの終わりの位置を返す。
+/// 字句を簡易的に読むだけなので、 `<` はブロックコマンドの引数と `'<` の場合のみ括弧として扱い、
+/// それ以外は比較演算子などとみなす。
+fn unclosed_block_argument(text: &str) -> (Option<(&str, char)>, usize) {
+    let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '.';
+    // 開き括弧と、それを引数とするブロックコマンド名
+    let mut stack: Vec<(char, Option<&str>)> = vec![];
+    // 直前に読んだブロックコマンド名。引数が続く間は保持する
+    let mut pending: Option<&str> = None;
+    let mut last_end = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((idx, c)) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+        let mut end = idx + c.len_utf8();
+        match c {
+            '%' => {
+                // コメントは行末まで読み飛ばす
+                while chars.next_if(|&(_, c)| c != '\n').is_some() {}
+                continue;
+            }
+            '`' => {
+                // 同じ数のバッククォートで閉じられるまでをリテラルとして読み飛ばす
+                let mut n = 1;
+                while chars.next_if(|&(_, c)| c == '`').is_some() {
+                    n += 1;
+                }
+                let delim = "`".repeat(n);
+                let body = idx + n;
+                end = text[body..]
+                    .find(&delim)
+                    .map_or(text.len(), |len| body + len + n);
+                while chars.next_if(|&(i, _)| i < end).is_some() {}
+                pending = None;
+            }
+            '\\' => {
+                // エスケープされた記号は読み飛ばし、コマンド名は一つのトークンとして読む
+                match chars.peek() {
+                    Some(&(i, next)) if next.is_ascii_punctuation() => {
+                        chars.next();
+                        end = i + next.len_utf8();
+                    }
+                    _ => {
+                        while let Some((i, c)) = chars.next_if(|&(_, c)| is_name_char(c)) {
+                            end = i + c.len_utf8();
+                        }
+                    }
+                }
+                pending = None;
+            }
+            '+' if chars
+                .peek()
+                .map_or(false, |&(_, c)| c.is_ascii_alphabetic()) =>
+            {
+                while let Some((i, c)) = chars.next_if(|&(_, c)| is_name_char(c)) {
+                    end = i + c.len_utf8();
+                }
+                pending = Some(&text[idx..end]);
+            }
+            '\'' if chars.peek().map(|&(_, c)| c) == Some('<') => {
+                chars.next();
+                end = idx + 2;
+                stack.push(('<', None));
+                pending = None;
+            }
+            // オプション引数の後にも引数が続く
+            '?' if chars.peek().map(|&(_, c)| c) == Some(':') => {
+                chars.next();
+                end = idx + 2;
+            }
+            '{' | '(' | '[' => stack.push((c, pending.take())),
+            '<' if pending.is_some() => stack.push((c, pending.take())),
+            '}' | ')' | ']' | '>' => {
+                let open = match c {
+                    '}' => '{',
+                    ')' => '(',
+                    ']' => '[',
+                    _ => '<',
+                };
+                pending = match stack.last() {
+                    Some(&(top, _)) if top == open => stack.pop().and_then(|(_, cmd)| cmd),
+                    _ => None,
+                };
+            }
+            _ => pending = None,
+        }
+        last_end = end;
+    }
+    let unclosed = match stack.last() {
+        Some(&(open @ ('{' | '<'), Some(cmd))) => Some((cmd, open)),
+        _ => None,
+    };
+    (unclosed, last_end)
+}
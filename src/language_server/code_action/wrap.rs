@@ -0,0 +1,105 @@
+//! 選択範囲をコマンドで囲む code action。
+
+use std::collections::HashSet;
+
+use itertools::Itertools;
+use lspower::lsp::{CodeAction, Range, Url};
+
+use crate::documents::{Component, ComponentBody, DocumentCache, Environment, Visibility};
+
+use super::super::completion::form_command_wrapping;
+use super::super::diagnostics::Fix;
+use super::super::snippet_edit::mark_as_snippet;
+use super::extract::text_selection;
+use super::to_rewrite;
+
+impl DocumentCache {
+    /// 選択されたインラインテキスト（ブロックテキスト）を、スコープ内にあって
+    /// `inline-text`（`block-text`）型の必須引数をとるコマンドで囲む。
+    /// snippet が真であれば、残りの必須引数はスニペットのプレースホルダにし、
+    /// 編集を SnippetTextEdit として送る。
+    pub(super) fn get_wrap_actions(
+        &self,
+        url: &Url,
+        range: &Range,
+        snippet: bool,
+    ) -> Vec<CodeAction> {
+        let (program_text, _) = match self.get_doc_info(url) {
+            Some(info) => info,
+            None => return vec![],
+        };
+        let (span, is_inline) = match text_selection(program_text, range) {
+            Some(selection) => selection,
+            None => return vec![],
+        };
+        let selected = program_text.get_text_from_span(span);
+        let text_type = if is_inline {
+            "inline-text"
+        } else {
+            "block-text"
+        };
+
+        let mut names = HashSet::new();
+        self.commands_in_scope(url, span.start, is_inline)
+            .into_iter()
+            .filter(|cmd| names.insert(cmd.name.as_str()))
+            .filter_map(|cmd| {
+                let type_args = match &cmd.body {
+                    ComponentBody::InlineCmd { type_args, .. }
+                    | ComponentBody::BlockCmd { type_args, .. } => type_args,
+                    _ => return None,
+                };
+                let call =
+                    form_command_wrapping(&cmd.name, type_args, text_type, selected, snippet)?;
+                Some((cmd.name.as_str(), call))
+            })
+            .sorted()
+            .map(|(name, call)| {
+                let fix = Fix {
+                    title: format!("Wrap with `{}`", name),
+                    edits: vec![(span, call)],
+                };
+                let mut action = to_rewrite(fix, url, program_text);
+                if snippet {
+                    mark_as_snippet(&mut action);
+                }
+                action
+            })
+            .collect()
+    }
+
+    /// pos で使えるインラインコマンド（ブロックコマンド）の一覧。
+    fn commands_in_scope(&self, url: &Url, pos: usize, is_inline: bool) -> Vec<&Component> {
+        let doc_data = match self.get(url) {
+            Some(doc_data) => doc_data,
+            None => return vec![],
+        };
+        let environment = match self.get_doc_info(url) {
+            Some((_, environment)) => environment,
+            None => return vec![],
+        };
+        let open_modules = doc_data.get_open_modules(pos);
+        let local = cmds_external(environment, &open_modules, is_inline)
+            .into_iter()
+            .filter(|cmd| cmd.scope.includes(pos));
+        let deps = self
+            .get_dependencies_recursive(environment.dependencies())
+            .into_iter()
+            .filter_map(|dep| dep.url.as_ref().and_then(|url| self.get_doc_info(url)))
+            .flat_map(|(_, env_dep)| cmds_external(env_dep, &open_modules, is_inline))
+            .filter(|cmd| matches!(cmd.visibility, Visibility::Public | Visibility::Direct));
+        local.chain(deps).collect()
+    }
+}
+
+fn cmds_external<'a>(
+    environment: &'a Environment,
+    open_modules: &[String],
+    is_inline: bool,
+) -> Vec<&'a Component> {
+    if is_inline {
+        environment.inline_cmds_external(open_modules)
+    } else {
+        environment.block_cmds_external(open_modules)
+    }
+}
@@ -0,0 +1,203 @@
+//! code lens の提供。
+
+use lspower::lsp::{CodeLens, Command, Location, Range, Url};
+use satysfi_parser::structure::{Program, ProgramText};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    documents::{Component, ComponentBody, DocumentCache, DocumentData, Visibility},
+    util::{line_end, ConvertPosition},
+};
+
+use super::code_action::statement_end;
+
+/// "Compile" の lens から呼ばれる、サーバ側で文書をコンパイルするコマンド。
+pub const COMPILE_COMMAND: &str = "satysfi-ls.compile";
+
+/// 参照数の lens をクリックしたときに参照の一覧を表示する、client 側のコマンド。
+/// 引数は文書の URI、位置、参照の Location の一覧。
+const SHOW_REFERENCES_COMMAND: &str = "editor.action.showReferences";
+
+/// 参照数の lens を codeLens/resolve で解決するための情報。
+#[derive(Debug, Serialize, Deserialize)]
+struct ReferencesData {
+    url: Url,
+    /// 定義の位置。
+    pos: usize,
+}
+
+impl DocumentCache {
+    /// 各コマンド・module の定義の上に参照数の lens を、
+    /// `.saty` ファイルでは文書本体の上に "Compile" の lens を置く。
+    /// 参照数は数えるのに時間がかかるため、 resolve するまで求めない。
+    pub fn get_code_lenses(&self, url: &Url) -> Vec<CodeLens> {
+        let (program_text, environment) = match self.get_doc_info(url) {
+            Some(info) => info,
+            None => return vec![],
+        };
+        let mut lenses: Vec<CodeLens> = lens_targets(environment.components())
+            .into_iter()
+            .filter_map(|component| {
+                let data = ReferencesData {
+                    url: url.clone(),
+                    pos: component.pos_definition.start,
+                };
+                Some(CodeLens {
+                    range: program_text.get_range(component.pos_definition)?,
+                    command: None,
+                    data: serde_json::to_value(data).ok(),
+                })
+            })
+            .collect();
+
+        if let Some(pos) =
+            document_start(program_text).and_then(|pos| program_text.get_position(pos))
+        {
+            lenses.push(CodeLens {
+                range: Range {
+                    start: pos,
+                    end: pos,
+                },
+                command: Some(Command {
+                    title: "Compile".to_owned(),
+                    command: COMPILE_COMMAND.to_owned(),
+                    arguments: serde_json::to_value(url).ok().map(|url| vec![url]),
+                }),
+                data: None,
+            });
+        }
+        lenses
+    }
+
+    /// 参照数の lens に "N references" というタイトルと、
+    /// クリックすると参照の一覧を表示するコマンドを付ける。
+    /// 定義が見つからなければそのまま返す。
+    pub fn resolve_code_lens(&self, lens: CodeLens) -> CodeLens {
+        let data = lens
+            .data
+            .clone()
+            .and_then(|data| serde_json::from_value::<ReferencesData>(data).ok());
+        let (url, locations) = match data.and_then(|data| {
+            let locations = self.reference_locations(&data.url, data.pos)?;
+            Some((data.url, locations))
+        }) {
+            Some(found) => found,
+            None => return lens,
+        };
+        let title = if locations.len() == 1 {
+            "1 reference".to_owned()
+        } else {
+            format!("{} references", locations.len())
+        };
+        let arguments = vec![
+            serde_json::to_value(url).ok(),
+            serde_json::to_value(lens.range.start).ok(),
+            serde_json::to_value(locations).ok(),
+        ];
+        CodeLens {
+            command: Some(Command {
+                title,
+                command: SHOW_REFERENCES_COMMAND.to_owned(),
+                arguments: arguments.into_iter().collect(),
+            }),
+            ..lens
+        }
+    }
+
+    /// pos で定義されているコンポーネントの参照の位置。
+    /// 公開されているものは、そのファイルに依存している開いたファイルでの参照も含める。
+    fn reference_locations(&self, url: &Url, pos: usize) -> Option<Vec<Location>> {
+        let (program_text, environment) = self.get_doc_info(url)?;
+        let component = lens_targets(environment.components())
+            .into_iter()
+            .find(|component| component.pos_definition.start == pos)?;
+
+        let to_locations = |url: &Url, program_text: &ProgramText| {
+            self.resolved_references(component, url, program_text)
+                .into_iter()
+                .filter_map(|cst| {
+                    Some(Location::new(
+                        url.clone(),
+                        program_text.get_range(cst.span)?,
+                    ))
+                })
+                .collect::<Vec<_>>()
+        };
+        let mut locations = to_locations(url, program_text);
+        if component.visibility != Visibility::Private {
+            for (other_url, doc_data) in &self.0 {
+                let (other_text, other_env) = match doc_data {
+                    DocumentData::Parsed {
+                        program_text,
+                        environment,
+                    } if other_url != url => (program_text, environment),
+                    _ => continue,
+                };
+                let depends = self
+                    .get_dependencies_recursive(other_env.dependencies())
+                    .iter()
+                    .any(|dep| dep.url.as_ref() == Some(url));
+                if depends {
+                    locations.extend(to_locations(other_url, other_text));
+                }
+            }
+        }
+        Some(locations)
+    }
+}
+
+/// 参照数の lens を置くコンポーネント。 module の中で定義されているものも含む。
+fn lens_targets(components: &[Component]) -> Vec<&Component> {
+    let mut targets = vec![];
+    for component in components {
+        match &component.body {
+            ComponentBody::Module { components } => {
+                targets.push(component);
+                targets.extend(lens_targets(components));
+            }
+            ComponentBody::InlineCmd { .. }
+            | ComponentBody::BlockCmd { .. }
+            | ComponentBody::MathCmd { .. } => targets.push(component),
+            _ => {}
+        }
+    }
+    targets
+}
+
+/// `.saty` ファイルの文書本体の式が始まる位置。
+fn document_start(program_text: &ProgramText) -> Option<usize> {
+    let (header, preamble) = match &program_text.structure {
+        Ok(Program::Saty {
+            header, preamble, ..
+        }) => (header, preamble),
+        _ => return None,
+    };
+    let text = program_text.text.as_str();
+    let pos = match (preamble.last(), header.last()) {
+        (Some(stmt), _) => {
+            // プリアンブルと文書本体は `in` で区切られている
+            let pos = skip_trivia(text, statement_end(stmt, program_text)?);
+            match text[pos..].strip_prefix("in") {
+                Some(_) => skip_trivia(text, pos + 2),
+                None => pos,
+            }
+        }
+        (None, Some(header)) => skip_trivia(text, line_end(text, header.name.span.end)),
+        (None, None) => skip_trivia(text, 0),
+    };
+    Some(pos)
+}
+
+/// 空白とコメントを読み飛ばした位置。
+fn skip_trivia(text: &str, mut pos: usize) -> usize {
+    loop {
+        let rest = &text[pos..];
+        let trimmed = rest.trim_start();
+        pos += rest.len() - trimmed.len();
+        if trimmed.starts_with('%') {
+            pos = line_end(text, pos);
+        } else {
+            return pos;
+        }
+    }
+}
@@ -0,0 +1,318 @@
+//! satysfi を実際に実行し、その出力から診断を作成する。
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    process::Stdio,
+    time::Duration,
+};
+
+use anyhow::{anyhow, Result};
+use itertools::Itertools;
+use lspower::lsp::{Diagnostic, DiagnosticSeverity, NumberOrString, Position, Range, Url};
+use regex::Regex;
+use tokio::process::Command;
+
+use crate::config::CompilerConfig;
+
+/// 保存時に行うコンパイルの内容。
+#[derive(Debug, Clone)]
+pub struct CompileTask {
+    /// コンパイルする `.saty` ファイル。
+    pub root: PathBuf,
+    /// 同じファイルに対して何回目に要求されたコンパイルか。
+    /// 後から要求されたコンパイルの結果を古い結果で上書きしないために用いる。
+    pub generation: u64,
+    executable: String,
+    args: Vec<String>,
+    timeout: Duration,
+}
+
+/// コンパイラが出力したメッセージ一つ分。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompilerMessage {
+    /// `Type Error` などのエラーの種類。
+    pub kind: String,
+    /// エラーのあったファイル。位置が示されていなければ None。
+    pub file: Option<PathBuf>,
+    /// 0-indexed の (行, 列) の組で表した範囲。
+    pub range: Option<((u32, u32), (u32, u32))>,
+    pub message: String,
+}
+
+impl CompileTask {
+    pub fn new(config: &CompilerConfig, root: PathBuf, generation: u64) -> CompileTask {
+        CompileTask {
+            root,
+            generation,
+            executable: config.executable.clone(),
+            args: config.args.clone(),
+            timeout: Duration::from_secs(config.timeout),
+        }
+    }
+
+    /// satysfi を実行し、その標準出力と標準エラー出力を合わせて返す。
+    /// 出力ファイルは一時ディレクトリに書き出す。
+    pub async fn run(&self) -> Result<String> {
+        let output_path = self.output_path()?;
+        let mut command = Command::new(&self.executable);
+        command
+            .arg(&self.root)
+            .arg("-o")
+            .arg(&output_path)
+            .args(&self.args)
+            .stdin(Stdio::null())
+            .kill_on_drop(true);
+        if let Some(parent) = self.root.parent() {
+            command.current_dir(parent);
+        }
+
+        let output = tokio::time::timeout(self.timeout, command.output())
+            .await
+            .map_err(|_| anyhow!("`{}` timed out after {:?}", self.executable, self.timeout))??;
+        Ok(format!(
+            "{}\n{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        ))
+    }
+
+    /// 出力ファイルのパス。
+    /// 同じ名前のファイルを別々のディレクトリでコンパイルしても衝突しないよう、
+    /// コンパイルするファイルのパスごとにディレクトリを分ける。
+    fn output_path(&self) -> Result<PathBuf> {
+        let stem = self
+            .root
+            .file_stem()
+            .ok_or_else(|| anyhow!("invalid file name: {:?}", self.root))?;
+        let mut hasher = DefaultHasher::new();
+        self.root.hash(&mut hasher);
+        let output_dir = std::env::temp_dir()
+            .join("satysfi-language-server")
+            .join(format!("{:016x}", hasher.finish()));
+        std::fs::create_dir_all(&output_dir)?;
+        Ok(output_dir.join(stem).with_extension("pdf"))
+    }
+}
+
+/// コンパイラの出力から `! [Type Error] at "file", line N, characters A-B:` の形の
+/// メッセージを取り出す。メッセージ本文はそれに続くインデントされた行。
+pub fn parse_compiler_output(output: &str) -> Vec<CompilerMessage> {
+    let re_header = Regex::new(r#"^! \[(?P<kind>[^\]]+)\](?P<rest>.*)$"#).unwrap();
+    let re_characters =
+        Regex::new(r#"^ at "(?P<file>[^"]+)", line (?P<line>\d+), characters (?P<start>\d+)-(?P<end>\d+):?(?P<msg>.*)$"#)
+            .unwrap();
+    let re_multiline = Regex::new(
+        r#"^ at "(?P<file>[^"]+)", line (?P<l1>\d+), character (?P<c1>\d+) to line (?P<l2>\d+), character (?P<c2>\d+):?(?P<msg>.*)$"#,
+    )
+    .unwrap();
+
+    let num = |caps: &regex::Captures, name: &str| -> u32 { caps[name].parse().unwrap_or(0) };
+
+    let mut messages = vec![];
+    let mut lines = output.lines().peekable();
+    while let Some(line) = lines.next() {
+        let caps = match re_header.captures(line) {
+            Some(caps) => caps,
+            None => continue,
+        };
+        let kind = caps["kind"].to_owned();
+        let rest = &caps["rest"];
+
+        let (file, range, first_line) = if let Some(caps) = re_characters.captures(rest) {
+            let line = num(&caps, "line").saturating_sub(1);
+            let range = ((line, num(&caps, "start")), (line, num(&caps, "end")));
+            (
+                Some(PathBuf::from(&caps["file"])),
+                Some(range),
+                caps["msg"].trim().to_owned(),
+            )
+        } else if let Some(caps) = re_multiline.captures(rest) {
+            let range = (
+                (num(&caps, "l1").saturating_sub(1), num(&caps, "c1")),
+                (num(&caps, "l2").saturating_sub(1), num(&caps, "c2")),
+            );
+            (
+                Some(PathBuf::from(&caps["file"])),
+                Some(range),
+                caps["msg"].trim().to_owned(),
+            )
+        } else {
+            (None, None, rest.trim_start_matches(':').trim().to_owned())
+        };
+
+        // 続くインデントされた行をメッセージ本文とする
+        let mut body = vec![first_line];
+        while let Some(next) = lines.peek() {
+            if next.starts_with(' ') || next.starts_with('\t') {
+                body.push(next.trim().to_owned());
+                lines.next();
+            } else {
+                break;
+            }
+        }
+
+        messages.push(CompilerMessage {
+            kind,
+            file,
+            range,
+            message: body.into_iter().filter(|s| !s.is_empty()).join("\n"),
+        });
+    }
+    messages
+}
+
+impl CompilerMessage {
+    /// このメッセージを報告するファイルの URL。
+    /// 位置が示されていなければコンパイルしたファイルに報告する。
+    pub fn url(&self, root: &Path) -> Option<Url> {
+        let path = match &self.file {
+            Some(file) if file.is_relative() => root.parent()?.join(file),
+            Some(file) => file.clone(),
+            None => root.to_owned(),
+        };
+        Url::from_file_path(path).ok()
+    }
+
+    /// メッセージを診断に変換する。
+    /// コンパイラの列はバイト単位なので、テキストがあれば UTF-16 単位に変換する。
+    pub fn to_diagnostic(&self, text: Option<&str>) -> Diagnostic {
+        let to_position = |(line, col): (u32, u32)| {
+            let character = text
+                .and_then(|text| text.split('\n').nth(line as usize))
+                .and_then(|line_text| line_text.get(..col as usize))
+                .map(|s| s.encode_utf16().count() as u32)
+                .unwrap_or(col);
+            Position { line, character }
+        };
+        let range = self
+            .range
+            .map(|(start, end)| Range {
+                start: to_position(start),
+                end: to_position(end),
+            })
+            .unwrap_or_default();
+        Diagnostic {
+            range,
+            severity: Some(DiagnosticSeverity::ERROR),
+            code: Some(NumberOrString::String(self.kind.clone())),
+            source: Some("satysfi".to_owned()),
+            message: self.message.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 決まった出力を返す satysfi の代わりのスクリプト。
+    fn stub_config() -> CompilerConfig {
+        CompilerConfig {
+            executable: concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/tests/fixtures/satysfi-stub.sh"
+            )
+            .to_owned(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parse_characters() {
+        let output = "! [Type Error] at \"doc.saty\", line 3, characters 12-18:\n    this expression has type\n      int,\n    but is expected of type\n      string.\n";
+        assert_eq!(
+            parse_compiler_output(output),
+            vec![CompilerMessage {
+                kind: "Type Error".to_owned(),
+                file: Some(PathBuf::from("doc.saty")),
+                range: Some(((2, 12), (2, 18))),
+                message: "this expression has type\nint,\nbut is expected of type\nstring."
+                    .to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_multiline_range() {
+        let output = "! [Syntax Error at Parser] at \"lib/util.satyh\", line 5, character 4 to line 7, character 2:\n    unexpected token.\n";
+        assert_eq!(
+            parse_compiler_output(output),
+            vec![CompilerMessage {
+                kind: "Syntax Error at Parser".to_owned(),
+                file: Some(PathBuf::from("lib/util.satyh")),
+                range: Some(((4, 4), (6, 2))),
+                message: "unexpected token.".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_message_on_header_line() {
+        let output =
+            "! [Error at Lexer] at \"doc.saty\", line 1, characters 0-1: illegal token '#'.\n";
+        let messages = parse_compiler_output(output);
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].range, Some(((0, 0), (0, 1))));
+        assert_eq!(messages[0].message, "illegal token '#'.");
+    }
+
+    #[test]
+    fn parse_without_location() {
+        let output = "  type checking 'doc.saty' ...\n! [Error] cannot find package 'missing'.\n\n  output: doc.pdf\n";
+        assert_eq!(
+            parse_compiler_output(output),
+            vec![CompilerMessage {
+                kind: "Error".to_owned(),
+                file: None,
+                range: None,
+                message: "cannot find package 'missing'.".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn diagnostic_range_in_utf16() {
+        let message = CompilerMessage {
+            kind: "Type Error".to_owned(),
+            file: None,
+            range: Some(((0, 6), (0, 9))),
+            message: String::new(),
+        };
+        let range = message.to_diagnostic(Some("あa bcde")).range;
+        assert_eq!(range.start, Position::new(0, 4));
+        assert_eq!(range.end, Position::new(0, 7));
+    }
+
+    #[test]
+    fn output_path_per_root() {
+        let config = stub_config();
+        let task_a = CompileTask::new(&config, PathBuf::from("/a/doc.saty"), 1);
+        let task_b = CompileTask::new(&config, PathBuf::from("/b/doc.saty"), 1);
+        let path_a = task_a.output_path().unwrap();
+        assert_ne!(path_a, task_b.output_path().unwrap());
+        assert_eq!(path_a, task_a.output_path().unwrap());
+        assert_eq!(path_a.file_name().unwrap(), "doc.pdf");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn run_stub_compiler() {
+        let root = std::env::temp_dir().join("doc.saty");
+        let task = CompileTask::new(&stub_config(), root.clone(), 1);
+        let output = task.run().await.unwrap();
+        let output_path = task.output_path().unwrap();
+        assert!(output.contains(&format!("output: {}", output_path.display())));
+
+        let messages = parse_compiler_output(&output);
+        let kinds: Vec<&str> = messages.iter().map(|m| m.kind.as_str()).collect();
+        assert_eq!(kinds, ["Type Error", "Syntax Error at Parser", "Error"]);
+        assert_eq!(
+            messages[1].url(&root),
+            Url::from_file_path(std::env::temp_dir().join("lib/util.satyh")).ok()
+        );
+        assert_eq!(messages[2].url(&root), Url::from_file_path(&root).ok());
+    }
+}
@@ -1,5 +1,8 @@
 use regex::Regex;
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 
 use glob::glob;
 use itertools::Itertools;
@@ -8,14 +11,33 @@ use lspower::lsp::{
     CompletionItem, CompletionItemKind, CompletionResponse, CompletionTextEdit, Documentation,
     InsertTextFormat, MarkupContent, MarkupKind, Position, Range, TextEdit, Url,
 };
-use satysfi_parser::{LineCol, Mode};
-use serde::Deserialize;
+use satysfi_parser::{LineCol, Mode, Rule, Span};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    documents::{require_candidate_dirs, ComponentBody, DocumentCache, DocumentData, Visibility},
+    documents::{
+        package_dirs, require_candidate_dirs, Component, ComponentBody, DocumentCache,
+        DocumentData, PackageIndex, Visibility,
+    },
     util::{ConvertPosition, UrlPos},
 };
 
+use super::code_action::{doc_comment, require_header_insertion};
+
+mod literal;
+mod ranking;
+mod resource;
+mod typed;
+
+pub use self::literal::get_literal_completion;
+pub use self::ranking::completion_query;
+use self::ranking::{
+    builtin, dependency_proximity, is_direct_dependency, local_proximity, Candidates, Proximity,
+};
+pub use self::resource::CompletionResources;
+use self::typed::{filter_by_type, literal_templates};
+use super::unicode_names::glyph_name;
+
 pub const COMPLETION_RESOUCES: &str = include_str!("../resource/completion_items.toml");
 
 pub fn get_primitive_list() -> Vec<CompletionItem> {
@@ -34,7 +56,7 @@ pub fn get_resouce_items() -> HashMap<String, Vec<CompletionResourceItem>> {
 }
 
 /// TOML ファイルに記述する completion items.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct CompletionResourceItem {
     /// The label of this completion item. By default also the text that is inserted when selecting
     /// this completion.
@@ -50,6 +72,19 @@ pub struct CompletionResourceItem {
     /// The format of the insert text. The format applies to both the insertText property and the
     /// newText property of a provided textEdit.
     pub insert_text_format: Option<String>,
+    /// The mode in which this item is completed. Defaults to `program`.
+    pub mode: Option<ResourceMode>,
+}
+
+/// completion item を補完するモード。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResourceMode {
+    Program,
+    Horizontal,
+    Vertical,
+    Math,
+    Header,
 }
 
 impl From<CompletionResourceItem> for CompletionItem {
@@ -79,29 +114,53 @@ impl DocumentCache {
         &self,
         curpos: &UrlPos,
         trigger: Option<&str>,
+        query: &str,
+        packages: &PackageIndex,
+        resources: &CompletionResources,
+        workspace_root: Option<&Path>,
     ) -> Option<CompletionResponse> {
         let line_str = self.get_line(curpos);
 
-        match self.get_mode(curpos) {
-            Mode::Program => Some(CompletionResponse::Array(
-                self.get_completion_list_program(curpos, trigger)?,
-            )),
-            Mode::ProgramType => None,
-            Mode::Vertical => Some(CompletionResponse::Array(
-                self.get_completion_list_vertical(curpos, line_str)?,
-            )),
-            Mode::Horizontal => Some(CompletionResponse::Array(
-                self.get_completion_list_horizontal(curpos, line_str)?,
-            )),
-            Mode::Math => Some(CompletionResponse::Array(
-                self.get_completion_list_math(curpos, line_str)?,
-            )),
-            Mode::Header => Some(CompletionResponse::Array(
-                self.get_completion_list_header(curpos)?,
-            )),
-            Mode::Literal => None,
-            Mode::Comment => None,
-        }
+        let candidates = match self.get_mode(curpos) {
+            Mode::Program => {
+                let user_items = resources.items(ResourceMode::Program);
+                self.get_completion_list_program(curpos, trigger, user_items)?
+            }
+            Mode::ProgramType => return None,
+            Mode::Vertical => [
+                self.get_completion_list_vertical(curpos, line_str, packages)?,
+                user_command_items(
+                    resources.items(ResourceMode::Vertical),
+                    curpos,
+                    line_str,
+                    0x2b,
+                ),
+            ]
+            .concat(),
+            Mode::Horizontal => [
+                self.get_completion_list_horizontal(curpos, line_str, packages)?,
+                user_command_items(
+                    resources.items(ResourceMode::Horizontal),
+                    curpos,
+                    line_str,
+                    0x5c,
+                ),
+            ]
+            .concat(),
+            Mode::Math => [
+                self.get_completion_list_math(curpos, line_str, packages)?,
+                user_command_items(resources.items(ResourceMode::Math), curpos, line_str, 0x5c),
+            ]
+            .concat(),
+            Mode::Header => [
+                self.get_completion_list_header(curpos).unwrap_or_default(),
+                builtin(resources.items(ResourceMode::Header)),
+            ]
+            .concat(),
+            Mode::Literal => return None,
+            Mode::Comment => return None,
+        };
+        Some(self.rank_completion_items(candidates, query, &curpos.url, workspace_root))
     }
 
     fn get_mode(&self, curpos: &UrlPos) -> Mode {
@@ -120,7 +179,8 @@ impl DocumentCache {
         &self,
         curpos: &UrlPos,
         trigger: Option<&str>,
-    ) -> Option<Vec<CompletionItem>> {
+        user_items: Vec<CompletionItem>,
+    ) -> Option<Candidates> {
         if trigger == Some(".") {
             return self.get_completion_list_with_module(curpos);
         }
@@ -141,7 +201,7 @@ impl DocumentCache {
             .iter()
             .filter(|var| var.scope.includes(pos_usize))
             .map(|var| {
-                variable_completion_item(
+                let item = variable_completion_item(
                     var.name.clone(),
                     "variable defined in this file".to_owned(),
                     if let ComponentBody::Variable {
@@ -153,7 +213,8 @@ impl DocumentCache {
                     } else {
                         None
                     },
-                )
+                );
+                (local_proximity(environment, var, pos_usize), item)
             })
             .collect_vec();
 
@@ -162,10 +223,11 @@ impl DocumentCache {
             .iter()
             .filter(|module| module.scope.includes(pos_usize))
             .map(|module| {
-                module_completion_item(
+                let item = module_completion_item(
                     module.name.clone(),
                     "module defined in this file".to_owned(),
-                )
+                );
+                (Proximity::Local, item)
             })
             .collect_vec();
 
@@ -185,11 +247,12 @@ impl DocumentCache {
                         doc_data.get_localized_modules(pos_usize),
                     ]
                     .concat();
+                    let is_direct = is_direct_dependency(environment, dep);
                     env_dep
                         .variables_external(&modules)
                         .iter()
                         .map(|var| {
-                            variable_completion_item(
+                            let item = variable_completion_item(
                                 var.name.clone(),
                                 format!("variable defined in package `{}`", dep.name),
                                 if let ComponentBody::Variable {
@@ -201,7 +264,8 @@ impl DocumentCache {
                                 } else {
                                     None
                                 },
-                            )
+                            );
+                            (dependency_proximity(env_dep, var, is_direct), item)
                         })
                         .collect_vec()
                 } else {
@@ -219,14 +283,16 @@ impl DocumentCache {
                     ..
                 }) = dep.url.as_ref().and_then(|url| self.get(url))
                 {
+                    let is_direct = is_direct_dependency(environment, dep);
                     env_dep
                         .modules()
                         .iter()
                         .map(|module| {
-                            module_completion_item(
+                            let item = module_completion_item(
                                 module.name.clone(),
                                 format!("module defined in package {}", dep.name),
-                            )
+                            );
+                            (dependency_proximity(env_dep, module, is_direct), item)
                         })
                         .collect_vec()
                 } else {
@@ -235,21 +301,32 @@ impl DocumentCache {
             })
             .concat();
 
-        let primitives = get_primitive_list();
+        let primitives = builtin([get_primitive_list(), user_items].concat());
 
-        Some(
-            [
-                local_variables,
-                deps_variables,
-                primitives,
-                local_modules,
-                deps_modules,
-            ]
-            .concat(),
-        )
+        let candidates = [
+            local_variables,
+            deps_variables,
+            primitives,
+            local_modules,
+            deps_modules,
+        ]
+        .concat();
+
+        // 括弧で囲まれた引数の中では、その引数に期待される型に合うものを補完する
+        if let Some(expected) = self.expected_argument_type(curpos) {
+            return Some(
+                [
+                    filter_by_type(candidates, &expected),
+                    self.variant_candidates(url, &expected),
+                    literal_templates(&expected),
+                ]
+                .concat(),
+            );
+        }
+        Some(candidates)
     }
 
-    fn get_completion_list_with_module(&self, curpos: &UrlPos) -> Option<Vec<CompletionItem>> {
+    fn get_completion_list_with_module(&self, curpos: &UrlPos) -> Option<Candidates> {
         let UrlPos { url, pos } = curpos;
         let (program_text, environment) = self.get_doc_info(url)?;
         let pos_usize = program_text.from_position(pos)?;
@@ -319,6 +396,8 @@ impl DocumentCache {
                     data: None,
                     tags: None,
                 })
+                // module 名で修飾しているので、どの module のものかは問わない
+                .map(|item| (Proximity::SameModule, item))
                 .collect_vec();
             return Some(items);
         }
@@ -329,7 +408,8 @@ impl DocumentCache {
         &self,
         curpos: &UrlPos,
         text: Option<&str>,
-    ) -> Option<Vec<CompletionItem>> {
+        packages: &PackageIndex,
+    ) -> Option<Candidates> {
         let UrlPos { url, pos } = curpos;
         let doc_data = self.get(url)?;
         let (program_text, environment) = self.get_doc_info(url)?;
@@ -343,13 +423,12 @@ impl DocumentCache {
             .iter()
             .filter(|var| var.scope.includes(pos_usize))
             .map(|cmd| {
-                self.command_completion_item(
-                    cmd.name.clone(),
+                let item = self.command_completion_item(
+                    cmd,
                     "inline-cmd defined in this file".to_owned(),
-                    &cmd.body,
-                    &cmd.url,
                     command_range,
-                )
+                );
+                (local_proximity(environment, cmd, pos_usize), item)
             })
             .collect_vec();
 
@@ -363,6 +442,7 @@ impl DocumentCache {
                     ..
                 }) = dep.url.as_ref().and_then(|url| self.get(url))
                 {
+                    let is_direct = is_direct_dependency(environment, dep);
                     env_dep
                         .inline_cmds_external(&doc_data.get_open_modules(pos_usize))
                         .iter()
@@ -370,13 +450,12 @@ impl DocumentCache {
                             matches!(cmd.visibility, Visibility::Public | Visibility::Direct)
                         })
                         .map(|cmd| {
-                            self.command_completion_item(
-                                cmd.name.clone(),
+                            let item = self.command_completion_item(
+                                cmd,
                                 format!("inline-cmd defined in package `{}`", dep.name),
-                                &cmd.body,
-                                &cmd.url,
                                 command_range,
-                            )
+                            );
+                            (dependency_proximity(env_dep, cmd, is_direct), item)
                         })
                         .collect_vec()
                 } else {
@@ -385,14 +464,18 @@ impl DocumentCache {
             })
             .concat();
 
-        Some([local_commands, deps_commands].concat())
+        let unrequired_commands =
+            self.unrequired_command_items(url, packages, Rule::inline_cmd_name, command_range);
+
+        Some([local_commands, deps_commands, unrequired_commands].concat())
     }
 
     fn get_completion_list_vertical(
         &self,
         curpos: &UrlPos,
         text: Option<&str>,
-    ) -> Option<Vec<CompletionItem>> {
+        packages: &PackageIndex,
+    ) -> Option<Candidates> {
         let UrlPos { url, pos } = curpos;
         let doc_data = self.get(url)?;
         let (program_text, environment) = self.get_doc_info(url)?;
@@ -406,13 +489,12 @@ impl DocumentCache {
             .iter()
             .filter(|var| var.scope.includes(pos_usize))
             .map(|cmd| {
-                self.command_completion_item(
-                    cmd.name.clone(),
+                let item = self.command_completion_item(
+                    cmd,
                     "block-cmd defined in this file".to_owned(),
-                    &cmd.body,
-                    &cmd.url,
                     command_range,
-                )
+                );
+                (local_proximity(environment, cmd, pos_usize), item)
             })
             .collect_vec();
 
@@ -426,6 +508,7 @@ impl DocumentCache {
                     ..
                 }) = dep.url.as_ref().and_then(|url| self.get(url))
                 {
+                    let is_direct = is_direct_dependency(environment, dep);
                     env_dep
                         .block_cmds_external(&doc_data.get_open_modules(pos_usize))
                         .iter()
@@ -433,13 +516,12 @@ impl DocumentCache {
                             matches!(cmd.visibility, Visibility::Public | Visibility::Direct)
                         })
                         .map(|cmd| {
-                            self.command_completion_item(
-                                cmd.name.clone(),
+                            let item = self.command_completion_item(
+                                cmd,
                                 format!("block-cmd defined in package `{}`", dep.name),
-                                &cmd.body,
-                                &cmd.url,
                                 command_range,
-                            )
+                            );
+                            (dependency_proximity(env_dep, cmd, is_direct), item)
                         })
                         .collect_vec()
                 } else {
@@ -448,14 +530,18 @@ impl DocumentCache {
             })
             .concat();
 
-        Some([local_commands, deps_commands].concat())
+        let unrequired_commands =
+            self.unrequired_command_items(url, packages, Rule::block_cmd_name, command_range);
+
+        Some([local_commands, deps_commands, unrequired_commands].concat())
     }
 
     fn get_completion_list_math(
         &self,
         curpos: &UrlPos,
         text: Option<&str>,
-    ) -> Option<Vec<CompletionItem>> {
+        packages: &PackageIndex,
+    ) -> Option<Candidates> {
         let UrlPos { url, pos } = curpos;
         let doc_data = self.get(url)?;
         let (program_text, environment) = self.get_doc_info(url)?;
@@ -469,13 +555,12 @@ impl DocumentCache {
             .iter()
             .filter(|var| var.scope.includes(pos_usize))
             .map(|cmd| {
-                self.command_completion_item(
-                    cmd.name.clone(),
+                let item = self.command_completion_item(
+                    cmd,
                     "math-cmd defined in this file".to_owned(),
-                    &cmd.body,
-                    &cmd.url,
                     command_range,
-                )
+                );
+                (local_proximity(environment, cmd, pos_usize), item)
             })
             .collect_vec();
 
@@ -489,6 +574,7 @@ impl DocumentCache {
                     ..
                 }) = dep.url.as_ref().and_then(|url| self.get(url))
                 {
+                    let is_direct = is_direct_dependency(environment, dep);
                     env_dep
                         .math_cmds_external(&doc_data.get_open_modules(pos_usize))
                         .iter()
@@ -496,13 +582,12 @@ impl DocumentCache {
                             matches!(cmd.visibility, Visibility::Public | Visibility::Direct)
                         })
                         .map(|cmd| {
-                            self.command_completion_item(
-                                cmd.name.clone(),
+                            let item = self.command_completion_item(
+                                cmd,
                                 format!("math-cmd defined in package `{}`", dep.name),
-                                &cmd.body,
-                                &cmd.url,
                                 command_range,
-                            )
+                            );
+                            (dependency_proximity(env_dep, cmd, is_direct), item)
                         })
                         .collect_vec()
                 } else {
@@ -511,10 +596,13 @@ impl DocumentCache {
             })
             .concat();
 
-        Some([local_commands, deps_commands].concat())
+        let unrequired_commands =
+            self.unrequired_command_items(url, packages, Rule::math_cmd_name, command_range);
+
+        Some([local_commands, deps_commands, unrequired_commands].concat())
     }
 
-    fn get_completion_list_header(&self, curpos: &UrlPos) -> Option<Vec<CompletionItem>> {
+    fn get_completion_list_header(&self, curpos: &UrlPos) -> Option<Candidates> {
         let UrlPos { url, pos } = curpos;
         let text = self.get_line(curpos)?;
         let (program_text, _) = self.get_doc_info(url)?;
@@ -528,7 +616,7 @@ impl DocumentCache {
         };
 
         if text.trim() == "@" {
-            return Some(vec![
+            let items = vec![
                 CompletionItem {
                     label: "@require:".to_owned(),
                     kind: None,
@@ -573,7 +661,8 @@ impl DocumentCache {
                     data: None,
                     tags: None,
                 },
-            ]);
+            ];
+            return Some(builtin(items));
         }
         if text.contains("@require:") {
             let file_path = url.to_file_path().ok();
@@ -618,7 +707,12 @@ impl DocumentCache {
             return Some(
                 pkg_names
                     .into_iter()
-                    .map(|s| header_completion_item("require", &s, range))
+                    .map(|s| {
+                        (
+                            Proximity::Builtin,
+                            header_completion_item("require", &s, range),
+                        )
+                    })
                     .collect(),
             );
         }
@@ -662,13 +756,72 @@ impl DocumentCache {
             return Some(
                 pkg_names
                     .into_iter()
-                    .map(|s| header_completion_item("import", &s, range))
+                    .map(|s| {
+                        (
+                            Proximity::Builtin,
+                            header_completion_item("import", &s, range),
+                        )
+                    })
                     .collect(),
             );
         }
         None
     }
 
+    /// `@require:` していないインストール済みのパッケージが公開しているコマンドの補完候補。
+    /// 選択すると `@require:` のヘッダを追加する。
+    fn unrequired_command_items(
+        &self,
+        url: &Url,
+        packages: &PackageIndex,
+        rule: Rule,
+        command_range: Option<Range>,
+    ) -> Candidates {
+        let (program_text, environment) = match self.get_doc_info(url) {
+            Some(info) => info,
+            None => return vec![],
+        };
+        let reachable = self.get_dependencies_recursive(environment.dependencies());
+        let kind = match rule {
+            Rule::inline_cmd_name => "inline-cmd",
+            Rule::block_cmd_name => "block-cmd",
+            _ => "math-cmd",
+        };
+        packages
+            .packages(&package_dirs(url))
+            .into_iter()
+            .filter(|pkg| &pkg.url != url)
+            .filter(|pkg| {
+                !reachable
+                    .iter()
+                    .any(|dep| dep.name == pkg.name || dep.url.as_ref() == Some(&pkg.url))
+            })
+            .flat_map(|pkg| {
+                let (span, new_text) = require_header_insertion(&program_text.text, &pkg.name);
+                let require_edit = program_text
+                    .get_range(span)
+                    .map(|range| TextEdit { range, new_text });
+                pkg.commands(rule).into_iter().map(move |cmd| {
+                    let mut item = self.command_completion_item(
+                        cmd,
+                        format!(
+                            "{} defined in package `{}` (adds `@require: {}`)",
+                            kind, pkg.name, pkg.name
+                        ),
+                        command_range,
+                    );
+                    // 型は resolve したときにこの後ろに付け加える
+                    item.detail = Some(match item.detail {
+                        Some(glyph) => format!("{} [{}]", glyph, pkg.name),
+                        None => format!("[{}]", pkg.name),
+                    });
+                    item.additional_text_edits = require_edit.clone().map(|edit| vec![edit]);
+                    (Proximity::NotRequired, item)
+                })
+            })
+            .collect()
+    }
+
     /// 現在補完しようとしている command の範囲を示す。
     fn get_cmd_range(pos: &Position, text: &str, chr: u16) -> Option<Range> {
         let utf16chars = text.encode_utf16().enumerate().collect_vec();
@@ -685,40 +838,28 @@ impl DocumentCache {
             })
     }
 
+    /// コマンドの補完候補。
+    /// 型や説明は completionItem/resolve で補うので、ここでは data に定義の場所のみを入れておく。
     fn command_completion_item(
         &self,
-        name: String,
+        cmd: &Component,
         desc: String,
-        body: &ComponentBody,
-        url: &Url,
         cmd_range: Option<Range>,
     ) -> CompletionItem {
-        let (detail, insert_text, insert_text_format) = match body {
+        let name = cmd.name.clone();
+        let (insert_text, insert_text_format) = match &cmd.body {
             ComponentBody::InlineCmd {
-                type_declaration: Some(dec),
+                type_declaration: Some(_),
                 type_args,
-            } => (
-                self.get_text_from_span(url, *dec).map(|s| s.to_owned()),
-                Some(form_command_text_snippet(&name, type_args)),
-                Some(InsertTextFormat::SNIPPET),
-            ),
-            ComponentBody::BlockCmd {
-                type_declaration: Some(dec),
+            }
+            | ComponentBody::BlockCmd {
+                type_declaration: Some(_),
                 type_args,
             } => (
-                self.get_text_from_span(url, *dec).map(|s| s.to_owned()),
                 Some(form_command_text_snippet(&name, type_args)),
                 Some(InsertTextFormat::SNIPPET),
             ),
-            ComponentBody::MathCmd {
-                type_declaration: Some(dec),
-                ..
-            } => (
-                self.get_text_from_span(url, *dec).map(|s| s.to_owned()),
-                None,
-                None,
-            ),
-            _ => (None, None, None),
+            _ => (None, None),
         };
 
         let text_edit = cmd_range.map(|range| {
@@ -727,30 +868,210 @@ impl DocumentCache {
                 new_text: insert_text.clone().unwrap_or_else(|| name.clone()),
             })
         });
+        // 数式コマンドが出力する文字を detail に示し、その文字名でも絞り込めるようにする
+        let (detail, filter_text) = match &cmd.body {
+            ComponentBody::MathCmd {
+                glyph: Some(glyph), ..
+            } => (
+                Some(glyph.clone()),
+                glyph_name(glyph)
+                    .map(|glyph_name| format!("{} {}", name, glyph_name.to_lowercase())),
+            ),
+            _ => (None, None),
+        };
+        let data = CompletionData {
+            url: cmd.url.clone(),
+            pos: cmd.pos_definition.start,
+            desc,
+        };
 
         CompletionItem {
             label: name,
             kind: Some(CompletionItemKind::VARIABLE),
             detail,
-            documentation: Some(Documentation::MarkupContent(MarkupContent {
-                kind: MarkupKind::Markdown,
-                value: desc,
-            })),
+            documentation: None,
             deprecated: None,
             preselect: None,
             sort_text: None,
-            filter_text: None,
+            filter_text,
             insert_text,
             insert_text_format,
             insert_text_mode: None,
             text_edit,
             additional_text_edits: None,
             command: None,
-            data: None,
+            data: serde_json::to_value(data).ok(),
             tags: None,
             commit_characters: None,
         }
     }
+
+    /// data に入れておいた定義の場所から、コマンドの型・ドキュメントコメント・
+    /// 定義しているパッケージ・使用例を補う。
+    pub fn resolve_completion_item(
+        &self,
+        item: CompletionItem,
+        packages: &PackageIndex,
+    ) -> CompletionItem {
+        let data = item
+            .data
+            .clone()
+            .and_then(|data| serde_json::from_value::<CompletionData>(data).ok());
+        let data = match data {
+            Some(data) => data,
+            None => return item,
+        };
+        // `@require:` していないパッケージのものは DocumentCache に無い
+        let doc_data = self
+            .get(&data.url)
+            .or_else(|| packages.find(&data.url).map(|pkg| &pkg.doc_data));
+        let (text, component) = match doc_data {
+            Some(DocumentData::Parsed {
+                program_text,
+                environment,
+            }) => match environment.component_at(data.pos) {
+                Some(component) => (program_text.text.as_str(), component),
+                None => return item,
+            },
+            _ => return item,
+        };
+
+        let signature =
+            cmd_type_declaration(&component.body).and_then(|span| text.get(span.start..span.end));
+        let detail = match (item.detail.clone(), signature) {
+            (Some(prefix), Some(signature)) => Some(format!("{} {}", prefix, signature)),
+            (None, Some(signature)) => Some(signature.to_owned()),
+            (detail, None) => detail,
+        };
+
+        let mut docs = vec![data.desc];
+        let documented_at = component
+            .pos_declaration
+            .unwrap_or(component.pos_definition)
+            .start;
+        docs.extend(doc_comment(text, documented_at));
+        if let ComponentBody::InlineCmd { type_args, .. }
+        | ComponentBody::BlockCmd { type_args, .. } = &component.body
+        {
+            docs.push(format!(
+                "```satysfi\n{}\n```",
+                form_command_example(&component.name, type_args)
+            ));
+        }
+
+        CompletionItem {
+            detail,
+            documentation: Some(Documentation::MarkupContent(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: docs.join("\n\n"),
+            })),
+            ..item
+        }
+    }
+}
+
+/// completionItem/resolve で補う情報を探すための、補完候補に持たせるデータ。
+#[derive(Debug, Serialize, Deserialize)]
+struct CompletionData {
+    /// 定義しているファイル。
+    url: Url,
+    /// 定義の位置。
+    pos: usize,
+    /// どこで定義されているかの説明。
+    desc: String,
+}
+
+/// コマンドの型が signature に宣言されていれば、その場所。
+fn cmd_type_declaration(body: &ComponentBody) -> Option<Span> {
+    match body {
+        ComponentBody::InlineCmd {
+            type_declaration, ..
+        }
+        | ComponentBody::BlockCmd {
+            type_declaration, ..
+        }
+        | ComponentBody::MathCmd {
+            type_declaration, ..
+        } => *type_declaration,
+        _ => None,
+    }
+}
+
+/// 補完候補の text_edit の範囲を、与えられた行の内容に合わせて計算し直す。
+/// additional_text_edits の範囲は current_range で最新のテキスト上の範囲に変換する。
+/// パースに失敗している間、古い解析結果から補完候補を作ったときに用いる。
+pub fn recompute_edit_ranges(
+    response: CompletionResponse,
+    line: &str,
+    pos: &Position,
+    current_range: impl Fn(Range) -> Option<Range>,
+) -> CompletionResponse {
+    let recompute = |item: CompletionItem| {
+        let additional_text_edits = item.additional_text_edits.map(|edits| {
+            edits
+                .into_iter()
+                .filter_map(|edit| {
+                    Some(TextEdit {
+                        range: current_range(edit.range)?,
+                        ..edit
+                    })
+                })
+                .collect()
+        });
+        let text_edit = match item.text_edit {
+            Some(CompletionTextEdit::Edit(TextEdit { new_text, .. })) => {
+                // 置き換える範囲は挿入するテキストの先頭文字（`\`, `+`, `@`）から始まる
+                let chr = new_text.encode_utf16().next();
+                chr.and_then(|chr| DocumentCache::get_cmd_range(pos, line, chr))
+                    .map(|range| CompletionTextEdit::Edit(TextEdit { range, new_text }))
+            }
+            _ => None,
+        };
+        CompletionItem {
+            text_edit,
+            additional_text_edits,
+            ..item
+        }
+    };
+    match response {
+        CompletionResponse::Array(items) => {
+            CompletionResponse::Array(items.into_iter().map(recompute).collect())
+        }
+        CompletionResponse::List(mut list) => {
+            list.items = list.items.into_iter().map(recompute).collect();
+            CompletionResponse::List(list)
+        }
+    }
+}
+
+/// ユーザが追加したコマンドの補完候補。
+/// ラベルがコマンドの記号で始まるものは、入力中のコマンドをその記号から置き換える。
+fn user_command_items(
+    items: Vec<CompletionItem>,
+    curpos: &UrlPos,
+    text: Option<&str>,
+    chr: u16,
+) -> Candidates {
+    let sigil = String::from_utf16_lossy(&[chr]);
+    let cmd_range = text.and_then(|text| DocumentCache::get_cmd_range(&curpos.pos, text, chr));
+    let items = items
+        .into_iter()
+        .map(|item| match cmd_range {
+            Some(range) if item.label.starts_with(&sigil) => CompletionItem {
+                text_edit: Some(CompletionTextEdit::Edit(TextEdit {
+                    range,
+                    new_text: item
+                        .insert_text
+                        .clone()
+                        .unwrap_or_else(|| item.label.clone()),
+                })),
+                insert_text: None,
+                ..item
+            },
+            _ => item,
+        })
+        .collect();
+    builtin(items)
 }
 
 fn module_completion_item(name: String, desc: String) -> CompletionItem {
@@ -872,6 +1193,89 @@ fn form_command_text_snippet(name: &str, type_args: &[String]) -> String {
     )
 }
 
+/// 補完候補の説明に載せる、コマンドの使用例。
+fn form_command_example(name: &str, type_args: &[String]) -> String {
+    let snippet = form_command_text_snippet(name, type_args);
+    let snippet = snippet.strip_suffix("$0").unwrap_or(&snippet);
+    // スニペットのためにエスケープした `\` を戻し、プレースホルダを `…` にする
+    let snippet = snippet.strip_prefix('\\').unwrap_or(snippet);
+    let placeholder = Regex::new(r#"\$\d+"#).unwrap();
+    placeholder.replace_all(snippet, "…").into_owned()
+}
+
+/// その型の引数を省略形（`\cmd{...}` や `+cmd<...>`）で書けるか。
+pub(super) fn is_compactible_type(ty: &str) -> bool {
+    ArgType::from_str(ty).is_compactible()
+}
+
+/// 型が text_type である最初の必須引数に content を与えた、コマンドの呼び出し。
+/// そのような必須引数がなければ None。
+/// その引数が最後の必須引数であれば省略形（`\cmd{...}`）で、そうでなければ括弧つきで書く。
+/// snippet が真であればスニペットとして書き、残りの必須引数をプレースホルダにする。
+/// そうでなければ残りの必須引数を空のテキストとし、テキスト以外の必須引数があれば None。
+pub(super) fn form_command_wrapping(
+    name: &str,
+    type_args: &[String],
+    text_type: &str,
+    content: &str,
+    snippet: bool,
+) -> Option<String> {
+    let required = type_args
+        .iter()
+        .map(|arg| ArgType::from_str(arg.as_str()))
+        .filter(|arg| !arg.optional)
+        .collect_vec();
+    let target = required.iter().position(|arg| arg.name == text_type)?;
+    // テキスト以外の引数には型の合う空の値を置けない
+    if !snippet && !required.iter().all(ArgType::is_compactible) {
+        return None;
+    }
+    let content = if snippet {
+        content
+            .replace('\\', "\\\\")
+            .replace('$', "\\$")
+            .replace('}', "\\}")
+    } else {
+        content.to_owned()
+    };
+
+    let mut args = vec![];
+    let mut compactible = true;
+    let mut require_semicolon = true;
+    for (idx, arg) in required.iter().enumerate().rev() {
+        // 省略形で書けるのは末尾に連続する引数のみ
+        if !arg.is_compactible() || (idx == target && idx + 1 != required.len()) {
+            compactible = false;
+        }
+        if compactible {
+            require_semicolon = false;
+        }
+        let placeholder = format!("${}", idx + 1);
+        let arg = arg.as_snippet(idx + 1, compactible);
+        args.push(if idx == target {
+            arg.replace(&placeholder, &content)
+        } else if snippet {
+            arg
+        } else {
+            arg.replace(&placeholder, "")
+        });
+    }
+    args.reverse();
+
+    let name = if snippet && name.starts_with('\\') {
+        // `\` はスニペットの場合エスケープする必要がある
+        format!("\\{}", name)
+    } else {
+        name.to_owned()
+    };
+    Some(format!(
+        "{}{}{}",
+        name,
+        args.join(""),
+        if require_semicolon { ";" } else { "" }
+    ))
+}
+
 struct ArgType<'a> {
     name: &'a str,
     optional: bool,
@@ -0,0 +1,166 @@
+//! 文字列リテラルの中での補完。
+//!
+//! 文字列リテラルを書いている途中はパースに失敗していることが多いため、
+//! 解析結果ではなく最新のテキストのカーソルより前の部分から補完候補を作る。
+
+use std::{fs, path::Path};
+
+use lspower::lsp::{
+    CompletionItem, CompletionItemKind, CompletionResponse, CompletionTextEdit, Position, Range,
+    TextEdit, Url,
+};
+
+use crate::{
+    config::{CrossReferenceConfig, PathArgument},
+    documents::{unclosed_font_literal, CrossReferences, FontKind, FontNames},
+    util::literal_argument,
+};
+
+/// カーソルが特定の意味を持つ文字列リテラルの中にあれば、その中身の補完候補。
+/// フォント名を指定する文字列リテラル、ファイルパスを受け取る引数の文字列リテラル、
+/// 相互参照のキーを参照する引数の文字列リテラルを対象とする。
+pub fn get_literal_completion(
+    url: &Url,
+    line: &str,
+    pos: &Position,
+    fonts: &FontNames,
+    path_arguments: &[PathArgument],
+    cross_reference: &CrossReferenceConfig,
+    references: &CrossReferences,
+) -> Option<CompletionResponse> {
+    let before: Vec<u16> = line.encode_utf16().take(pos.character as usize).collect();
+    let before = String::from_utf16_lossy(&before);
+    let open = before.rfind('`')?;
+    // 開きの '`' の直後からカーソルまでを置き換える
+    let range = Range {
+        start: Position {
+            line: pos.line,
+            character: before[..=open].encode_utf16().count() as u32,
+        },
+        end: *pos,
+    };
+
+    if let Some(kind) = unclosed_font_literal(&before) {
+        return Some(font_completion(fonts, kind, range));
+    }
+
+    // 閉じていない文字列リテラルの中にあるときのみ補完する
+    if before.matches('`').count() % 2 == 0 {
+        return None;
+    }
+    // 文字列リテラルがどの関数・コマンドの何番目の引数であるかによって補完するものを決める
+    let (name, position) = literal_argument(&before[..open])?;
+    if let Some(arg) = path_arguments
+        .iter()
+        .find(|arg| arg.command == name && arg.position == position)
+    {
+        return path_completion(url, &before[open + 1..], arg, pos);
+    }
+    if cross_reference
+        .references
+        .iter()
+        .any(|arg| arg.matches(&name, position))
+    {
+        return Some(key_completion(references, range));
+    }
+    None
+}
+
+fn font_completion(fonts: &FontNames, kind: FontKind, range: Range) -> CompletionResponse {
+    let detail = match kind {
+        FontKind::Text => "font registered in fonts.satysfi-hash",
+        FontKind::Math => "math font registered in math-fonts.satysfi-hash",
+    };
+    let items = fonts
+        .get(kind)
+        .iter()
+        .map(|font| CompletionItem {
+            label: font.clone(),
+            kind: Some(CompletionItemKind::VALUE),
+            detail: Some(detail.to_owned()),
+            text_edit: Some(CompletionTextEdit::Edit(TextEdit {
+                range,
+                new_text: font.clone(),
+            })),
+            ..Default::default()
+        })
+        .collect();
+    CompletionResponse::Array(items)
+}
+
+/// 文書全体で定義されている相互参照のキーの補完候補。
+fn key_completion(references: &CrossReferences, range: Range) -> CompletionResponse {
+    let items = references
+        .keys()
+        .into_iter()
+        .map(|key| CompletionItem {
+            label: key.to_owned(),
+            kind: Some(CompletionItemKind::REFERENCE),
+            detail: Some("cross-reference key".to_owned()),
+            text_edit: Some(CompletionTextEdit::Edit(TextEdit {
+                range,
+                new_text: key.to_owned(),
+            })),
+            ..Default::default()
+        })
+        .collect();
+    CompletionResponse::Array(items)
+}
+
+/// 文書のあるディレクトリからの相対パスの補完候補。
+/// 入力中のパスの最後の `/` より後ろの部分を、そのディレクトリにあるファイル名で補完する。
+fn path_completion(
+    url: &Url,
+    typed: &str,
+    arg: &PathArgument,
+    pos: &Position,
+) -> Option<CompletionResponse> {
+    let doc_dir = url.to_file_path().ok()?.parent()?.to_owned();
+    let (dir_part, name_part) = match typed.rfind('/') {
+        Some(idx) => (&typed[..=idx], &typed[idx + 1..]),
+        None => ("", typed),
+    };
+    let range = Range {
+        start: Position {
+            line: pos.line,
+            character: pos.character - name_part.encode_utf16().count() as u32,
+        },
+        end: *pos,
+    };
+    let show_hidden = name_part.starts_with('.');
+
+    let items = fs::read_dir(doc_dir.join(dir_part))
+        .ok()?
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            if name.starts_with('.') && !show_hidden {
+                return None;
+            }
+            let is_dir = entry.path().is_dir();
+            if !is_dir {
+                let ext = Path::new(&name).extension()?.to_str()?.to_lowercase();
+                if !arg.extensions.iter().any(|e| e.to_lowercase() == ext) {
+                    return None;
+                }
+            }
+            let (label, kind) = if is_dir {
+                (format!("{}/", name), CompletionItemKind::FOLDER)
+            } else {
+                (name, CompletionItemKind::FILE)
+            };
+            Some(CompletionItem {
+                // ディレクトリを先に並べる
+                sort_text: Some(format!("{}{}", !is_dir as u8, label)),
+                kind: Some(kind),
+                text_edit: Some(CompletionTextEdit::Edit(TextEdit {
+                    range,
+                    new_text: label.clone(),
+                })),
+                label,
+                ..Default::default()
+            })
+        })
+        .collect();
+    Some(CompletionResponse::Array(items))
+}
@@ -0,0 +1,276 @@
+//! 補完候補の絞り込みと並べ替え。
+//!
+//! 定義されている場所の近さ、入力中の文字列との一致の度合い、
+//! ワークスペース内のファイルでの使用頻度の順に候補を並べ、その順序を sort_text に書き込む。
+
+use std::{cmp::Reverse, collections::HashMap, path::Path};
+
+use itertools::Itertools;
+use lspower::lsp::{CompletionItem, CompletionList, CompletionResponse, Position, Url};
+
+use crate::documents::{
+    Component, ComponentBody, Dependency, DocumentCache, DocumentData, Environment, Visibility,
+};
+
+/// 一度に返す補完候補の最大数。
+/// これを超える場合は is_incomplete として、入力が進んだときに再度要求させる。
+const MAX_ITEMS: usize = 200;
+
+/// 定義されている場所の近さを付けた補完候補。
+pub type Candidates = Vec<(Proximity, CompletionItem)>;
+
+/// 補完候補が定義されている場所の近さ。先にあるものほど近い。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Proximity {
+    /// 括弧で囲まれた引数の中で、その引数に期待される型に合うもの。
+    ExpectedType,
+    /// このファイルで定義されているもの。
+    Local,
+    /// カーソルを含む module で定義されているもの。
+    SameModule,
+    /// open している module で定義されているもの。
+    OpenedModule,
+    /// 直接 `@require:` / `@import:` しているパッケージで定義されているもの。
+    DirectDependency,
+    /// 間接的に読み込まれているパッケージで定義されているもの。
+    TransitiveDependency,
+    /// プリミティブやヘッダなど、組み込みのもの。
+    Builtin,
+    /// まだ `@require:` していないパッケージで定義されているもの。
+    NotRequired,
+}
+
+/// 入力中の文字列との一致の度合い。先にあるものほど良い。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum MatchQuality {
+    Exact,
+    Prefix,
+    CaseInsensitivePrefix,
+    Substring,
+    Subsequence,
+}
+
+/// コマンド名などの先頭に付く記号。一致の判定では無視する。
+const SIGILS: &[char] = &['\\', '+', '#', '@'];
+
+impl DocumentCache {
+    /// 補完候補を入力中の文字列で絞り込み、定義の近さ・一致の度合い・使用頻度の順に並べる。
+    pub fn rank_completion_items(
+        &self,
+        candidates: Candidates,
+        query: &str,
+        url: &Url,
+        workspace_root: Option<&Path>,
+    ) -> CompletionResponse {
+        let frequency = self.usage_frequency(url, workspace_root);
+        let mut ranked = candidates
+            .into_iter()
+            .filter_map(|(proximity, item)| {
+                let label = item.filter_text.as_deref().unwrap_or(&item.label);
+                let quality = match_quality(label, query)?;
+                let count = frequency.get(item.label.as_str()).copied().unwrap_or(0);
+                Some(((proximity, quality, Reverse(count)), item))
+            })
+            .collect_vec();
+        ranked.sort_by(|(key1, item1), (key2, item2)| {
+            key1.cmp(key2).then_with(|| item1.label.cmp(&item2.label))
+        });
+
+        let is_incomplete = ranked.len() > MAX_ITEMS;
+        let items = ranked
+            .into_iter()
+            .take(MAX_ITEMS)
+            .enumerate()
+            .map(|(idx, (_, item))| CompletionItem {
+                sort_text: Some(format!("{:04}", idx)),
+                // スニペットではなくラベルで絞り込ませる
+                filter_text: item
+                    .filter_text
+                    .clone()
+                    .or_else(|| Some(item.label.clone())),
+                ..item
+            })
+            .collect();
+        if is_incomplete {
+            CompletionResponse::List(CompletionList {
+                is_incomplete,
+                items,
+            })
+        } else {
+            CompletionResponse::Array(items)
+        }
+    }
+
+    /// ワークスペース内のファイルでの、各変数名・コマンド名・module 名の出現回数の合計。
+    /// パッケージでの出現は利用者の書き方を表さないので数えない。
+    /// ワークスペースが開かれていなければ、補完を行っているファイルのみを数える。
+    fn usage_frequency(&self, url: &Url, workspace_root: Option<&Path>) -> HashMap<&str, usize> {
+        let is_workspace_file = |doc_url: &Url| match workspace_root {
+            Some(root) => doc_url
+                .to_file_path()
+                .map_or(false, |path| path.starts_with(root)),
+            None => doc_url == url,
+        };
+        let mut frequency = HashMap::new();
+        for (doc_url, doc_data) in &self.0 {
+            if let DocumentData::Parsed { environment, .. } = doc_data {
+                if !is_workspace_file(doc_url) {
+                    continue;
+                }
+                for (name, count) in environment.usage() {
+                    *frequency.entry(name.as_str()).or_insert(0) += count;
+                }
+            }
+        }
+        frequency
+    }
+}
+
+/// 行のうちカーソルより前の部分から、補完しようとしている入力中の文字列を取り出す。
+/// module 名で修飾されている場合は `.` より後ろの部分とする。
+pub fn completion_query(line: &str, pos: &Position) -> String {
+    let before: Vec<u16> = line.encode_utf16().take(pos.character as usize).collect();
+    let before = String::from_utf16_lossy(&before);
+    let query: String = before
+        .chars()
+        .rev()
+        .take_while(|&c| c.is_alphanumeric() || c == '-' || c == '_' || c == '/')
+        .collect();
+    query.chars().rev().collect()
+}
+
+/// 入力中の文字列との一致の度合い。一致しなければ None。
+fn match_quality(label: &str, query: &str) -> Option<MatchQuality> {
+    let label = label.trim_start_matches(SIGILS);
+    let query = query.trim_start_matches(SIGILS);
+    if query.is_empty() {
+        return Some(MatchQuality::Prefix);
+    }
+    if label == query {
+        return Some(MatchQuality::Exact);
+    }
+    if label.starts_with(query) {
+        return Some(MatchQuality::Prefix);
+    }
+    let (label, query) = (label.to_lowercase(), query.to_lowercase());
+    if label.starts_with(&query) {
+        Some(MatchQuality::CaseInsensitivePrefix)
+    } else if label.contains(&query) {
+        Some(MatchQuality::Substring)
+    } else if is_subsequence(&query, &label) {
+        Some(MatchQuality::Subsequence)
+    } else {
+        None
+    }
+}
+
+/// query の文字がすべて、この順に label に現れるか。
+fn is_subsequence(query: &str, label: &str) -> bool {
+    let mut label = label.chars();
+    query.chars().all(|c| label.any(|l| l == c))
+}
+
+/// 組み込みの補完候補。
+pub fn builtin(items: Vec<CompletionItem>) -> Candidates {
+    items
+        .into_iter()
+        .map(|item| (Proximity::Builtin, item))
+        .collect()
+}
+
+/// そのファイルで定義されているコンポーネントの近さ。
+/// module の中で定義されているものは、カーソルがその module の中にあるかで区別する。
+pub fn local_proximity(environment: &Environment, component: &Component, pos: usize) -> Proximity {
+    match enclosing_module(environment, component) {
+        // module の scope は module の定義の直後から始まる
+        Some(module) if module.pos_definition.start <= pos && pos < module.scope.start => {
+            Proximity::SameModule
+        }
+        Some(_) => Proximity::OpenedModule,
+        None => Proximity::Local,
+    }
+}
+
+/// 依存しているパッケージで定義されているコンポーネントの近さ。
+pub fn dependency_proximity(
+    env_dep: &Environment,
+    component: &Component,
+    is_direct: bool,
+) -> Proximity {
+    if enclosing_module(env_dep, component).is_some() {
+        Proximity::OpenedModule
+    } else if is_direct {
+        Proximity::DirectDependency
+    } else {
+        Proximity::TransitiveDependency
+    }
+}
+
+/// そのファイルのヘッダに直接書かれている依存先か。
+pub fn is_direct_dependency(environment: &Environment, dep: &Dependency) -> bool {
+    environment
+        .dependencies()
+        .iter()
+        .any(|direct| direct.kind == dep.kind && direct.name == dep.name)
+}
+
+/// コンポーネントを含む module。 `direct` で公開されているものは module の外にあるものとみなす。
+fn enclosing_module<'a>(
+    environment: &'a Environment,
+    component: &Component,
+) -> Option<&'a Component> {
+    if component.visibility == Visibility::Direct {
+        return None;
+    }
+    environment.modules().into_iter().find(|module| {
+        matches!(&module.body, ComponentBody::Module { components }
+            if components.iter().any(|c| std::ptr::eq(c, component)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use lspower::lsp::Position;
+
+    use super::{completion_query, match_quality, MatchQuality};
+
+    #[test]
+    fn quality_of_each_kind() {
+        assert_eq!(match_quality("\\emph", "emph"), Some(MatchQuality::Exact));
+        assert_eq!(match_quality("\\emph", "em"), Some(MatchQuality::Prefix));
+        assert_eq!(
+            match_quality("\\Emph", "em"),
+            Some(MatchQuality::CaseInsensitivePrefix)
+        );
+        assert_eq!(
+            match_quality("+section", "ction"),
+            Some(MatchQuality::Substring)
+        );
+        assert_eq!(
+            match_quality("+subsection", "sbs"),
+            Some(MatchQuality::Subsequence)
+        );
+        assert_eq!(match_quality("\\emph", "xyz"), None);
+        assert_eq!(match_quality("\\emph", ""), Some(MatchQuality::Prefix));
+    }
+
+    #[test]
+    fn prefix_before_substring_before_fuzzy() {
+        let query = "sec";
+        let labels = ["+side-cap", "+subsection", "+section", "+sec"];
+        let mut ranked: Vec<_> = labels
+            .iter()
+            .filter_map(|label| Some((match_quality(label, query)?, *label)))
+            .collect();
+        ranked.sort();
+        let ranked: Vec<&str> = ranked.into_iter().map(|(_, label)| label).collect();
+        assert_eq!(ranked, vec!["+sec", "+section", "+subsection", "+side-cap"]);
+    }
+
+    #[test]
+    fn query_before_cursor() {
+        let line = "  \\Mod.inser-image";
+        assert_eq!(completion_query(line, &Position::new(0, 12)), "inser");
+        assert_eq!(completion_query("+sec", &Position::new(0, 4)), "sec");
+    }
+}
@@ -0,0 +1,90 @@
+//! ユーザが追加する補完候補のリソースファイル。
+//!
+//! 組み込みの `completion_items.toml` と同じ形式の TOML ファイルを設定で指定すると、
+//! その中の項目を組み込みの候補と併せて補完する。
+//! ファイルは更新時刻を見て、変更されていれば読み込み直す。
+//! 補完のたびにファイルシステムを調べないよう、更新時刻を調べる間隔を空ける。
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+use itertools::Itertools;
+use log::error;
+use lspower::lsp::CompletionItem;
+
+use super::{CompletionResourceItem, ResourceMode};
+
+/// リソースファイルの更新時刻を調べ直すまでの間隔。
+const CHECK_INTERVAL: Duration = Duration::from_secs(3);
+
+/// 読み込んだリソースファイルごとの、更新時刻とその中の項目。
+#[derive(Debug, Default)]
+pub struct CompletionResources {
+    files: HashMap<PathBuf, (Option<SystemTime>, Vec<CompletionResourceItem>)>,
+    /// 最後に更新時刻を調べた時刻。
+    checked_at: Option<Instant>,
+}
+
+impl CompletionResources {
+    /// 指定したリソースファイルを読み込む。
+    /// 前回から更新されていないファイルは読み込み直さず、指定されなくなったファイルは取り除く。
+    /// 指定されたファイルが前回と同じであれば、前回から CHECK_INTERVAL が経つまでは何もしない。
+    pub fn load(&mut self, paths: &[PathBuf]) {
+        let same_paths = paths.iter().all(|path| self.files.contains_key(path))
+            && self.files.keys().all(|path| paths.contains(path));
+        let recently_checked = self
+            .checked_at
+            .map_or(false, |checked_at| checked_at.elapsed() < CHECK_INTERVAL);
+        if same_paths && recently_checked {
+            return;
+        }
+        self.checked_at = Some(Instant::now());
+
+        self.files.retain(|path, _| paths.contains(path));
+        for path in paths {
+            let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+            if self.files.get(path).map(|(loaded, _)| *loaded) == Some(modified) {
+                continue;
+            }
+            self.files
+                .insert(path.clone(), (modified, read_resource_file(path)));
+        }
+    }
+
+    /// 指定したモードで補完する項目。
+    pub fn items(&self, mode: ResourceMode) -> Vec<CompletionItem> {
+        self.files
+            .keys()
+            .sorted()
+            .flat_map(|path| &self.files[path].1)
+            .filter(|item| item.mode.unwrap_or(ResourceMode::Program) == mode)
+            .cloned()
+            .map(CompletionItem::from)
+            .collect()
+    }
+}
+
+/// リソースファイルの項目。ファイルが無い、または形式が誤っている場合は空とする。
+fn read_resource_file(path: &Path) -> Vec<CompletionResourceItem> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) => {
+            error!("failed to read the completion resource {:?}: {}", path, err);
+            return vec![];
+        }
+    };
+    match toml::from_str::<HashMap<String, Vec<CompletionResourceItem>>>(&text) {
+        Ok(resources) => resources.into_iter().flat_map(|(_, items)| items).collect(),
+        Err(err) => {
+            error!(
+                "failed to parse the completion resource {:?}: {}",
+                path, err
+            );
+            vec![]
+        }
+    }
+}
@@ -0,0 +1,256 @@
+//! 引数に期待される型に基づく補完。
+//!
+//! カーソルが関数・コマンドの括弧で囲まれた引数の中にあるとき、その引数の型を
+//! コマンドの type_args または関数の signature から求める。
+//! 型の分かっている候補はその型が一致するもののみを残して優先し、
+//! 期待される型の variant のコンストラクタとリテラルの雛形を加える。
+
+use lspower::lsp::{CompletionItem, CompletionItemKind, InsertTextFormat, Url};
+
+use crate::{
+    documents::{Component, ComponentBody, DocumentCache, DocumentData, Visibility},
+    util::{line_start, unqualified, written_arguments, ConvertPosition, UrlPos},
+};
+
+use super::ranking::{Candidates, Proximity};
+
+impl DocumentCache {
+    /// カーソルが関数・コマンドの括弧で囲まれた引数の中にあれば、その引数に期待される型。
+    /// `?:` で与えた引数はオプション引数の型に、それ以外は必須引数の型に、前から順に対応させる。
+    /// 呼び出しのうち、引数の開き括弧と同じ行に書かれた部分のみを読むため、
+    /// 関数名やそれより前の引数が前の行にある場合は型を求めない。
+    pub(super) fn expected_argument_type(&self, curpos: &UrlPos) -> Option<String> {
+        let (program_text, _) = self.get_doc_info(&curpos.url)?;
+        let pos = program_text.from_position(&curpos.pos)?;
+        let text = program_text.text.as_str();
+
+        let open = unclosed_paren(&text[..pos])?;
+        let before = text[line_start(text, open)..open].trim_end();
+        let (before, is_optional) = match before.strip_suffix("?:") {
+            Some(before) => (before, true),
+            None => (before, false),
+        };
+        let (callee, written) = written_arguments(before)?;
+        let component = self.find_callee(&curpos.url, &unqualified(callee), pos)?;
+        let params: Vec<&str> = match &component.body {
+            ComponentBody::InlineCmd { type_args, .. }
+            | ComponentBody::BlockCmd { type_args, .. } => {
+                type_args.iter().map(|ty| ty.trim()).collect()
+            }
+            ComponentBody::Variable {
+                type_declaration: Some(span),
+            } => {
+                let ty = self.get_text_from_span(&component.url, *span)?;
+                let mut params = split_arrows(ty);
+                // 最後の要素は返り値の型
+                params.pop();
+                params
+            }
+            _ => return None,
+        };
+        let index = written
+            .iter()
+            .filter(|&&optional| optional == is_optional)
+            .count();
+        let ty = params
+            .into_iter()
+            .filter(|ty| ty.ends_with('?') == is_optional)
+            .nth(index)?;
+        Some(normalize_type(ty.trim_end_matches('?')))
+    }
+
+    /// 呼び出されている関数・コマンドの定義。
+    /// このファイルで定義されているもの（module の中も含む）、依存先で公開されているものの順に探す。
+    fn find_callee(&self, url: &Url, name: &str, pos: usize) -> Option<&Component> {
+        let (_, environment) = self.get_doc_info(url)?;
+        let is_callee = |c: &&Component| {
+            c.name == name
+                && matches!(
+                    c.body,
+                    ComponentBody::Variable { .. }
+                        | ComponentBody::InlineCmd { .. }
+                        | ComponentBody::BlockCmd { .. }
+                )
+        };
+        let local = all_components(environment.components());
+        let local = local
+            .into_iter()
+            .filter(is_callee)
+            .max_by_key(|c| c.scope.includes(pos));
+        if let Some(component) = local {
+            return Some(component);
+        }
+        self.get_dependencies_recursive(environment.dependencies())
+            .into_iter()
+            .filter_map(|dep| match dep.url.as_ref().and_then(|url| self.get(url)) {
+                Some(DocumentData::Parsed { environment, .. }) => Some(environment),
+                _ => None,
+            })
+            .flat_map(|environment| all_components(environment.components()))
+            .filter(|c| c.visibility != Visibility::Private)
+            .find(is_callee)
+    }
+
+    /// 期待される型の variant のコンストラクタ。
+    pub(super) fn variant_candidates(&self, url: &Url, expected: &str) -> Candidates {
+        let (_, environment) = match self.get_doc_info(url) {
+            Some(info) => info,
+            None => return vec![],
+        };
+        let deps = self
+            .get_dependencies_recursive(environment.dependencies())
+            .into_iter()
+            .filter_map(|dep| match dep.url.as_ref().and_then(|url| self.get(url)) {
+                Some(DocumentData::Parsed { environment, .. }) => Some(environment),
+                _ => None,
+            });
+        std::iter::once(environment)
+            .chain(deps)
+            .flat_map(|environment| all_components(environment.components()))
+            .filter(|c| {
+                matches!(&c.body, ComponentBody::Variant { type_name }
+                    if normalize_type(type_name) == expected)
+            })
+            .map(|c| {
+                let item = CompletionItem {
+                    label: c.name.clone(),
+                    kind: Some(CompletionItemKind::ENUM_MEMBER),
+                    detail: Some(expected.to_owned()),
+                    ..Default::default()
+                };
+                (Proximity::ExpectedType, item)
+            })
+            .collect()
+    }
+}
+
+/// 型の分かっている候補を、期待される型に合うものに絞り込む。
+/// 変数やプリミティブの候補は detail にその型を持っている。
+///
+/// - 型が一致する変数と、 `unit -> t` 型の関数は最も近い候補とする
+/// - 返り値の型が一致する関数は、引数を与えれば使えるためそのまま残す
+/// - 型の分からない候補（module など）もそのまま残す
+pub fn filter_by_type(candidates: Candidates, expected: &str) -> Candidates {
+    candidates
+        .into_iter()
+        .filter_map(|(proximity, item)| {
+            let ty = match &item.detail {
+                Some(ty) if item.kind != Some(CompletionItemKind::MODULE) => normalize_type(ty),
+                _ => return Some((proximity, item)),
+            };
+            let params = split_arrows(&ty);
+            let ret = normalize_type(params.last()?);
+            if ty == expected || (params.len() == 2 && params[0] == "unit" && ret == expected) {
+                Some((Proximity::ExpectedType, item))
+            } else if ret == expected {
+                Some((proximity, item))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// 期待される型のリテラルの雛形。
+pub fn literal_templates(expected: &str) -> Candidates {
+    let templates: &[(&str, &str)] = match expected {
+        "bool" => &[("true", "true"), ("false", "false")],
+        "int" => &[("0", "${1:0}")],
+        "float" => &[("0.", "${1:0}.")],
+        "length" => &[
+            ("0pt", "${1:0}pt"),
+            ("0mm", "${1:0}mm"),
+            ("0cm", "${1:0}cm"),
+        ],
+        "string" => &[("` `", "`$1`")],
+        "unit" => &[("()", "()")],
+        "inline-text" => &[("{ }", "{$1}")],
+        "block-text" => &[("'< >", "'<$1>")],
+        "math" => &[("${ }", "\\${$1}")],
+        "color" => &[
+            ("Gray", "Gray(${1:0.})"),
+            ("RGB", "RGB(${1:0.}, ${2:0.}, ${3:0.})"),
+            ("CMYK", "CMYK(${1:0.}, ${2:0.}, ${3:0.}, ${4:0.})"),
+        ],
+        ty if ty.ends_with(" list") => &[("[ ]", "[$1]")],
+        ty if ty.starts_with("(|") => &[("(| |)", "(| $1 |)")],
+        _ => &[],
+    };
+    templates
+        .iter()
+        .map(|&(label, snippet)| {
+            let item = CompletionItem {
+                label: label.to_owned(),
+                kind: Some(CompletionItemKind::VALUE),
+                detail: Some(expected.to_owned()),
+                insert_text: Some(snippet.to_owned()),
+                insert_text_format: Some(InsertTextFormat::SNIPPET),
+                ..Default::default()
+            };
+            (Proximity::ExpectedType, item)
+        })
+        .collect()
+}
+
+/// module の中で定義されているものも含めたコンポーネント。
+fn all_components(components: &[Component]) -> Vec<&Component> {
+    let mut all = vec![];
+    for component in components {
+        all.push(component);
+        if let ComponentBody::Module { components } = &component.body {
+            all.extend(all_components(components));
+        }
+    }
+    all
+}
+
+/// カーソルより前のテキストのうち、閉じられていない最も内側の `(` の位置。
+/// それより先に閉じられていない `{` や `[` があれば None。
+fn unclosed_paren(before: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (idx, c) in before.char_indices().rev() {
+        match c {
+            ')' | '}' | ']' => depth += 1,
+            '(' | '{' | '[' if depth > 0 => depth -= 1,
+            '(' => return Some(idx),
+            '{' | '[' => return None,
+            // 文の区切りを越えて探さない
+            ';' if depth == 0 => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// `length -> inline-text -> bool` のような関数の型を、最も外側の `->` で区切ったもの。
+fn split_arrows(ty: &str) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (idx, c) in ty.char_indices() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            '-' if depth == 0 && ty[idx..].starts_with("->") => {
+                parts.push(ty[start..idx].trim());
+                start = idx + 2;
+            }
+            _ => {}
+        }
+    }
+    parts.push(ty[start..].trim());
+    parts
+}
+
+/// 比較のために、空白を詰め、全体を囲む括弧を取り除いた型。
+fn normalize_type(ty: &str) -> String {
+    let mut ty = ty.split_whitespace().collect::<Vec<_>>().join(" ");
+    while ty.starts_with('(') && !ty.starts_with("(|") && ty.ends_with(')') {
+        let inner = &ty[1..ty.len() - 1];
+        if split_arrows(inner).len() > 1 || inner.contains(&['(', ')'][..]) {
+            break;
+        }
+        ty = inner.trim().to_owned();
+    }
+    ty
+}
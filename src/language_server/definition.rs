@@ -1,12 +1,49 @@
-use lspower::lsp::{GotoDefinitionResponse, Location, Range};
-use satysfi_parser::{Cst, Rule};
+use lspower::lsp::{GotoDefinitionResponse, Location, Range, Url};
+use satysfi_parser::{structure::ProgramText, Cst, Rule};
 
 use crate::{
-    documents::{Component, DocumentCache, DocumentData},
-    util::{ConvertPosition, UrlPos},
+    config::CrossReferenceConfig,
+    documents::{Component, ComponentBody, DocumentCache, DocumentData, KeyKind},
+    util::{offset_to_position, ConvertPosition, UrlPos},
 };
 
 impl DocumentCache {
+    /// カーソル下の文字列リテラルが相互参照のキーを参照していれば、そのキーを定義している場所。
+    pub fn get_key_definition(
+        &self,
+        curpos: &UrlPos,
+        config: &CrossReferenceConfig,
+    ) -> Option<GotoDefinitionResponse> {
+        let UrlPos { url, pos } = curpos;
+        let (program_text, _) = self.get_doc_info(url)?;
+        let pos_usize = program_text.from_position(pos)?;
+        let references = self.cross_references(url, config);
+        let occurrence = references
+            .occurrence_at(pos_usize)
+            .filter(|occurrence| occurrence.kind == KeyKind::Reference)?;
+
+        let mut locations = references
+            .definitions
+            .get(&occurrence.key)?
+            .iter()
+            .filter_map(|(url, span)| {
+                let text = self.get(url)?.text();
+                Some(Location {
+                    uri: url.clone(),
+                    range: Range {
+                        start: offset_to_position(text, span.start),
+                        end: offset_to_position(text, span.end),
+                    },
+                })
+            })
+            .collect::<Vec<_>>();
+        match locations.len() {
+            0 => None,
+            1 => Some(GotoDefinitionResponse::Scalar(locations.remove(0))),
+            _ => Some(GotoDefinitionResponse::Array(locations)),
+        }
+    }
+
     pub fn get_definition_list(&self, curpos: &UrlPos) -> Option<GotoDefinitionResponse> {
         let (url, pos_definition) = self
             .find_component_under_cursor(curpos)
@@ -182,4 +219,95 @@ impl DocumentCache {
         };
         component.map(|c| (cst, c))
     }
+
+    /// url のファイルにおいて component を参照している箇所の一覧。定義と sig での宣言は含めない。
+    /// 修飾されていない名前は定義へのジャンプと同じ規則で解決し、 `M.x` のように
+    /// module 名で修飾された名前は、その位置から見える module M が component を含むものに限る。
+    pub fn resolved_references<'a>(
+        &self,
+        component: &Component,
+        url: &Url,
+        program_text: &'a ProgramText,
+    ) -> Vec<&'a Cst> {
+        program_text
+            .cst
+            .pickup(component.name_rule())
+            .into_iter()
+            .filter(|cst| {
+                let is_definition = url == &component.url
+                    && (cst.span.start == component.pos_definition.start
+                        || Some(cst.span) == component.pos_declaration);
+                if is_definition {
+                    return false;
+                }
+                let text = program_text.get_text(cst);
+                match split_qualified(text) {
+                    Some((module_name, name)) => {
+                        name == component.name
+                            && self
+                                .find_module(url, module_name, cst.span.start)
+                                .map_or(false, |module| contains(module, component))
+                    }
+                    None => {
+                        let curpos = match program_text.get_position(cst.span.start) {
+                            Some(pos) => UrlPos {
+                                url: url.clone(),
+                                pos,
+                            },
+                            None => return false,
+                        };
+                        text == component.name
+                            && matches!(
+                                self.find_component_under_cursor(&curpos),
+                                Some((_, resolved)) if resolved.pos_definition == component.pos_definition
+                                    && resolved.url == component.url
+                            )
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// url のファイルの pos から見える、 name という名前の module。
+    /// そのファイルで定義されたものを優先し、なければ依存先で定義されたものを探す。
+    fn find_module(&self, url: &Url, name: &str, pos: usize) -> Option<&Component> {
+        let (_, environment) = self.get_doc_info(url)?;
+        let local = environment
+            .modules()
+            .into_iter()
+            .find(|module| module.scope.includes(pos) && module.name == name);
+        local.or_else(|| {
+            environment.dependencies().iter().find_map(|dep| {
+                let (_, env_dep) = self.get_doc_info(dep.url.as_ref()?)?;
+                env_dep
+                    .modules()
+                    .into_iter()
+                    .find(|module| module.name == name)
+            })
+        })
+    }
+}
+
+/// `M.x` や `\M.cmd` のように module 名で修飾された名前を、 module 名と、
+/// 修飾を除いた名前（`x` や `\cmd`）に分ける。
+fn split_qualified(text: &str) -> Option<(&str, String)> {
+    let (prefix, base) = text.rsplit_once('.')?;
+    let sigil_len = prefix.len() - prefix.trim_start_matches(&['\\', '+'][..]).len();
+    Some((
+        &prefix[sigil_len..],
+        format!("{}{}", &prefix[..sigil_len], base),
+    ))
+}
+
+/// module の要素に component があるか。
+fn contains(module: &Component, component: &Component) -> bool {
+    match &module.body {
+        ComponentBody::Module { components } => {
+            module.url == component.url
+                && components
+                    .iter()
+                    .any(|c| c.pos_definition == component.pos_definition)
+        }
+        _ => false,
+    }
 }
@@ -1,26 +1,164 @@
 use itertools::Itertools;
 use log::info;
-use lspower::lsp::{Diagnostic, DiagnosticSeverity, Position, Range, Url};
-use satysfi_parser::Rule;
+use lspower::lsp::{
+    CodeDescription, Diagnostic, DiagnosticSeverity, NumberOrString, Position, Range, Url,
+};
+use satysfi_parser::{structure::ProgramText, Cst, LineCol, Rule, Span};
 use std::collections::HashMap;
 
-use crate::{documents::DocumentData, util::ConvertPosition};
+use crate::{
+    config::Config,
+    documents::{CrossReferences, DocumentData, FontNames, SyntaxError, DUMMY_RULES},
+    util::{offset_to_position, ConvertPosition},
+};
 
+pub use self::cross_references::check_cross_references;
+pub use self::fonts::check_fonts;
+pub use self::lint::{run_lints, LintRule, Suppressions};
+pub use self::signature::check_signatures;
+pub use self::unused::check_unused;
+
+mod cross_references;
+mod fonts;
+mod lint;
+mod signature;
+mod unused;
+
+/// 診断の出どころ。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiagnosticSource {
+    /// パーサによる構文エラー。
+    Parser,
+    /// lint ルールによる問題。
+    Lint,
+    /// 外部ツールによる診断。
+    External,
+}
+
+impl DiagnosticSource {
+    const ALL: [DiagnosticSource; 3] = [
+        DiagnosticSource::Parser,
+        DiagnosticSource::Lint,
+        DiagnosticSource::External,
+    ];
+}
+
+/// ファイルごと・出どころごとに診断を保持する。
+/// publish するときはすべての出どころの診断をまとめて送る。
 #[derive(Debug, Default)]
 pub struct DiagnosticCollection {
-    map: HashMap<Url, Vec<Diagnostic>>,
+    map: HashMap<(Url, DiagnosticSource), Vec<Diagnostic>>,
 }
 
-const DUMMY_RULES: &[Rule] = &[
-    Rule::dummy_stmt,
-    Rule::dummy_header,
-    Rule::dummy_sig_stmt,
-    Rule::dummy_block_cmd_incomplete,
-    Rule::dummy_inline_cmd_incomplete,
-];
+impl DiagnosticCollection {
+    /// その出どころの診断を置き換える。
+    pub fn set(&mut self, url: Url, source: DiagnosticSource, diagnostics: Vec<Diagnostic>) {
+        self.map.insert((url, source), diagnostics);
+    }
+
+    /// その url に関するすべての出どころの診断をまとめて返す。
+    pub fn get(&self, url: &Url) -> Vec<Diagnostic> {
+        DiagnosticSource::ALL
+            .iter()
+            .filter_map(|&source| self.map.get(&(url.clone(), source)))
+            .flatten()
+            .cloned()
+            .collect()
+    }
+
+    /// 診断を保持しているファイルの一覧。
+    pub fn urls(&self) -> Vec<Url> {
+        self.map
+            .keys()
+            .map(|(url, _)| url)
+            .unique()
+            .cloned()
+            .collect()
+    }
 
-pub fn get_diagnostics(doc_data: &DocumentData) -> Vec<Diagnostic> {
+    /// その出どころの診断をすべて取り除き、影響を受けたファイルの一覧を返す。
+    pub fn clear(&mut self, source: DiagnosticSource) -> Vec<Url> {
+        let urls: Vec<Url> = self
+            .map
+            .keys()
+            .filter(|(_, s)| *s == source)
+            .map(|(url, _)| url.clone())
+            .collect();
+        for url in &urls {
+            self.map.remove(&(url.clone(), source));
+        }
+        urls
+    }
+}
+
+/// パーサ以外の検査で見つかった問題。修正候補を伴うことがある。
+#[derive(Debug)]
+pub struct Problem {
+    /// 問題の種類を表す文字列。 lint ルールの code と一致する。
+    pub code: &'static str,
+    /// 問題のある箇所。
+    pub span: Span,
+    pub message: String,
+    /// code action として提示する修正候補。
+    pub fixes: Vec<Fix>,
+}
+
+/// 問題に対する修正。
+#[derive(Debug)]
+pub struct Fix {
+    pub title: String,
+    /// 置換する範囲と置換後のテキスト。
+    pub edits: Vec<(Span, String)>,
+}
+
+impl Problem {
+    /// 診断に変換する。範囲がテキストの外にあれば None。
+    pub fn to_diagnostic(&self, program_text: &ProgramText, config: &Config) -> Option<Diagnostic> {
+        let rule = LintRule::find(self.code);
+        Some(Diagnostic {
+            range: program_text.get_range(self.span)?,
+            severity: rule
+                .and_then(|rule| rule.level(config).severity())
+                .or(Some(DiagnosticSeverity::WARNING)),
+            code: Some(NumberOrString::String(self.code.to_owned())),
+            code_description: rule
+                .and_then(|rule| Url::parse(&rule.href()).ok())
+                .map(|href| CodeDescription { href }),
+            source: Some("satysfi-ls".to_owned()),
+            message: self.message.clone(),
+            related_information: None,
+            tags: rule
+                .filter(|rule| !rule.tags.is_empty())
+                .map(|rule| rule.tags.to_vec()),
+            data: None,
+        })
+    }
+}
+
+/// lint を実行し、その結果を診断として返す。
+pub fn get_lint_diagnostics(
+    doc_data: &DocumentData,
+    config: &Config,
+    fonts: &FontNames,
+    references: &CrossReferences,
+) -> Vec<Diagnostic> {
     match doc_data {
+        DocumentData::Parsed {
+            program_text,
+            environment,
+        } => run_lints(program_text, environment, config, fonts, references)
+            .iter()
+            .filter_map(|problem| problem.to_diagnostic(program_text, config))
+            .collect(),
+        DocumentData::NotParsed { .. } => vec![],
+    }
+}
+
+/// パーサによる構文エラーを診断として返す。
+/// lint と同様に、抑制コメントで抑制されたものは除く。
+pub fn get_syntax_diagnostics(doc_data: &DocumentData, config: &Config) -> Vec<Diagnostic> {
+    let suppressions = Suppressions::from_text(doc_data.text());
+    let diagnostics = match doc_data {
         DocumentData::Parsed {
             program_text: csttext,
             ..
@@ -31,65 +169,111 @@ pub fn get_diagnostics(doc_data: &DocumentData) -> Vec<Diagnostic> {
                 .concat();
             dummy_csts
                 .into_iter()
-                .map(|cst| {
-                    let range = Range {
-                        start: csttext.get_position(cst.span.start).unwrap(),
-                        end: csttext.get_position(cst.span.end).unwrap(),
-                    };
-                    Diagnostic {
-                        range,
-                        severity: Some(DiagnosticSeverity::ERROR),
-                        code: None,
-                        code_description: None,
-                        source: Some("Syntax Error".to_owned()),
-                        message: cst.rule.error_description().unwrap(),
-                        related_information: None,
-                        tags: None,
-                        data: None,
+                .filter_map(|cst| dummy_diagnostic(csttext, cst, config))
+                .collect_vec()
+        }
+        DocumentData::NotParsed { errors, text } => {
+            info!("Not parsed!: {} errors", errors.len());
+            errors
+                .iter()
+                .map(|error| match error {
+                    SyntaxError::Unexpected { linecol, expect } => {
+                        unexpected_diagnostic(text, linecol, expect, config)
+                    }
+                    SyntaxError::Incomplete { span, rule } => {
+                        let range = Range {
+                            start: offset_to_position(text, span.start),
+                            end: offset_to_position(text, span.end),
+                        };
+                        syntax_diagnostic(
+                            range,
+                            rule.error_description().unwrap(),
+                            Some(*rule),
+                            config,
+                        )
                     }
                 })
-                .collect()
-        }
-        DocumentData::NotParsed {
-            linecol,
-            expect,
-            text,
-        } => {
-            info!("Not parsed!: {:?}", linecol);
-            let line_text = text.split('\n').nth(linecol.line).unwrap();
-            let character = line_text
-                .chars()
-                .take(linecol.column)
-                .collect::<String>()
-                .encode_utf16()
                 .collect_vec()
-                .len();
-            let pos = Position {
-                line: linecol.line as u32,
-                character: character as u32,
-            };
-            let range = Range {
-                start: pos,
-                end: pos,
+        }
+    };
+    diagnostics
+        .into_iter()
+        .filter(|diagnostic| diagnostic.severity.is_some())
+        .filter(|diagnostic| {
+            let code = match &diagnostic.code {
+                Some(NumberOrString::String(code)) => code.as_str(),
+                _ => return true,
             };
+            !suppressions.is_suppressed(code, Some(diagnostic.range.start.line as usize))
+        })
+        .collect()
+}
 
-            let message = format!(
-                "Unexpected character. Expected:\n{}",
-                expect.iter().map(|s| format!("- {}", s)).join("\n")
-            );
-
-            let diagnostics = Diagnostic {
-                range,
-                severity: Some(DiagnosticSeverity::ERROR),
-                code: None,
-                code_description: None,
-                source: Some("Syntax Error".to_owned()),
-                message,
-                related_information: None,
-                tags: None,
-                data: None,
-            };
-            vec![diagnostics]
-        }
+/// パースを続行できなかった箇所の診断。
+pub fn unexpected_diagnostic(
+    text: &str,
+    linecol: &LineCol,
+    expect: &[&str],
+    config: &Config,
+) -> Diagnostic {
+    let line_text = text.split('\n').nth(linecol.line).unwrap_or_default();
+    let character = line_text
+        .chars()
+        .take(linecol.column)
+        .collect::<String>()
+        .encode_utf16()
+        .collect_vec()
+        .len();
+    let pos = Position {
+        line: linecol.line as u32,
+        character: character as u32,
+    };
+    let range = Range {
+        start: pos,
+        end: pos,
+    };
+    let message = format!(
+        "Unexpected character. Expected:\n{}",
+        expect.iter().map(|s| format!("- {}", s)).join("\n")
+    );
+    syntax_diagnostic(range, message, None, config)
+}
+
+/// パーサが dummy ノードとして読み飛ばした箇所の診断。範囲がテキストの外にあれば None。
+pub fn dummy_diagnostic(
+    program_text: &ProgramText,
+    cst: &Cst,
+    config: &Config,
+) -> Option<Diagnostic> {
+    let range = program_text.get_range(cst.span)?;
+    Some(syntax_diagnostic(
+        range,
+        cst.rule.error_description()?,
+        Some(cst.rule),
+        config,
+    ))
+}
+
+/// 構文エラーの診断。 lint と同様に、ルールの code と説明へのリンクを付け、重要度は設定に従う。
+/// 重要度が `off` に設定されている場合、 severity は None となる。
+fn syntax_diagnostic(
+    range: Range,
+    message: String,
+    rule: Option<Rule>,
+    config: &Config,
+) -> Diagnostic {
+    let rule = LintRule::syntax(rule);
+    Diagnostic {
+        range,
+        severity: rule.level(config).severity(),
+        code: Some(NumberOrString::String(rule.code.to_owned())),
+        code_description: Url::parse(&rule.href())
+            .ok()
+            .map(|href| CodeDescription { href }),
+        source: Some("satysfi-ls".to_owned()),
+        message,
+        related_information: None,
+        tags: None,
+        data: None,
     }
 }
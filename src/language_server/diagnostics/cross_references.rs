@@ -0,0 +1,50 @@
+//! 相互参照のキーの誤りを検出する。
+
+use crate::documents::{CrossReferences, KeyKind};
+
+use super::{fonts::similar_names, Fix, Problem};
+
+/// 定義されていないキーへの参照と、複数回定義されているキーを報告する。
+/// 文書を構成するファイルをすべて辿れなかった場合、定義されていないキーは報告しない。
+pub fn check_cross_references(references: &CrossReferences) -> Vec<Problem> {
+    let keys: Vec<String> = references
+        .keys()
+        .into_iter()
+        .map(ToOwned::to_owned)
+        .collect();
+    references
+        .occurrences
+        .iter()
+        .filter_map(|occurrence| {
+            let key = &occurrence.key;
+            let definitions = references.definitions.get(key).map(Vec::len).unwrap_or(0);
+            match occurrence.kind {
+                KeyKind::Reference if definitions == 0 && references.complete => {
+                    let fixes = similar_names(key, &keys)
+                        .into_iter()
+                        .map(|similar| Fix {
+                            title: format!("Replace with `{}`", similar),
+                            edits: vec![(occurrence.span, similar.to_owned())],
+                        })
+                        .collect();
+                    Some(Problem {
+                        code: "undefined-key",
+                        span: occurrence.span,
+                        message: format!("Key `{}` is not defined in the document.", key),
+                        fixes,
+                    })
+                }
+                KeyKind::Definition if definitions > 1 => Some(Problem {
+                    code: "duplicate-key",
+                    span: occurrence.span,
+                    message: format!(
+                        "Key `{}` is defined {} times in the document.",
+                        key, definitions
+                    ),
+                    fixes: vec![],
+                }),
+                _ => None,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,68 @@
+//! フォントハッシュファイルに登録されていないフォント名を検出する。
+
+use satysfi_parser::structure::ProgramText;
+
+use crate::documents::{font_literals, FontKind, FontNames};
+
+use super::{Fix, Problem};
+
+/// `set-font` や `set-math-font` に渡しているフォント名のうち、
+/// フォントハッシュファイルに登録されていないものを報告する。
+/// ハッシュファイルが一つも見つからない種類のフォントは検査しない。
+pub fn check_fonts(program_text: &ProgramText, fonts: &FontNames) -> Vec<Problem> {
+    let text = program_text.text.as_str();
+    font_literals(program_text)
+        .into_iter()
+        .filter_map(|(span, kind)| {
+            let known = fonts.get(kind);
+            let name = &text[span.start..span.end];
+            if known.is_empty() || known.iter().any(|font| font == name) {
+                return None;
+            }
+            let hash = match kind {
+                FontKind::Text => "fonts.satysfi-hash",
+                FontKind::Math => "math-fonts.satysfi-hash",
+            };
+            let fixes = similar_names(name, known)
+                .into_iter()
+                .map(|font| Fix {
+                    title: format!("Replace with `{}`", font),
+                    edits: vec![(span, font.to_owned())],
+                })
+                .collect();
+            Some(Problem {
+                code: "unknown-font",
+                span,
+                message: format!("Font `{}` is not registered in `{}`.", name, hash),
+                fixes,
+            })
+        })
+        .collect()
+}
+
+/// 綴りの似ている名前。大文字・小文字の違いを除いて編集距離が 2 以下のものを近い順に返す。
+pub(super) fn similar_names<'a>(name: &str, known: &'a [String]) -> Vec<&'a str> {
+    let name = name.to_lowercase();
+    let mut similar: Vec<(usize, &str)> = known
+        .iter()
+        .map(|font| (edit_distance(&name, &font.to_lowercase()), font.as_str()))
+        .filter(|&(distance, _)| distance <= 2)
+        .collect();
+    similar.sort();
+    similar.into_iter().map(|(_, font)| font).collect()
+}
+
+/// 二つの文字列の編集距離。
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1];
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = prev[j] + (ca != cb) as usize;
+            cur.push(substitution.min(prev[j + 1] + 1).min(cur[j] + 1));
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
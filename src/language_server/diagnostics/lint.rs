@@ -0,0 +1,241 @@
+//! lint ルールの一覧と、それらをまとめて実行する仕組み。
+
+use std::collections::HashMap;
+
+use lspower::lsp::{DiagnosticSeverity, DiagnosticTag};
+use regex::Regex;
+use satysfi_parser::{structure::ProgramText, LineCol, Rule};
+
+use crate::{
+    config::{Config, LintLevel},
+    documents::{CrossReferences, Environment, FontNames},
+};
+
+use super::{check_cross_references, check_fonts, check_signatures, check_unused, Problem};
+
+/// lint ルールの説明を置いている場所。
+const LINT_DOCS_URL: &str =
+    "https://github.com/monaqa/satysfi-language-server/blob/main/docs/lints.md";
+
+/// lint ルール。
+#[derive(Debug)]
+pub struct LintRule {
+    /// ルールを一意に表す文字列。診断の code や抑制コメント、設定で用いる。
+    pub code: &'static str,
+    /// 設定で上書きされていないときの重要度。
+    pub default_level: LintLevel,
+    /// 診断に付与するタグ。
+    pub tags: &'static [DiagnosticTag],
+}
+
+pub const LINT_RULES: &[LintRule] = &[
+    LintRule {
+        code: "syntax-error",
+        default_level: LintLevel::Error,
+        tags: &[],
+    },
+    LintRule {
+        code: "incomplete-statement",
+        default_level: LintLevel::Error,
+        tags: &[],
+    },
+    LintRule {
+        code: "incomplete-header",
+        default_level: LintLevel::Error,
+        tags: &[],
+    },
+    LintRule {
+        code: "incomplete-sig-stmt",
+        default_level: LintLevel::Error,
+        tags: &[],
+    },
+    LintRule {
+        code: "incomplete-block-cmd",
+        default_level: LintLevel::Error,
+        tags: &[],
+    },
+    LintRule {
+        code: "incomplete-inline-cmd",
+        default_level: LintLevel::Error,
+        tags: &[],
+    },
+    LintRule {
+        code: "sig-missing-definition",
+        default_level: LintLevel::Error,
+        tags: &[],
+    },
+    LintRule {
+        code: "sig-missing-type",
+        default_level: LintLevel::Error,
+        tags: &[],
+    },
+    LintRule {
+        code: "sig-direct-kind-mismatch",
+        default_level: LintLevel::Error,
+        tags: &[],
+    },
+    LintRule {
+        code: "unused",
+        default_level: LintLevel::Warning,
+        tags: &[DiagnosticTag::UNNECESSARY],
+    },
+    LintRule {
+        code: "unknown-font",
+        default_level: LintLevel::Warning,
+        tags: &[],
+    },
+    LintRule {
+        code: "undefined-key",
+        default_level: LintLevel::Warning,
+        tags: &[],
+    },
+    LintRule {
+        code: "duplicate-key",
+        default_level: LintLevel::Warning,
+        tags: &[],
+    },
+];
+
+impl LintRule {
+    pub fn find(code: &str) -> Option<&'static LintRule> {
+        LINT_RULES.iter().find(|rule| rule.code == code)
+    }
+
+    /// パーサが dummy ノードとして読み飛ばした構文エラーに対応するルール。
+    /// パースを続行できなかったエラーは `syntax-error` とする。
+    pub fn syntax(rule: Option<Rule>) -> &'static LintRule {
+        let code = match rule {
+            Some(Rule::dummy_stmt) => "incomplete-statement",
+            Some(Rule::dummy_header) => "incomplete-header",
+            Some(Rule::dummy_sig_stmt) => "incomplete-sig-stmt",
+            Some(Rule::dummy_block_cmd_incomplete) => "incomplete-block-cmd",
+            Some(Rule::dummy_inline_cmd_incomplete) => "incomplete-inline-cmd",
+            _ => "syntax-error",
+        };
+        LintRule::find(code).unwrap()
+    }
+
+    /// 設定を考慮したルールの重要度。
+    pub fn level(&self, config: &Config) -> LintLevel {
+        config.lint_level(self.code).unwrap_or(self.default_level)
+    }
+
+    /// ルールの説明へのリンク。
+    pub fn href(&self) -> String {
+        format!("{}#{}", LINT_DOCS_URL, self.code)
+    }
+}
+
+impl LintLevel {
+    pub fn severity(self) -> Option<DiagnosticSeverity> {
+        match self {
+            LintLevel::Off => None,
+            LintLevel::Hint => Some(DiagnosticSeverity::HINT),
+            LintLevel::Information => Some(DiagnosticSeverity::INFORMATION),
+            LintLevel::Warning => Some(DiagnosticSeverity::WARNING),
+            LintLevel::Error => Some(DiagnosticSeverity::ERROR),
+        }
+    }
+}
+
+/// すべての lint を実行し、無効化・抑制されていない問題を返す。
+/// fonts はフォント名の検査に用いる、フォントハッシュファイルに登録されているフォント名。
+pub fn run_lints(
+    program_text: &ProgramText,
+    environment: &Environment,
+    config: &Config,
+    fonts: &FontNames,
+    references: &CrossReferences,
+) -> Vec<Problem> {
+    let suppressions = Suppressions::from_text(&program_text.text);
+    let problems = vec![
+        check_signatures(program_text),
+        check_unused(program_text, environment),
+        check_fonts(program_text, fonts),
+        check_cross_references(references),
+    ];
+    problems
+        .into_iter()
+        .flatten()
+        .filter(|problem| {
+            LintRule::find(problem.code).map(|rule| rule.level(config)) != Some(LintLevel::Off)
+        })
+        .filter(|problem| {
+            let line = program_text
+                .get_line_col(problem.span.start)
+                .map(|LineCol { line, .. }| line);
+            !suppressions.is_suppressed(problem.code, line)
+        })
+        .collect()
+}
+
+/// 抑制の対象となるルール。
+#[derive(Debug, Clone)]
+enum RuleSet {
+    All,
+    Codes(Vec<String>),
+}
+
+impl RuleSet {
+    fn from_args(args: &str) -> RuleSet {
+        let codes: Vec<String> = args
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_owned())
+            .collect();
+        if codes.is_empty() {
+            RuleSet::All
+        } else {
+            RuleSet::Codes(codes)
+        }
+    }
+
+    fn contains(&self, code: &str) -> bool {
+        match self {
+            RuleSet::All => true,
+            RuleSet::Codes(codes) => codes.iter().any(|c| c == code),
+        }
+    }
+}
+
+/// `% satysfi-ls: disable-next-line unused` のような抑制コメントの情報。
+///
+/// - `disable-next-line [codes]`: 次の行で指定したルールを抑制する。
+/// - `disable-line [codes]`: そのコメントがある行で指定したルールを抑制する。
+/// - `disable [codes]`: ファイル全体で指定したルールを抑制する。
+///
+/// code を省略した場合はすべてのルールを抑制する。
+#[derive(Debug, Default)]
+pub struct Suppressions {
+    whole_file: Vec<RuleSet>,
+    lines: HashMap<usize, Vec<RuleSet>>,
+}
+
+impl Suppressions {
+    pub fn from_text(text: &str) -> Suppressions {
+        let re = Regex::new(r"%\s*satysfi-ls:\s*(disable-next-line|disable-line|disable)\b(.*)$")
+            .unwrap();
+        let mut suppressions = Suppressions::default();
+        for (line, line_text) in text.split('\n').enumerate() {
+            if let Some(caps) = re.captures(line_text) {
+                let rules = RuleSet::from_args(caps.get(2).unwrap().as_str());
+                match caps.get(1).unwrap().as_str() {
+                    "disable-next-line" => {
+                        suppressions.lines.entry(line + 1).or_default().push(rules)
+                    }
+                    "disable-line" => suppressions.lines.entry(line).or_default().push(rules),
+                    _ => suppressions.whole_file.push(rules),
+                }
+            }
+        }
+        suppressions
+    }
+
+    pub fn is_suppressed(&self, code: &str, line: Option<usize>) -> bool {
+        let in_line = line
+            .and_then(|line| self.lines.get(&line))
+            .map(|rules| rules.iter().any(|rule| rule.contains(code)))
+            .unwrap_or(false);
+        in_line || self.whole_file.iter().any(|rule| rule.contains(code))
+    }
+}
@@ -0,0 +1,344 @@
+//! module の struct と sig の整合性を検査する。
+
+use itertools::Itertools;
+use satysfi_parser::{
+    grammar::{type_block_cmd, type_inline_cmd, type_math_cmd},
+    structure::{LetRecInner, Program, ProgramText, Signature, Statement, TypeInner},
+    Cst, Rule, Span,
+};
+
+use crate::util::expand_to_lines;
+
+use super::super::code_action::{command_stub, insert_before_end};
+use super::{Fix, Problem};
+
+/// struct 内で定義されているものの種類。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DefinitionKind {
+    Value,
+    InlineCmd,
+    BlockCmd,
+    MathCmd,
+    Type,
+}
+
+impl DefinitionKind {
+    fn keyword(self) -> &'static str {
+        match self {
+            DefinitionKind::Value => "let",
+            DefinitionKind::InlineCmd => "let-inline",
+            DefinitionKind::BlockCmd => "let-block",
+            DefinitionKind::MathCmd => "let-math",
+            DefinitionKind::Type => "type",
+        }
+    }
+
+    fn is_command(self) -> bool {
+        matches!(
+            self,
+            DefinitionKind::InlineCmd | DefinitionKind::BlockCmd | DefinitionKind::MathCmd
+        )
+    }
+
+    /// 宣言された型（`[inline-text] inline-cmd` など）からコマンドの種類を推定する。
+    fn from_signature(name: &str, signature: &str) -> DefinitionKind {
+        let signature = signature.trim_end();
+        if signature.ends_with("inline-cmd") {
+            DefinitionKind::InlineCmd
+        } else if signature.ends_with("block-cmd") {
+            DefinitionKind::BlockCmd
+        } else if signature.ends_with("math-cmd") {
+            DefinitionKind::MathCmd
+        } else if name.starts_with('+') {
+            DefinitionKind::BlockCmd
+        } else if name.starts_with('\\') {
+            DefinitionKind::InlineCmd
+        } else {
+            DefinitionKind::Value
+        }
+    }
+}
+
+/// プログラム中のすべての module について sig と struct の対応を検査する。
+pub fn check_signatures(program_text: &ProgramText) -> Vec<Problem> {
+    let preamble = match &program_text.structure {
+        Ok(Program::Saty { preamble, .. }) => preamble,
+        Ok(Program::Satyh { preamble, .. }) => preamble,
+        Err(_) => return vec![],
+    };
+    preamble
+        .iter()
+        .map(|stmt| check_stmt(stmt, program_text))
+        .concat()
+}
+
+fn check_stmt(stmt: &Statement, program_text: &ProgramText) -> Vec<Problem> {
+    if let Statement::Module {
+        name,
+        signature,
+        statements,
+    } = stmt
+    {
+        let module_span = program_text.cst.get_parent(name).unwrap().span;
+        let definitions = collect_definitions(statements, program_text);
+        let problems = signature
+            .iter()
+            .filter_map(|sig| check_sig(sig, module_span, &definitions, program_text));
+        // module の中に module がある場合も検査する
+        let nested = statements
+            .iter()
+            .map(|stmt| check_stmt(stmt, program_text))
+            .concat();
+        problems.chain(nested).collect()
+    } else {
+        vec![]
+    }
+}
+
+/// struct 内で定義されている名前と種類の一覧。
+fn collect_definitions<'a>(
+    statements: &'a [Statement],
+    program_text: &'a ProgramText,
+) -> Vec<(&'a str, DefinitionKind)> {
+    let vars = |pat: &'a Cst| {
+        pat.pickup(Rule::var)
+            .into_iter()
+            .map(|var| (program_text.get_text(var), DefinitionKind::Value))
+            .collect_vec()
+    };
+    statements
+        .iter()
+        .map(|stmt| match stmt {
+            Statement::Let { pat, .. } => vars(pat),
+            Statement::LetRec(inners) => inners
+                .iter()
+                .map(|LetRecInner { pattern, .. }| vars(pattern))
+                .concat(),
+            Statement::LetInline { cmd, .. } => {
+                vec![(program_text.get_text(cmd), DefinitionKind::InlineCmd)]
+            }
+            Statement::LetBlock { cmd, .. } => {
+                vec![(program_text.get_text(cmd), DefinitionKind::BlockCmd)]
+            }
+            Statement::LetMath { cmd, .. } => {
+                vec![(program_text.get_text(cmd), DefinitionKind::MathCmd)]
+            }
+            Statement::LetMutable { var, .. } => {
+                vec![(program_text.get_text(var), DefinitionKind::Value)]
+            }
+            Statement::Type(inners) => inners
+                .iter()
+                .map(|TypeInner { name, .. }| (program_text.get_text(name), DefinitionKind::Type))
+                .collect_vec(),
+            Statement::Module { .. } | Statement::Open(_) => vec![],
+        })
+        .concat()
+}
+
+fn check_sig(
+    sig: &Signature,
+    module_span: Span,
+    definitions: &[(&str, DefinitionKind)],
+    program_text: &ProgramText,
+) -> Option<Problem> {
+    match sig {
+        Signature::Val { var, signature, .. } => {
+            let name = program_text.get_text(var);
+            if definitions
+                .iter()
+                .any(|&(def, kind)| def == name && kind != DefinitionKind::Type)
+            {
+                return None;
+            }
+            let kind = DefinitionKind::from_signature(name, program_text.get_text(signature));
+            Some(Problem {
+                code: "sig-missing-definition",
+                span: var.span,
+                message: format!(
+                    "`{}` is declared in the signature but never defined in the struct.",
+                    name
+                ),
+                fixes: missing_definition_fixes(var, kind, signature, module_span, program_text),
+            })
+        }
+
+        Signature::Direct { var, signature, .. } => {
+            let name = program_text.get_text(var);
+            let kind = DefinitionKind::from_signature(name, program_text.get_text(signature));
+            let bare_name = strip_sigil(name);
+            let defined = definitions
+                .iter()
+                .find(|&&(def, def_kind)| def_kind.is_command() && strip_sigil(def) == bare_name);
+            match defined {
+                Some(&(def, def_kind)) if def == name && def_kind == kind => None,
+                Some(&(def, def_kind)) => Some(Problem {
+                    code: "sig-direct-kind-mismatch",
+                    span: var.span,
+                    message: format!(
+                        "`direct {}` does not match the definition `{} {}`.",
+                        name,
+                        def_kind.keyword(),
+                        def
+                    ),
+                    fixes: missing_definition_fixes(
+                        var,
+                        kind,
+                        signature,
+                        module_span,
+                        program_text,
+                    ),
+                }),
+                None => Some(Problem {
+                    code: "sig-missing-definition",
+                    span: var.span,
+                    message: format!(
+                        "`{}` is declared in the signature but never defined in the struct.",
+                        name
+                    ),
+                    fixes: missing_definition_fixes(
+                        var,
+                        kind,
+                        signature,
+                        module_span,
+                        program_text,
+                    ),
+                }),
+            }
+        }
+
+        Signature::Type { name, .. } => {
+            let type_name = program_text.get_text(name);
+            if definitions
+                .iter()
+                .any(|&(def, kind)| def == type_name && kind == DefinitionKind::Type)
+            {
+                return None;
+            }
+            let decl_span = enclosing_sig_stmt(name, program_text);
+            let stub = format!(
+                "{} = unit",
+                program_text.get_text_from_span(Span {
+                    start: decl_span.start,
+                    end: name.span.end
+                })
+            );
+            Some(Problem {
+                code: "sig-missing-type",
+                span: name.span,
+                message: format!(
+                    "type `{}` is declared in the signature but never defined in the struct.",
+                    type_name
+                ),
+                fixes: vec![
+                    insert_stub_fix(&stub, module_span, program_text),
+                    remove_declaration_fix(name, program_text),
+                ]
+                .into_iter()
+                .flatten()
+                .collect(),
+            })
+        }
+    }
+}
+
+fn missing_definition_fixes(
+    var: &Cst,
+    kind: DefinitionKind,
+    signature: &Cst,
+    module_span: Span,
+    program_text: &ProgramText,
+) -> Vec<Fix> {
+    let name = program_text.get_text(var);
+    let stub = stub_definition(name, kind, program_text.get_text(signature));
+    vec![
+        insert_stub_fix(&stub, module_span, program_text),
+        remove_declaration_fix(var, program_text),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// 宣言された型に合わせた仮の定義を作る。
+fn stub_definition(name: &str, kind: DefinitionKind, signature: &str) -> String {
+    let type_args = match kind {
+        DefinitionKind::InlineCmd => type_inline_cmd(signature).ok(),
+        DefinitionKind::BlockCmd => type_block_cmd(signature).ok(),
+        DefinitionKind::MathCmd => type_math_cmd(signature).ok(),
+        _ => None,
+    }
+    .map(|cst| {
+        cst.inner
+            .iter()
+            .map(|arg| signature[arg.span.start..arg.span.end].to_owned())
+            .collect_vec()
+    })
+    .unwrap_or_default();
+    let params = type_args
+        .iter()
+        .enumerate()
+        .map(|(idx, arg)| {
+            if arg.trim_end().ends_with('?') {
+                format!("?:arg{}", idx + 1)
+            } else {
+                format!("arg{}", idx + 1)
+            }
+        })
+        .collect_vec();
+    // 記号がずれている場合は宣言された種類に合わせる
+    let bare_name = strip_sigil(name);
+    match kind {
+        DefinitionKind::InlineCmd | DefinitionKind::MathCmd => {
+            command_stub(kind.keyword(), &format!("\\{}", bare_name), &params)
+        }
+        DefinitionKind::BlockCmd => {
+            command_stub(kind.keyword(), &format!("+{}", bare_name), &params)
+        }
+        DefinitionKind::Value | DefinitionKind::Type => format!("let {} = ()", name),
+    }
+}
+
+/// struct の末尾（`end` の直前）に stub を挿入する。
+fn insert_stub_fix(stub: &str, module_span: Span, program_text: &ProgramText) -> Option<Fix> {
+    let text = program_text.text.as_str();
+    let end_kw = module_span.end.checked_sub(3)?;
+    if text.get(end_kw..module_span.end) != Some("end") {
+        return None;
+    }
+    Some(Fix {
+        title: format!("Insert stub definition `{}`", stub),
+        edits: vec![insert_before_end(text, end_kw, stub)],
+    })
+}
+
+fn remove_declaration_fix(name: &Cst, program_text: &ProgramText) -> Option<Fix> {
+    let span = enclosing_sig_stmt(name, program_text);
+    Some(Fix {
+        title: format!(
+            "Remove declaration `{}`",
+            program_text.get_text_from_span(span).trim()
+        ),
+        edits: vec![(expand_to_lines(&program_text.text, span), String::new())],
+    })
+}
+
+/// 宣言中の名前を含む sig 内の文（`val ...`, `direct ...`, `type ...`）の範囲。
+fn enclosing_sig_stmt(name: &Cst, program_text: &ProgramText) -> Span {
+    program_text
+        .cst
+        .dig(name.span.start)
+        .into_iter()
+        .filter(|cst| {
+            matches!(
+                cst.rule,
+                Rule::sig_type_stmt | Rule::sig_val_stmt | Rule::sig_direct_stmt
+            )
+        })
+        .min_by_key(|cst| cst.span.end - cst.span.start)
+        .map(|cst| cst.span)
+        .unwrap_or(name.span)
+}
+
+fn strip_sigil(name: &str) -> &str {
+    name.trim_start_matches(|c| c == '\\' || c == '+')
+}
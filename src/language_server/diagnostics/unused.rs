@@ -0,0 +1,45 @@
+//! 使われていない定義を検出する。
+
+use satysfi_parser::structure::{Program, ProgramText};
+
+use crate::documents::{Component, ComponentBody, Environment, Visibility};
+
+use super::Problem;
+
+/// 使われていない定義を報告する。
+///
+/// - `.saty` ファイルのトップレベルの定義（外部から参照されることがないため）
+/// - module 内で signature に現れない（private な）定義
+pub fn check_unused(program_text: &ProgramText, environment: &Environment) -> Vec<Problem> {
+    let is_saty = matches!(program_text.structure, Ok(Program::Saty { .. }));
+    let mut problems = vec![];
+    for component in environment.components() {
+        match &component.body {
+            ComponentBody::Module { components } => {
+                let private = components
+                    .iter()
+                    .filter(|c| c.visibility == Visibility::Private);
+                problems.extend(private.filter_map(|c| unused_problem(c, program_text)));
+            }
+            _ if is_saty => problems.extend(unused_problem(component, program_text)),
+            _ => {}
+        }
+    }
+    problems
+}
+
+fn unused_problem(component: &Component, program_text: &ProgramText) -> Option<Problem> {
+    // `_` で始まる変数は意図的に使っていないものとみなす
+    if matches!(component.body, ComponentBody::Module { .. }) || component.name.starts_with('_') {
+        return None;
+    }
+    if !component.references(program_text).is_empty() {
+        return None;
+    }
+    Some(Problem {
+        code: "unused",
+        span: component.pos_definition,
+        message: format!("`{}` is defined but never used.", component.name),
+        fixes: vec![],
+    })
+}
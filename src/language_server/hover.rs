@@ -5,8 +5,15 @@ use crate::{
     util::{ConvertPosition, UrlPos},
 };
 
+use super::unicode_names::glyph_name;
+
+mod length;
+
 impl DocumentCache {
     pub fn get_hover(&self, curpos: &UrlPos) -> Option<Hover> {
+        if let Some(hover) = self.get_length_hover(curpos) {
+            return Some(hover);
+        }
         let UrlPos { url, .. } = curpos;
         let (cst, component) = self.find_component_under_cursor(curpos)?;
 
@@ -64,9 +71,18 @@ impl DocumentCache {
                     v
                 }
                 ComponentBody::MathCmd {
-                    type_declaration, ..
+                    type_declaration,
+                    glyph,
+                    ..
                 } => {
                     let mut v = vec![MarkedString::String("math command".to_owned())];
+                    if let Some(glyph) = glyph {
+                        let desc = match glyph_name(glyph) {
+                            Some(name) => format!("produces `{}` ({})", glyph, name),
+                            None => format!("produces `{}`", glyph),
+                        };
+                        v.push(MarkedString::String(desc));
+                    }
                     if let Some(span) = type_declaration {
                         v.push(MarkedString::LanguageString(LanguageString {
                             language: "satysfi".to_owned(),
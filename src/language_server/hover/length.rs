@@ -0,0 +1,295 @@
+//! 長さのリテラルと、長さの定数式の値の hover。
+//!
+//! `2cm +' 3mm` や `1inch *' 0.5` のように定数のみからなる長さの式を CST の上で畳み込み、
+//! その値を pt, mm, cm, inch で表示する。
+
+use lspower::lsp::{Hover, HoverContents, MarkupContent, MarkupKind};
+use satysfi_parser::{structure::ProgramText, Cst, Rule};
+
+use crate::{
+    documents::DocumentCache,
+    util::{ConvertPosition, UrlPos},
+};
+
+/// 長さの単位と、その 1 単位あたりの pt 数。 SATySFi の pt は 1/72 inch である。
+const UNITS: &[(&str, f64)] = &[
+    ("pt", 1.0),
+    ("mm", 72.0 / 25.4),
+    ("cm", 72.0 / 2.54),
+    ("inch", 72.0),
+];
+
+/// 定数式の値。
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Value {
+    /// pt で表した長さ。
+    Length(f64),
+    /// 浮動小数点数または整数。
+    Number(f64),
+}
+
+/// 定数式を構成する字句。
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Value(Value),
+    /// `+'`, `-'`, `*'`, `/'` のいずれか。
+    Operator(char),
+    Open,
+    Close,
+}
+
+impl DocumentCache {
+    /// カーソル下の長さのリテラル、またはそれを含む長さの定数式の値を表示する。
+    pub(super) fn get_length_hover(&self, curpos: &UrlPos) -> Option<Hover> {
+        let (program_text, _) = self.get_doc_info(&curpos.url)?;
+        let pos = program_text.from_position(&curpos.pos)?;
+
+        // 内側のノードから順に評価し、評価できる限り外側へと広げる
+        let mut csts = program_text.cst.dig(pos);
+        csts.sort_by_key(|cst| cst.span.end - cst.span.start);
+        // 長さのリテラルの内部のノードは読み飛ばす
+        if let Some(idx) = csts.iter().position(|cst| cst.rule == Rule::const_length) {
+            csts.drain(..idx);
+        }
+        let mut evaluated: Option<(&Cst, f64)> = None;
+        for cst in csts {
+            match evaluate(cst, program_text) {
+                Some(Value::Length(pt)) => evaluated = Some((cst, pt)),
+                // `1inch *' 0.5` の `0.5` や `/'` の結果は、それを含む式が長さになりうる
+                Some(Value::Number(_)) => {}
+                // 演算子の上にカーソルがある場合は、それを含む式まで広げる
+                None if evaluated.is_none() && operator(program_text.get_text(cst)).is_some() => {}
+                None => break,
+            }
+        }
+        let (cst, pt) = evaluated?;
+
+        let expr = program_text.get_text(cst);
+        let values = UNITS
+            .iter()
+            .map(|(unit, per_unit)| format!("| `{}` | {} |", unit, format_number(pt / per_unit)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let value = format!(
+            "```satysfi\n{}\n```\n\n| unit | value |\n|:--|--:|\n{}",
+            expr, values
+        );
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value,
+            }),
+            range: program_text.get_range(cst.span),
+        })
+    }
+}
+
+/// 定数のみからなる長さ・数値の式の値。定数式でなければ None。
+fn evaluate(cst: &Cst, program_text: &ProgramText) -> Option<Value> {
+    let text = program_text.get_text(cst);
+    if cst.rule == Rule::const_length {
+        return parse_length(text).map(Value::Length);
+    }
+    if cst.inner.is_empty() {
+        // `inf` や `NaN` のような変数名を数値として読まないようにする
+        let text = text.trim();
+        if !text.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+            return None;
+        }
+        return text.parse().ok().map(Value::Number);
+    }
+
+    // 子ノードとその間にある演算子・括弧を字句として並べ、それを評価する
+    let mut tokens = vec![];
+    let mut pos = cst.span.start;
+    for child in &cst.inner {
+        tokens.extend(gap_tokens(&program_text.text[pos..child.span.start])?);
+        match operator(program_text.get_text(child)) {
+            Some(op) => tokens.push(Token::Operator(op)),
+            None => tokens.push(Token::Value(evaluate(child, program_text)?)),
+        }
+        pos = child.span.end;
+    }
+    tokens.extend(gap_tokens(&program_text.text[pos..cst.span.end])?);
+
+    let mut tokens = tokens.into_iter().peekable();
+    let value = eval_sum(&mut tokens)?;
+    if tokens.next().is_some() {
+        return None;
+    }
+    Some(value)
+}
+
+/// `2cm` や `1.5inch` のような長さのリテラルを pt で表した値。
+fn parse_length(text: &str) -> Option<f64> {
+    let text = text.trim();
+    let (number, unit) = text.split_at(text.find(|c: char| c.is_ascii_alphabetic())?);
+    let (_, per_unit) = UNITS.iter().find(|(name, _)| *name == unit)?;
+    Some(number.parse::<f64>().ok()? * per_unit)
+}
+
+fn operator(text: &str) -> Option<char> {
+    match text.trim() {
+        "+'" => Some('+'),
+        "-'" => Some('-'),
+        "*'" => Some('*'),
+        "/'" => Some('/'),
+        _ => None,
+    }
+}
+
+/// 子ノードの間にあるテキストを字句に分ける。演算子と括弧以外のものがあれば None。
+fn gap_tokens(gap: &str) -> Option<Vec<Token>> {
+    let mut tokens = vec![];
+    let mut rest = gap.trim_start();
+    while !rest.is_empty() {
+        let (token, len) = match rest.chars().next()? {
+            '(' => (Token::Open, 1),
+            ')' => (Token::Close, 1),
+            _ => (Token::Operator(operator(rest.get(..2)?)?), 2),
+        };
+        tokens.push(token);
+        rest = rest[len..].trim_start();
+    }
+    Some(tokens)
+}
+
+type Tokens = std::iter::Peekable<std::vec::IntoIter<Token>>;
+
+/// `+'` と `-'` からなる式。長さ同士の足し算・引き算のみを許す。
+fn eval_sum(tokens: &mut Tokens) -> Option<Value> {
+    let mut value = eval_product(tokens)?;
+    while let Some(&Token::Operator(op)) = tokens.peek() {
+        if op != '+' && op != '-' {
+            break;
+        }
+        tokens.next();
+        let rhs = eval_product(tokens)?;
+        value = match (value, rhs) {
+            (Value::Length(l), Value::Length(r)) if op == '+' => Value::Length(l + r),
+            (Value::Length(l), Value::Length(r)) => Value::Length(l - r),
+            _ => return None,
+        };
+    }
+    Some(value)
+}
+
+/// `*'` と `/'` からなる式。 `*'` は長さと数値の掛け算、 `/'` は長さ同士の割り算である。
+fn eval_product(tokens: &mut Tokens) -> Option<Value> {
+    let mut value = eval_atom(tokens)?;
+    while let Some(&Token::Operator(op)) = tokens.peek() {
+        if op != '*' && op != '/' {
+            break;
+        }
+        tokens.next();
+        let rhs = eval_atom(tokens)?;
+        value = match (value, rhs) {
+            (Value::Length(l), Value::Number(r)) if op == '*' => Value::Length(l * r),
+            (Value::Length(l), Value::Length(r)) if op == '/' && r != 0.0 => Value::Number(l / r),
+            _ => return None,
+        };
+    }
+    Some(value)
+}
+
+fn eval_atom(tokens: &mut Tokens) -> Option<Value> {
+    match tokens.next()? {
+        Token::Value(value) => Some(value),
+        Token::Open => {
+            let value = eval_sum(tokens)?;
+            match tokens.next()? {
+                Token::Close => Some(value),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// 小数点以下 4 桁までで表し、末尾の 0 を取り除いたもの。
+fn format_number(value: f64) -> String {
+    let text = format!("{:.4}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_owned()
+    } else {
+        text.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use satysfi_parser::{structure::ProgramText, Rule};
+
+    use super::{eval_sum, evaluate, format_number, gap_tokens, parse_length, Token, Value};
+
+    /// `let x = expr` の expr を評価する。
+    fn eval_expr(expr: &str) -> Option<Value> {
+        let program_text = ProgramText::parse(&format!("let x = {}\n", expr)).unwrap();
+        let stmt = program_text.cst.pickup(Rule::let_stmt)[0];
+        evaluate(stmt.inner.last()?, &program_text)
+    }
+
+    fn length(text: &str) -> Token {
+        Token::Value(Value::Length(parse_length(text).unwrap()))
+    }
+
+    fn pt(value: Option<Value>) -> String {
+        match value {
+            Some(Value::Length(pt)) => format_number(pt),
+            other => panic!("expected a length, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn literal_in_each_unit() {
+        assert_eq!(format_number(parse_length("1inch").unwrap()), "72");
+        assert_eq!(format_number(parse_length("2.54cm").unwrap()), "72");
+        assert_eq!(format_number(parse_length("25.4mm").unwrap()), "72");
+        assert_eq!(parse_length("3em"), None);
+    }
+
+    #[test]
+    fn mixed_units() {
+        let tokens = vec![length("1inch"), Token::Operator('-'), length("2.54cm")];
+        assert_eq!(pt(eval_sum(&mut tokens.into_iter().peekable())), "0");
+        assert_eq!(
+            pt(eval_expr("1cm +' 10mm")),
+            format_number(72.0 / 2.54 * 2.0)
+        );
+    }
+
+    #[test]
+    fn parenthesized() {
+        let mut tokens = gap_tokens("(").unwrap();
+        tokens.extend(vec![length("1inch"), Token::Operator('+'), length("1pt")]);
+        tokens.extend(gap_tokens(") *'").unwrap());
+        tokens.push(Token::Value(Value::Number(2.0)));
+        assert_eq!(pt(eval_sum(&mut tokens.into_iter().peekable())), "146");
+        assert_eq!(pt(eval_expr("(1inch +' 1pt) *' 2.0")), "146");
+        // 括弧がなければ掛け算が先
+        assert_eq!(pt(eval_expr("1inch +' 1pt *' 2.0")), "74");
+    }
+
+    #[test]
+    fn ratio_of_lengths() {
+        let tokens = vec![length("1inch"), Token::Operator('/'), length("2pt")];
+        assert_eq!(
+            eval_sum(&mut tokens.into_iter().peekable()),
+            Some(Value::Number(36.0))
+        );
+        assert_eq!(eval_expr("1inch /' 2pt"), Some(Value::Number(36.0)));
+        assert_eq!(
+            pt(eval_expr("1cm *' (1inch /' 2pt)")),
+            format_number(72.0 / 2.54 * 36.0)
+        );
+    }
+
+    #[test]
+    fn reject_ill_typed() {
+        assert_eq!(eval_expr("1cm +' 2.0"), None);
+        assert_eq!(eval_expr("1cm /' 0pt"), None);
+        assert_eq!(eval_expr("1cm *' x"), None);
+        assert_eq!(gap_tokens(" + "), None);
+    }
+}
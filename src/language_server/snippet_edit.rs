@@ -0,0 +1,177 @@
+//! code action の編集にスニペットを含める、 `snippetTextEdit` 拡張への対応。
+//!
+//! lsp-types の `TextEdit` は `insertTextFormat` を持てないため、
+//! スニペットを含む code action には `data` に目印を付けておき、
+//! client に送る直前に JSON の上で `documentChanges` の SnippetTextEdit に書き換える。
+
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use lspower::jsonrpc::{Incoming, Outgoing};
+use lspower::lsp::{CodeAction, InsertTextFormat};
+use serde_json::{json, Value};
+use tower_service::Service;
+
+/// 編集にスニペットを含む code action の `data` に付ける目印。
+const SNIPPET_MARKER: &str = "satysfi-ls.snippet";
+
+/// code action の編集をスニペットとして送るよう目印を付ける。
+pub fn mark_as_snippet(action: &mut CodeAction) {
+    action.data = Some(json!({ SNIPPET_MARKER: true }));
+}
+
+/// LspService を包み、目印の付いた code action の編集を SnippetTextEdit に書き換える。
+#[derive(Debug)]
+pub struct SnippetEditService<S>(S);
+
+impl<S> SnippetEditService<S> {
+    pub fn new(service: S) -> Self {
+        SnippetEditService(service)
+    }
+}
+
+impl<S> Service<Incoming> for SnippetEditService<S>
+where
+    S: Service<Incoming, Response = Option<Outgoing>>,
+    S::Future: Send + 'static,
+    S::Error: 'static,
+{
+    type Response = Option<Outgoing>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.0.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Incoming) -> Self::Future {
+        Box::pin(rewrite_response(self.0.call(request)))
+    }
+}
+
+async fn rewrite_response<F, E>(future: F) -> Result<Option<Outgoing>, E>
+where
+    F: Future<Output = Result<Option<Outgoing>, E>>,
+{
+    Ok(future.await?.map(rewrite_outgoing))
+}
+
+fn rewrite_outgoing(outgoing: Outgoing) -> Outgoing {
+    let mut value = match serde_json::to_value(&outgoing) {
+        Ok(value) => value,
+        Err(_) => return outgoing,
+    };
+    if !rewrite_snippet_edits(&mut value) {
+        return outgoing;
+    }
+    serde_json::from_value(value).unwrap_or(outgoing)
+}
+
+/// 応答に含まれる、目印の付いた code action の `changes` を、
+/// `insertTextFormat` を付けた `documentChanges` に置き換える。書き換えたかどうかを返す。
+fn rewrite_snippet_edits(response: &mut Value) -> bool {
+    let actions = match response.get_mut("result").and_then(Value::as_array_mut) {
+        Some(actions) => actions,
+        None => return false,
+    };
+    let mut rewritten = false;
+    for action in actions.iter_mut().filter_map(Value::as_object_mut) {
+        let marked = action
+            .get("data")
+            .and_then(|data| data.get(SNIPPET_MARKER))
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        if !marked {
+            continue;
+        }
+        action.remove("data");
+        let edit = match action.get_mut("edit").and_then(Value::as_object_mut) {
+            Some(edit) => edit,
+            None => continue,
+        };
+        let changes = match edit.remove("changes") {
+            Some(Value::Object(changes)) => changes,
+            _ => continue,
+        };
+        let document_changes = changes
+            .into_iter()
+            .map(|(uri, edits)| {
+                let edits = match edits {
+                    Value::Array(edits) => edits,
+                    _ => vec![],
+                };
+                let edits = edits
+                    .into_iter()
+                    .map(|mut edit| {
+                        edit["insertTextFormat"] = json!(InsertTextFormat::SNIPPET);
+                        edit
+                    })
+                    .collect::<Vec<_>>();
+                json!({
+                    "textDocument": { "uri": uri, "version": null },
+                    "edits": edits,
+                })
+            })
+            .collect::<Vec<_>>();
+        edit.insert("documentChanges".to_owned(), Value::Array(document_changes));
+        rewritten = true;
+    }
+    rewritten
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{rewrite_snippet_edits, SNIPPET_MARKER};
+
+    #[test]
+    fn rewrite_marked_action() {
+        let range = json!({
+            "start": { "line": 0, "character": 0 },
+            "end": { "line": 0, "character": 3 },
+        });
+        let mut response = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": [
+                {
+                    "title": "Wrap with `\\emph`",
+                    "edit": { "changes": { "file:///a.saty": [
+                        { "range": range.clone(), "newText": "\\\\emph{foo}" }
+                    ] } },
+                    "data": { SNIPPET_MARKER: true },
+                },
+                {
+                    "title": "Organize imports",
+                    "edit": { "changes": { "file:///a.saty": [
+                        { "range": range, "newText": "" }
+                    ] } },
+                },
+            ],
+        });
+        assert!(rewrite_snippet_edits(&mut response));
+
+        let snippet = &response["result"][0];
+        assert!(snippet.get("data").is_none());
+        assert!(snippet["edit"].get("changes").is_none());
+        let change = &snippet["edit"]["documentChanges"][0];
+        assert_eq!(change["textDocument"]["uri"], "file:///a.saty");
+        assert_eq!(change["textDocument"]["version"], json!(null));
+        assert_eq!(change["edits"][0]["newText"], "\\\\emph{foo}");
+        assert_eq!(change["edits"][0]["insertTextFormat"], 2);
+
+        let plain = &response["result"][1];
+        assert!(plain["edit"].get("changes").is_some());
+        assert!(plain["edit"].get("documentChanges").is_none());
+    }
+
+    #[test]
+    fn keep_other_responses() {
+        let mut response = json!({ "jsonrpc": "2.0", "id": 1, "result": null });
+        assert!(!rewrite_snippet_edits(&mut response));
+    }
+}
@@ -0,0 +1,1802 @@
+//! 数式記号の Unicode 文字名の表。
+//!
+//! 数式コマンドが出力する文字を文字名で検索できるようにするために用いる。
+//! Unicode Character Database (Unicode 14.0.0) から、数式でよく用いられる範囲の文字を抜き出して生成した。
+
+/// 数式でよく用いられる文字とその Unicode 文字名。文字の順に並んでいる。
+static NAMES: &[(char, &str)] = &[
+    ('\u{A1}', "INVERTED EXCLAMATION MARK"),
+    ('\u{A2}', "CENT SIGN"),
+    ('\u{A3}', "POUND SIGN"),
+    ('\u{A4}', "CURRENCY SIGN"),
+    ('\u{A5}', "YEN SIGN"),
+    ('\u{A6}', "BROKEN BAR"),
+    ('\u{A7}', "SECTION SIGN"),
+    ('\u{A8}', "DIAERESIS"),
+    ('\u{A9}', "COPYRIGHT SIGN"),
+    ('\u{AA}', "FEMININE ORDINAL INDICATOR"),
+    ('\u{AB}', "LEFT-POINTING DOUBLE ANGLE QUOTATION MARK"),
+    ('\u{AC}', "NOT SIGN"),
+    ('\u{AD}', "SOFT HYPHEN"),
+    ('\u{AE}', "REGISTERED SIGN"),
+    ('\u{AF}', "MACRON"),
+    ('\u{B0}', "DEGREE SIGN"),
+    ('\u{B1}', "PLUS-MINUS SIGN"),
+    ('\u{B2}', "SUPERSCRIPT TWO"),
+    ('\u{B3}', "SUPERSCRIPT THREE"),
+    ('\u{B4}', "ACUTE ACCENT"),
+    ('\u{B5}', "MICRO SIGN"),
+    ('\u{B6}', "PILCROW SIGN"),
+    ('\u{B7}', "MIDDLE DOT"),
+    ('\u{B8}', "CEDILLA"),
+    ('\u{B9}', "SUPERSCRIPT ONE"),
+    ('\u{BA}', "MASCULINE ORDINAL INDICATOR"),
+    ('\u{BB}', "RIGHT-POINTING DOUBLE ANGLE QUOTATION MARK"),
+    ('\u{BC}', "VULGAR FRACTION ONE QUARTER"),
+    ('\u{BD}', "VULGAR FRACTION ONE HALF"),
+    ('\u{BE}', "VULGAR FRACTION THREE QUARTERS"),
+    ('\u{BF}', "INVERTED QUESTION MARK"),
+    ('\u{D7}', "MULTIPLICATION SIGN"),
+    ('\u{F7}', "DIVISION SIGN"),
+    ('\u{131}', "LATIN SMALL LETTER DOTLESS I"),
+    ('\u{237}', "LATIN SMALL LETTER DOTLESS J"),
+    ('\u{391}', "GREEK CAPITAL LETTER ALPHA"),
+    ('\u{392}', "GREEK CAPITAL LETTER BETA"),
+    ('\u{393}', "GREEK CAPITAL LETTER GAMMA"),
+    ('\u{394}', "GREEK CAPITAL LETTER DELTA"),
+    ('\u{395}', "GREEK CAPITAL LETTER EPSILON"),
+    ('\u{396}', "GREEK CAPITAL LETTER ZETA"),
+    ('\u{397}', "GREEK CAPITAL LETTER ETA"),
+    ('\u{398}', "GREEK CAPITAL LETTER THETA"),
+    ('\u{399}', "GREEK CAPITAL LETTER IOTA"),
+    ('\u{39A}', "GREEK CAPITAL LETTER KAPPA"),
+    ('\u{39B}', "GREEK CAPITAL LETTER LAMDA"),
+    ('\u{39C}', "GREEK CAPITAL LETTER MU"),
+    ('\u{39D}', "GREEK CAPITAL LETTER NU"),
+    ('\u{39E}', "GREEK CAPITAL LETTER XI"),
+    ('\u{39F}', "GREEK CAPITAL LETTER OMICRON"),
+    ('\u{3A0}', "GREEK CAPITAL LETTER PI"),
+    ('\u{3A1}', "GREEK CAPITAL LETTER RHO"),
+    ('\u{3A3}', "GREEK CAPITAL LETTER SIGMA"),
+    ('\u{3A4}', "GREEK CAPITAL LETTER TAU"),
+    ('\u{3A5}', "GREEK CAPITAL LETTER UPSILON"),
+    ('\u{3A6}', "GREEK CAPITAL LETTER PHI"),
+    ('\u{3A7}', "GREEK CAPITAL LETTER CHI"),
+    ('\u{3A8}', "GREEK CAPITAL LETTER PSI"),
+    ('\u{3A9}', "GREEK CAPITAL LETTER OMEGA"),
+    ('\u{3AA}', "GREEK CAPITAL LETTER IOTA WITH DIALYTIKA"),
+    ('\u{3AB}', "GREEK CAPITAL LETTER UPSILON WITH DIALYTIKA"),
+    ('\u{3AC}', "GREEK SMALL LETTER ALPHA WITH TONOS"),
+    ('\u{3AD}', "GREEK SMALL LETTER EPSILON WITH TONOS"),
+    ('\u{3AE}', "GREEK SMALL LETTER ETA WITH TONOS"),
+    ('\u{3AF}', "GREEK SMALL LETTER IOTA WITH TONOS"),
+    (
+        '\u{3B0}',
+        "GREEK SMALL LETTER UPSILON WITH DIALYTIKA AND TONOS",
+    ),
+    ('\u{3B1}', "GREEK SMALL LETTER ALPHA"),
+    ('\u{3B2}', "GREEK SMALL LETTER BETA"),
+    ('\u{3B3}', "GREEK SMALL LETTER GAMMA"),
+    ('\u{3B4}', "GREEK SMALL LETTER DELTA"),
+    ('\u{3B5}', "GREEK SMALL LETTER EPSILON"),
+    ('\u{3B6}', "GREEK SMALL LETTER ZETA"),
+    ('\u{3B7}', "GREEK SMALL LETTER ETA"),
+    ('\u{3B8}', "GREEK SMALL LETTER THETA"),
+    ('\u{3B9}', "GREEK SMALL LETTER IOTA"),
+    ('\u{3BA}', "GREEK SMALL LETTER KAPPA"),
+    ('\u{3BB}', "GREEK SMALL LETTER LAMDA"),
+    ('\u{3BC}', "GREEK SMALL LETTER MU"),
+    ('\u{3BD}', "GREEK SMALL LETTER NU"),
+    ('\u{3BE}', "GREEK SMALL LETTER XI"),
+    ('\u{3BF}', "GREEK SMALL LETTER OMICRON"),
+    ('\u{3C0}', "GREEK SMALL LETTER PI"),
+    ('\u{3C1}', "GREEK SMALL LETTER RHO"),
+    ('\u{3C2}', "GREEK SMALL LETTER FINAL SIGMA"),
+    ('\u{3C3}', "GREEK SMALL LETTER SIGMA"),
+    ('\u{3C4}', "GREEK SMALL LETTER TAU"),
+    ('\u{3C5}', "GREEK SMALL LETTER UPSILON"),
+    ('\u{3C6}', "GREEK SMALL LETTER PHI"),
+    ('\u{3C7}', "GREEK SMALL LETTER CHI"),
+    ('\u{3C8}', "GREEK SMALL LETTER PSI"),
+    ('\u{3C9}', "GREEK SMALL LETTER OMEGA"),
+    ('\u{3CA}', "GREEK SMALL LETTER IOTA WITH DIALYTIKA"),
+    ('\u{3CB}', "GREEK SMALL LETTER UPSILON WITH DIALYTIKA"),
+    ('\u{3CC}', "GREEK SMALL LETTER OMICRON WITH TONOS"),
+    ('\u{3CD}', "GREEK SMALL LETTER UPSILON WITH TONOS"),
+    ('\u{3CE}', "GREEK SMALL LETTER OMEGA WITH TONOS"),
+    ('\u{3CF}', "GREEK CAPITAL KAI SYMBOL"),
+    ('\u{3D0}', "GREEK BETA SYMBOL"),
+    ('\u{3D1}', "GREEK THETA SYMBOL"),
+    ('\u{3D2}', "GREEK UPSILON WITH HOOK SYMBOL"),
+    ('\u{3D3}', "GREEK UPSILON WITH ACUTE AND HOOK SYMBOL"),
+    ('\u{3D4}', "GREEK UPSILON WITH DIAERESIS AND HOOK SYMBOL"),
+    ('\u{3D5}', "GREEK PHI SYMBOL"),
+    ('\u{3D6}', "GREEK PI SYMBOL"),
+    ('\u{3D7}', "GREEK KAI SYMBOL"),
+    ('\u{3D8}', "GREEK LETTER ARCHAIC KOPPA"),
+    ('\u{3D9}', "GREEK SMALL LETTER ARCHAIC KOPPA"),
+    ('\u{3DA}', "GREEK LETTER STIGMA"),
+    ('\u{3DB}', "GREEK SMALL LETTER STIGMA"),
+    ('\u{3DC}', "GREEK LETTER DIGAMMA"),
+    ('\u{3DD}', "GREEK SMALL LETTER DIGAMMA"),
+    ('\u{3DE}', "GREEK LETTER KOPPA"),
+    ('\u{3DF}', "GREEK SMALL LETTER KOPPA"),
+    ('\u{3E0}', "GREEK LETTER SAMPI"),
+    ('\u{3E1}', "GREEK SMALL LETTER SAMPI"),
+    ('\u{3E2}', "COPTIC CAPITAL LETTER SHEI"),
+    ('\u{3E3}', "COPTIC SMALL LETTER SHEI"),
+    ('\u{3E4}', "COPTIC CAPITAL LETTER FEI"),
+    ('\u{3E5}', "COPTIC SMALL LETTER FEI"),
+    ('\u{3E6}', "COPTIC CAPITAL LETTER KHEI"),
+    ('\u{3E7}', "COPTIC SMALL LETTER KHEI"),
+    ('\u{3E8}', "COPTIC CAPITAL LETTER HORI"),
+    ('\u{3E9}', "COPTIC SMALL LETTER HORI"),
+    ('\u{3EA}', "COPTIC CAPITAL LETTER GANGIA"),
+    ('\u{3EB}', "COPTIC SMALL LETTER GANGIA"),
+    ('\u{3EC}', "COPTIC CAPITAL LETTER SHIMA"),
+    ('\u{3ED}', "COPTIC SMALL LETTER SHIMA"),
+    ('\u{3EE}', "COPTIC CAPITAL LETTER DEI"),
+    ('\u{3EF}', "COPTIC SMALL LETTER DEI"),
+    ('\u{3F0}', "GREEK KAPPA SYMBOL"),
+    ('\u{3F1}', "GREEK RHO SYMBOL"),
+    ('\u{3F2}', "GREEK LUNATE SIGMA SYMBOL"),
+    ('\u{3F3}', "GREEK LETTER YOT"),
+    ('\u{3F4}', "GREEK CAPITAL THETA SYMBOL"),
+    ('\u{3F5}', "GREEK LUNATE EPSILON SYMBOL"),
+    ('\u{3F6}', "GREEK REVERSED LUNATE EPSILON SYMBOL"),
+    ('\u{2016}', "DOUBLE VERTICAL LINE"),
+    ('\u{2020}', "DAGGER"),
+    ('\u{2021}', "DOUBLE DAGGER"),
+    ('\u{2022}', "BULLET"),
+    ('\u{2023}', "TRIANGULAR BULLET"),
+    ('\u{2024}', "ONE DOT LEADER"),
+    ('\u{2025}', "TWO DOT LEADER"),
+    ('\u{2026}', "HORIZONTAL ELLIPSIS"),
+    ('\u{2027}', "HYPHENATION POINT"),
+    ('\u{2030}', "PER MILLE SIGN"),
+    ('\u{2031}', "PER TEN THOUSAND SIGN"),
+    ('\u{2032}', "PRIME"),
+    ('\u{2033}', "DOUBLE PRIME"),
+    ('\u{2034}', "TRIPLE PRIME"),
+    ('\u{2035}', "REVERSED PRIME"),
+    ('\u{2036}', "REVERSED DOUBLE PRIME"),
+    ('\u{2037}', "REVERSED TRIPLE PRIME"),
+    ('\u{203B}', "REFERENCE MARK"),
+    ('\u{2044}', "FRACTION SLASH"),
+    ('\u{2057}', "QUADRUPLE PRIME"),
+    ('\u{2100}', "ACCOUNT OF"),
+    ('\u{2101}', "ADDRESSED TO THE SUBJECT"),
+    ('\u{2102}', "DOUBLE-STRUCK CAPITAL C"),
+    ('\u{2103}', "DEGREE CELSIUS"),
+    ('\u{2104}', "CENTRE LINE SYMBOL"),
+    ('\u{2105}', "CARE OF"),
+    ('\u{2106}', "CADA UNA"),
+    ('\u{2107}', "EULER CONSTANT"),
+    ('\u{2108}', "SCRUPLE"),
+    ('\u{2109}', "DEGREE FAHRENHEIT"),
+    ('\u{210A}', "SCRIPT SMALL G"),
+    ('\u{210B}', "SCRIPT CAPITAL H"),
+    ('\u{210C}', "BLACK-LETTER CAPITAL H"),
+    ('\u{210D}', "DOUBLE-STRUCK CAPITAL H"),
+    ('\u{210E}', "PLANCK CONSTANT"),
+    ('\u{210F}', "PLANCK CONSTANT OVER TWO PI"),
+    ('\u{2110}', "SCRIPT CAPITAL I"),
+    ('\u{2111}', "BLACK-LETTER CAPITAL I"),
+    ('\u{2112}', "SCRIPT CAPITAL L"),
+    ('\u{2113}', "SCRIPT SMALL L"),
+    ('\u{2114}', "L B BAR SYMBOL"),
+    ('\u{2115}', "DOUBLE-STRUCK CAPITAL N"),
+    ('\u{2116}', "NUMERO SIGN"),
+    ('\u{2117}', "SOUND RECORDING COPYRIGHT"),
+    ('\u{2118}', "SCRIPT CAPITAL P"),
+    ('\u{2119}', "DOUBLE-STRUCK CAPITAL P"),
+    ('\u{211A}', "DOUBLE-STRUCK CAPITAL Q"),
+    ('\u{211B}', "SCRIPT CAPITAL R"),
+    ('\u{211C}', "BLACK-LETTER CAPITAL R"),
+    ('\u{211D}', "DOUBLE-STRUCK CAPITAL R"),
+    ('\u{211E}', "PRESCRIPTION TAKE"),
+    ('\u{211F}', "RESPONSE"),
+    ('\u{2120}', "SERVICE MARK"),
+    ('\u{2121}', "TELEPHONE SIGN"),
+    ('\u{2122}', "TRADE MARK SIGN"),
+    ('\u{2123}', "VERSICLE"),
+    ('\u{2124}', "DOUBLE-STRUCK CAPITAL Z"),
+    ('\u{2125}', "OUNCE SIGN"),
+    ('\u{2126}', "OHM SIGN"),
+    ('\u{2127}', "INVERTED OHM SIGN"),
+    ('\u{2128}', "BLACK-LETTER CAPITAL Z"),
+    ('\u{2129}', "TURNED GREEK SMALL LETTER IOTA"),
+    ('\u{212A}', "KELVIN SIGN"),
+    ('\u{212B}', "ANGSTROM SIGN"),
+    ('\u{212C}', "SCRIPT CAPITAL B"),
+    ('\u{212D}', "BLACK-LETTER CAPITAL C"),
+    ('\u{212E}', "ESTIMATED SYMBOL"),
+    ('\u{212F}', "SCRIPT SMALL E"),
+    ('\u{2130}', "SCRIPT CAPITAL E"),
+    ('\u{2131}', "SCRIPT CAPITAL F"),
+    ('\u{2132}', "TURNED CAPITAL F"),
+    ('\u{2133}', "SCRIPT CAPITAL M"),
+    ('\u{2134}', "SCRIPT SMALL O"),
+    ('\u{2135}', "ALEF SYMBOL"),
+    ('\u{2136}', "BET SYMBOL"),
+    ('\u{2137}', "GIMEL SYMBOL"),
+    ('\u{2138}', "DALET SYMBOL"),
+    ('\u{2139}', "INFORMATION SOURCE"),
+    ('\u{213A}', "ROTATED CAPITAL Q"),
+    ('\u{213B}', "FACSIMILE SIGN"),
+    ('\u{213C}', "DOUBLE-STRUCK SMALL PI"),
+    ('\u{213D}', "DOUBLE-STRUCK SMALL GAMMA"),
+    ('\u{213E}', "DOUBLE-STRUCK CAPITAL GAMMA"),
+    ('\u{213F}', "DOUBLE-STRUCK CAPITAL PI"),
+    ('\u{2140}', "DOUBLE-STRUCK N-ARY SUMMATION"),
+    ('\u{2141}', "TURNED SANS-SERIF CAPITAL G"),
+    ('\u{2142}', "TURNED SANS-SERIF CAPITAL L"),
+    ('\u{2143}', "REVERSED SANS-SERIF CAPITAL L"),
+    ('\u{2144}', "TURNED SANS-SERIF CAPITAL Y"),
+    ('\u{2145}', "DOUBLE-STRUCK ITALIC CAPITAL D"),
+    ('\u{2146}', "DOUBLE-STRUCK ITALIC SMALL D"),
+    ('\u{2147}', "DOUBLE-STRUCK ITALIC SMALL E"),
+    ('\u{2148}', "DOUBLE-STRUCK ITALIC SMALL I"),
+    ('\u{2149}', "DOUBLE-STRUCK ITALIC SMALL J"),
+    ('\u{214A}', "PROPERTY LINE"),
+    ('\u{214B}', "TURNED AMPERSAND"),
+    ('\u{214C}', "PER SIGN"),
+    ('\u{214D}', "AKTIESELSKAB"),
+    ('\u{214E}', "TURNED SMALL F"),
+    ('\u{214F}', "SYMBOL FOR SAMARITAN SOURCE"),
+    ('\u{2190}', "LEFTWARDS ARROW"),
+    ('\u{2191}', "UPWARDS ARROW"),
+    ('\u{2192}', "RIGHTWARDS ARROW"),
+    ('\u{2193}', "DOWNWARDS ARROW"),
+    ('\u{2194}', "LEFT RIGHT ARROW"),
+    ('\u{2195}', "UP DOWN ARROW"),
+    ('\u{2196}', "NORTH WEST ARROW"),
+    ('\u{2197}', "NORTH EAST ARROW"),
+    ('\u{2198}', "SOUTH EAST ARROW"),
+    ('\u{2199}', "SOUTH WEST ARROW"),
+    ('\u{219A}', "LEFTWARDS ARROW WITH STROKE"),
+    ('\u{219B}', "RIGHTWARDS ARROW WITH STROKE"),
+    ('\u{219C}', "LEFTWARDS WAVE ARROW"),
+    ('\u{219D}', "RIGHTWARDS WAVE ARROW"),
+    ('\u{219E}', "LEFTWARDS TWO HEADED ARROW"),
+    ('\u{219F}', "UPWARDS TWO HEADED ARROW"),
+    ('\u{21A0}', "RIGHTWARDS TWO HEADED ARROW"),
+    ('\u{21A1}', "DOWNWARDS TWO HEADED ARROW"),
+    ('\u{21A2}', "LEFTWARDS ARROW WITH TAIL"),
+    ('\u{21A3}', "RIGHTWARDS ARROW WITH TAIL"),
+    ('\u{21A4}', "LEFTWARDS ARROW FROM BAR"),
+    ('\u{21A5}', "UPWARDS ARROW FROM BAR"),
+    ('\u{21A6}', "RIGHTWARDS ARROW FROM BAR"),
+    ('\u{21A7}', "DOWNWARDS ARROW FROM BAR"),
+    ('\u{21A8}', "UP DOWN ARROW WITH BASE"),
+    ('\u{21A9}', "LEFTWARDS ARROW WITH HOOK"),
+    ('\u{21AA}', "RIGHTWARDS ARROW WITH HOOK"),
+    ('\u{21AB}', "LEFTWARDS ARROW WITH LOOP"),
+    ('\u{21AC}', "RIGHTWARDS ARROW WITH LOOP"),
+    ('\u{21AD}', "LEFT RIGHT WAVE ARROW"),
+    ('\u{21AE}', "LEFT RIGHT ARROW WITH STROKE"),
+    ('\u{21AF}', "DOWNWARDS ZIGZAG ARROW"),
+    ('\u{21B0}', "UPWARDS ARROW WITH TIP LEFTWARDS"),
+    ('\u{21B1}', "UPWARDS ARROW WITH TIP RIGHTWARDS"),
+    ('\u{21B2}', "DOWNWARDS ARROW WITH TIP LEFTWARDS"),
+    ('\u{21B3}', "DOWNWARDS ARROW WITH TIP RIGHTWARDS"),
+    ('\u{21B4}', "RIGHTWARDS ARROW WITH CORNER DOWNWARDS"),
+    ('\u{21B5}', "DOWNWARDS ARROW WITH CORNER LEFTWARDS"),
+    ('\u{21B6}', "ANTICLOCKWISE TOP SEMICIRCLE ARROW"),
+    ('\u{21B7}', "CLOCKWISE TOP SEMICIRCLE ARROW"),
+    ('\u{21B8}', "NORTH WEST ARROW TO LONG BAR"),
+    (
+        '\u{21B9}',
+        "LEFTWARDS ARROW TO BAR OVER RIGHTWARDS ARROW TO BAR",
+    ),
+    ('\u{21BA}', "ANTICLOCKWISE OPEN CIRCLE ARROW"),
+    ('\u{21BB}', "CLOCKWISE OPEN CIRCLE ARROW"),
+    ('\u{21BC}', "LEFTWARDS HARPOON WITH BARB UPWARDS"),
+    ('\u{21BD}', "LEFTWARDS HARPOON WITH BARB DOWNWARDS"),
+    ('\u{21BE}', "UPWARDS HARPOON WITH BARB RIGHTWARDS"),
+    ('\u{21BF}', "UPWARDS HARPOON WITH BARB LEFTWARDS"),
+    ('\u{21C0}', "RIGHTWARDS HARPOON WITH BARB UPWARDS"),
+    ('\u{21C1}', "RIGHTWARDS HARPOON WITH BARB DOWNWARDS"),
+    ('\u{21C2}', "DOWNWARDS HARPOON WITH BARB RIGHTWARDS"),
+    ('\u{21C3}', "DOWNWARDS HARPOON WITH BARB LEFTWARDS"),
+    ('\u{21C4}', "RIGHTWARDS ARROW OVER LEFTWARDS ARROW"),
+    ('\u{21C5}', "UPWARDS ARROW LEFTWARDS OF DOWNWARDS ARROW"),
+    ('\u{21C6}', "LEFTWARDS ARROW OVER RIGHTWARDS ARROW"),
+    ('\u{21C7}', "LEFTWARDS PAIRED ARROWS"),
+    ('\u{21C8}', "UPWARDS PAIRED ARROWS"),
+    ('\u{21C9}', "RIGHTWARDS PAIRED ARROWS"),
+    ('\u{21CA}', "DOWNWARDS PAIRED ARROWS"),
+    ('\u{21CB}', "LEFTWARDS HARPOON OVER RIGHTWARDS HARPOON"),
+    ('\u{21CC}', "RIGHTWARDS HARPOON OVER LEFTWARDS HARPOON"),
+    ('\u{21CD}', "LEFTWARDS DOUBLE ARROW WITH STROKE"),
+    ('\u{21CE}', "LEFT RIGHT DOUBLE ARROW WITH STROKE"),
+    ('\u{21CF}', "RIGHTWARDS DOUBLE ARROW WITH STROKE"),
+    ('\u{21D0}', "LEFTWARDS DOUBLE ARROW"),
+    ('\u{21D1}', "UPWARDS DOUBLE ARROW"),
+    ('\u{21D2}', "RIGHTWARDS DOUBLE ARROW"),
+    ('\u{21D3}', "DOWNWARDS DOUBLE ARROW"),
+    ('\u{21D4}', "LEFT RIGHT DOUBLE ARROW"),
+    ('\u{21D5}', "UP DOWN DOUBLE ARROW"),
+    ('\u{21D6}', "NORTH WEST DOUBLE ARROW"),
+    ('\u{21D7}', "NORTH EAST DOUBLE ARROW"),
+    ('\u{21D8}', "SOUTH EAST DOUBLE ARROW"),
+    ('\u{21D9}', "SOUTH WEST DOUBLE ARROW"),
+    ('\u{21DA}', "LEFTWARDS TRIPLE ARROW"),
+    ('\u{21DB}', "RIGHTWARDS TRIPLE ARROW"),
+    ('\u{21DC}', "LEFTWARDS SQUIGGLE ARROW"),
+    ('\u{21DD}', "RIGHTWARDS SQUIGGLE ARROW"),
+    ('\u{21DE}', "UPWARDS ARROW WITH DOUBLE STROKE"),
+    ('\u{21DF}', "DOWNWARDS ARROW WITH DOUBLE STROKE"),
+    ('\u{21E0}', "LEFTWARDS DASHED ARROW"),
+    ('\u{21E1}', "UPWARDS DASHED ARROW"),
+    ('\u{21E2}', "RIGHTWARDS DASHED ARROW"),
+    ('\u{21E3}', "DOWNWARDS DASHED ARROW"),
+    ('\u{21E4}', "LEFTWARDS ARROW TO BAR"),
+    ('\u{21E5}', "RIGHTWARDS ARROW TO BAR"),
+    ('\u{21E6}', "LEFTWARDS WHITE ARROW"),
+    ('\u{21E7}', "UPWARDS WHITE ARROW"),
+    ('\u{21E8}', "RIGHTWARDS WHITE ARROW"),
+    ('\u{21E9}', "DOWNWARDS WHITE ARROW"),
+    ('\u{21EA}', "UPWARDS WHITE ARROW FROM BAR"),
+    ('\u{21EB}', "UPWARDS WHITE ARROW ON PEDESTAL"),
+    (
+        '\u{21EC}',
+        "UPWARDS WHITE ARROW ON PEDESTAL WITH HORIZONTAL BAR",
+    ),
+    (
+        '\u{21ED}',
+        "UPWARDS WHITE ARROW ON PEDESTAL WITH VERTICAL BAR",
+    ),
+    ('\u{21EE}', "UPWARDS WHITE DOUBLE ARROW"),
+    ('\u{21EF}', "UPWARDS WHITE DOUBLE ARROW ON PEDESTAL"),
+    ('\u{21F0}', "RIGHTWARDS WHITE ARROW FROM WALL"),
+    ('\u{21F1}', "NORTH WEST ARROW TO CORNER"),
+    ('\u{21F2}', "SOUTH EAST ARROW TO CORNER"),
+    ('\u{21F3}', "UP DOWN WHITE ARROW"),
+    ('\u{21F4}', "RIGHT ARROW WITH SMALL CIRCLE"),
+    ('\u{21F5}', "DOWNWARDS ARROW LEFTWARDS OF UPWARDS ARROW"),
+    ('\u{21F6}', "THREE RIGHTWARDS ARROWS"),
+    ('\u{21F7}', "LEFTWARDS ARROW WITH VERTICAL STROKE"),
+    ('\u{21F8}', "RIGHTWARDS ARROW WITH VERTICAL STROKE"),
+    ('\u{21F9}', "LEFT RIGHT ARROW WITH VERTICAL STROKE"),
+    ('\u{21FA}', "LEFTWARDS ARROW WITH DOUBLE VERTICAL STROKE"),
+    ('\u{21FB}', "RIGHTWARDS ARROW WITH DOUBLE VERTICAL STROKE"),
+    ('\u{21FC}', "LEFT RIGHT ARROW WITH DOUBLE VERTICAL STROKE"),
+    ('\u{21FD}', "LEFTWARDS OPEN-HEADED ARROW"),
+    ('\u{21FE}', "RIGHTWARDS OPEN-HEADED ARROW"),
+    ('\u{21FF}', "LEFT RIGHT OPEN-HEADED ARROW"),
+    ('\u{2200}', "FOR ALL"),
+    ('\u{2201}', "COMPLEMENT"),
+    ('\u{2202}', "PARTIAL DIFFERENTIAL"),
+    ('\u{2203}', "THERE EXISTS"),
+    ('\u{2204}', "THERE DOES NOT EXIST"),
+    ('\u{2205}', "EMPTY SET"),
+    ('\u{2206}', "INCREMENT"),
+    ('\u{2207}', "NABLA"),
+    ('\u{2208}', "ELEMENT OF"),
+    ('\u{2209}', "NOT AN ELEMENT OF"),
+    ('\u{220A}', "SMALL ELEMENT OF"),
+    ('\u{220B}', "CONTAINS AS MEMBER"),
+    ('\u{220C}', "DOES NOT CONTAIN AS MEMBER"),
+    ('\u{220D}', "SMALL CONTAINS AS MEMBER"),
+    ('\u{220E}', "END OF PROOF"),
+    ('\u{220F}', "N-ARY PRODUCT"),
+    ('\u{2210}', "N-ARY COPRODUCT"),
+    ('\u{2211}', "N-ARY SUMMATION"),
+    ('\u{2212}', "MINUS SIGN"),
+    ('\u{2213}', "MINUS-OR-PLUS SIGN"),
+    ('\u{2214}', "DOT PLUS"),
+    ('\u{2215}', "DIVISION SLASH"),
+    ('\u{2216}', "SET MINUS"),
+    ('\u{2217}', "ASTERISK OPERATOR"),
+    ('\u{2218}', "RING OPERATOR"),
+    ('\u{2219}', "BULLET OPERATOR"),
+    ('\u{221A}', "SQUARE ROOT"),
+    ('\u{221B}', "CUBE ROOT"),
+    ('\u{221C}', "FOURTH ROOT"),
+    ('\u{221D}', "PROPORTIONAL TO"),
+    ('\u{221E}', "INFINITY"),
+    ('\u{221F}', "RIGHT ANGLE"),
+    ('\u{2220}', "ANGLE"),
+    ('\u{2221}', "MEASURED ANGLE"),
+    ('\u{2222}', "SPHERICAL ANGLE"),
+    ('\u{2223}', "DIVIDES"),
+    ('\u{2224}', "DOES NOT DIVIDE"),
+    ('\u{2225}', "PARALLEL TO"),
+    ('\u{2226}', "NOT PARALLEL TO"),
+    ('\u{2227}', "LOGICAL AND"),
+    ('\u{2228}', "LOGICAL OR"),
+    ('\u{2229}', "INTERSECTION"),
+    ('\u{222A}', "UNION"),
+    ('\u{222B}', "INTEGRAL"),
+    ('\u{222C}', "DOUBLE INTEGRAL"),
+    ('\u{222D}', "TRIPLE INTEGRAL"),
+    ('\u{222E}', "CONTOUR INTEGRAL"),
+    ('\u{222F}', "SURFACE INTEGRAL"),
+    ('\u{2230}', "VOLUME INTEGRAL"),
+    ('\u{2231}', "CLOCKWISE INTEGRAL"),
+    ('\u{2232}', "CLOCKWISE CONTOUR INTEGRAL"),
+    ('\u{2233}', "ANTICLOCKWISE CONTOUR INTEGRAL"),
+    ('\u{2234}', "THEREFORE"),
+    ('\u{2235}', "BECAUSE"),
+    ('\u{2236}', "RATIO"),
+    ('\u{2237}', "PROPORTION"),
+    ('\u{2238}', "DOT MINUS"),
+    ('\u{2239}', "EXCESS"),
+    ('\u{223A}', "GEOMETRIC PROPORTION"),
+    ('\u{223B}', "HOMOTHETIC"),
+    ('\u{223C}', "TILDE OPERATOR"),
+    ('\u{223D}', "REVERSED TILDE"),
+    ('\u{223E}', "INVERTED LAZY S"),
+    ('\u{223F}', "SINE WAVE"),
+    ('\u{2240}', "WREATH PRODUCT"),
+    ('\u{2241}', "NOT TILDE"),
+    ('\u{2242}', "MINUS TILDE"),
+    ('\u{2243}', "ASYMPTOTICALLY EQUAL TO"),
+    ('\u{2244}', "NOT ASYMPTOTICALLY EQUAL TO"),
+    ('\u{2245}', "APPROXIMATELY EQUAL TO"),
+    ('\u{2246}', "APPROXIMATELY BUT NOT ACTUALLY EQUAL TO"),
+    ('\u{2247}', "NEITHER APPROXIMATELY NOR ACTUALLY EQUAL TO"),
+    ('\u{2248}', "ALMOST EQUAL TO"),
+    ('\u{2249}', "NOT ALMOST EQUAL TO"),
+    ('\u{224A}', "ALMOST EQUAL OR EQUAL TO"),
+    ('\u{224B}', "TRIPLE TILDE"),
+    ('\u{224C}', "ALL EQUAL TO"),
+    ('\u{224D}', "EQUIVALENT TO"),
+    ('\u{224E}', "GEOMETRICALLY EQUIVALENT TO"),
+    ('\u{224F}', "DIFFERENCE BETWEEN"),
+    ('\u{2250}', "APPROACHES THE LIMIT"),
+    ('\u{2251}', "GEOMETRICALLY EQUAL TO"),
+    ('\u{2252}', "APPROXIMATELY EQUAL TO OR THE IMAGE OF"),
+    ('\u{2253}', "IMAGE OF OR APPROXIMATELY EQUAL TO"),
+    ('\u{2254}', "COLON EQUALS"),
+    ('\u{2255}', "EQUALS COLON"),
+    ('\u{2256}', "RING IN EQUAL TO"),
+    ('\u{2257}', "RING EQUAL TO"),
+    ('\u{2258}', "CORRESPONDS TO"),
+    ('\u{2259}', "ESTIMATES"),
+    ('\u{225A}', "EQUIANGULAR TO"),
+    ('\u{225B}', "STAR EQUALS"),
+    ('\u{225C}', "DELTA EQUAL TO"),
+    ('\u{225D}', "EQUAL TO BY DEFINITION"),
+    ('\u{225E}', "MEASURED BY"),
+    ('\u{225F}', "QUESTIONED EQUAL TO"),
+    ('\u{2260}', "NOT EQUAL TO"),
+    ('\u{2261}', "IDENTICAL TO"),
+    ('\u{2262}', "NOT IDENTICAL TO"),
+    ('\u{2263}', "STRICTLY EQUIVALENT TO"),
+    ('\u{2264}', "LESS-THAN OR EQUAL TO"),
+    ('\u{2265}', "GREATER-THAN OR EQUAL TO"),
+    ('\u{2266}', "LESS-THAN OVER EQUAL TO"),
+    ('\u{2267}', "GREATER-THAN OVER EQUAL TO"),
+    ('\u{2268}', "LESS-THAN BUT NOT EQUAL TO"),
+    ('\u{2269}', "GREATER-THAN BUT NOT EQUAL TO"),
+    ('\u{226A}', "MUCH LESS-THAN"),
+    ('\u{226B}', "MUCH GREATER-THAN"),
+    ('\u{226C}', "BETWEEN"),
+    ('\u{226D}', "NOT EQUIVALENT TO"),
+    ('\u{226E}', "NOT LESS-THAN"),
+    ('\u{226F}', "NOT GREATER-THAN"),
+    ('\u{2270}', "NEITHER LESS-THAN NOR EQUAL TO"),
+    ('\u{2271}', "NEITHER GREATER-THAN NOR EQUAL TO"),
+    ('\u{2272}', "LESS-THAN OR EQUIVALENT TO"),
+    ('\u{2273}', "GREATER-THAN OR EQUIVALENT TO"),
+    ('\u{2274}', "NEITHER LESS-THAN NOR EQUIVALENT TO"),
+    ('\u{2275}', "NEITHER GREATER-THAN NOR EQUIVALENT TO"),
+    ('\u{2276}', "LESS-THAN OR GREATER-THAN"),
+    ('\u{2277}', "GREATER-THAN OR LESS-THAN"),
+    ('\u{2278}', "NEITHER LESS-THAN NOR GREATER-THAN"),
+    ('\u{2279}', "NEITHER GREATER-THAN NOR LESS-THAN"),
+    ('\u{227A}', "PRECEDES"),
+    ('\u{227B}', "SUCCEEDS"),
+    ('\u{227C}', "PRECEDES OR EQUAL TO"),
+    ('\u{227D}', "SUCCEEDS OR EQUAL TO"),
+    ('\u{227E}', "PRECEDES OR EQUIVALENT TO"),
+    ('\u{227F}', "SUCCEEDS OR EQUIVALENT TO"),
+    ('\u{2280}', "DOES NOT PRECEDE"),
+    ('\u{2281}', "DOES NOT SUCCEED"),
+    ('\u{2282}', "SUBSET OF"),
+    ('\u{2283}', "SUPERSET OF"),
+    ('\u{2284}', "NOT A SUBSET OF"),
+    ('\u{2285}', "NOT A SUPERSET OF"),
+    ('\u{2286}', "SUBSET OF OR EQUAL TO"),
+    ('\u{2287}', "SUPERSET OF OR EQUAL TO"),
+    ('\u{2288}', "NEITHER A SUBSET OF NOR EQUAL TO"),
+    ('\u{2289}', "NEITHER A SUPERSET OF NOR EQUAL TO"),
+    ('\u{228A}', "SUBSET OF WITH NOT EQUAL TO"),
+    ('\u{228B}', "SUPERSET OF WITH NOT EQUAL TO"),
+    ('\u{228C}', "MULTISET"),
+    ('\u{228D}', "MULTISET MULTIPLICATION"),
+    ('\u{228E}', "MULTISET UNION"),
+    ('\u{228F}', "SQUARE IMAGE OF"),
+    ('\u{2290}', "SQUARE ORIGINAL OF"),
+    ('\u{2291}', "SQUARE IMAGE OF OR EQUAL TO"),
+    ('\u{2292}', "SQUARE ORIGINAL OF OR EQUAL TO"),
+    ('\u{2293}', "SQUARE CAP"),
+    ('\u{2294}', "SQUARE CUP"),
+    ('\u{2295}', "CIRCLED PLUS"),
+    ('\u{2296}', "CIRCLED MINUS"),
+    ('\u{2297}', "CIRCLED TIMES"),
+    ('\u{2298}', "CIRCLED DIVISION SLASH"),
+    ('\u{2299}', "CIRCLED DOT OPERATOR"),
+    ('\u{229A}', "CIRCLED RING OPERATOR"),
+    ('\u{229B}', "CIRCLED ASTERISK OPERATOR"),
+    ('\u{229C}', "CIRCLED EQUALS"),
+    ('\u{229D}', "CIRCLED DASH"),
+    ('\u{229E}', "SQUARED PLUS"),
+    ('\u{229F}', "SQUARED MINUS"),
+    ('\u{22A0}', "SQUARED TIMES"),
+    ('\u{22A1}', "SQUARED DOT OPERATOR"),
+    ('\u{22A2}', "RIGHT TACK"),
+    ('\u{22A3}', "LEFT TACK"),
+    ('\u{22A4}', "DOWN TACK"),
+    ('\u{22A5}', "UP TACK"),
+    ('\u{22A6}', "ASSERTION"),
+    ('\u{22A7}', "MODELS"),
+    ('\u{22A8}', "TRUE"),
+    ('\u{22A9}', "FORCES"),
+    ('\u{22AA}', "TRIPLE VERTICAL BAR RIGHT TURNSTILE"),
+    ('\u{22AB}', "DOUBLE VERTICAL BAR DOUBLE RIGHT TURNSTILE"),
+    ('\u{22AC}', "DOES NOT PROVE"),
+    ('\u{22AD}', "NOT TRUE"),
+    ('\u{22AE}', "DOES NOT FORCE"),
+    (
+        '\u{22AF}',
+        "NEGATED DOUBLE VERTICAL BAR DOUBLE RIGHT TURNSTILE",
+    ),
+    ('\u{22B0}', "PRECEDES UNDER RELATION"),
+    ('\u{22B1}', "SUCCEEDS UNDER RELATION"),
+    ('\u{22B2}', "NORMAL SUBGROUP OF"),
+    ('\u{22B3}', "CONTAINS AS NORMAL SUBGROUP"),
+    ('\u{22B4}', "NORMAL SUBGROUP OF OR EQUAL TO"),
+    ('\u{22B5}', "CONTAINS AS NORMAL SUBGROUP OR EQUAL TO"),
+    ('\u{22B6}', "ORIGINAL OF"),
+    ('\u{22B7}', "IMAGE OF"),
+    ('\u{22B8}', "MULTIMAP"),
+    ('\u{22B9}', "HERMITIAN CONJUGATE MATRIX"),
+    ('\u{22BA}', "INTERCALATE"),
+    ('\u{22BB}', "XOR"),
+    ('\u{22BC}', "NAND"),
+    ('\u{22BD}', "NOR"),
+    ('\u{22BE}', "RIGHT ANGLE WITH ARC"),
+    ('\u{22BF}', "RIGHT TRIANGLE"),
+    ('\u{22C0}', "N-ARY LOGICAL AND"),
+    ('\u{22C1}', "N-ARY LOGICAL OR"),
+    ('\u{22C2}', "N-ARY INTERSECTION"),
+    ('\u{22C3}', "N-ARY UNION"),
+    ('\u{22C4}', "DIAMOND OPERATOR"),
+    ('\u{22C5}', "DOT OPERATOR"),
+    ('\u{22C6}', "STAR OPERATOR"),
+    ('\u{22C7}', "DIVISION TIMES"),
+    ('\u{22C8}', "BOWTIE"),
+    ('\u{22C9}', "LEFT NORMAL FACTOR SEMIDIRECT PRODUCT"),
+    ('\u{22CA}', "RIGHT NORMAL FACTOR SEMIDIRECT PRODUCT"),
+    ('\u{22CB}', "LEFT SEMIDIRECT PRODUCT"),
+    ('\u{22CC}', "RIGHT SEMIDIRECT PRODUCT"),
+    ('\u{22CD}', "REVERSED TILDE EQUALS"),
+    ('\u{22CE}', "CURLY LOGICAL OR"),
+    ('\u{22CF}', "CURLY LOGICAL AND"),
+    ('\u{22D0}', "DOUBLE SUBSET"),
+    ('\u{22D1}', "DOUBLE SUPERSET"),
+    ('\u{22D2}', "DOUBLE INTERSECTION"),
+    ('\u{22D3}', "DOUBLE UNION"),
+    ('\u{22D4}', "PITCHFORK"),
+    ('\u{22D5}', "EQUAL AND PARALLEL TO"),
+    ('\u{22D6}', "LESS-THAN WITH DOT"),
+    ('\u{22D7}', "GREATER-THAN WITH DOT"),
+    ('\u{22D8}', "VERY MUCH LESS-THAN"),
+    ('\u{22D9}', "VERY MUCH GREATER-THAN"),
+    ('\u{22DA}', "LESS-THAN EQUAL TO OR GREATER-THAN"),
+    ('\u{22DB}', "GREATER-THAN EQUAL TO OR LESS-THAN"),
+    ('\u{22DC}', "EQUAL TO OR LESS-THAN"),
+    ('\u{22DD}', "EQUAL TO OR GREATER-THAN"),
+    ('\u{22DE}', "EQUAL TO OR PRECEDES"),
+    ('\u{22DF}', "EQUAL TO OR SUCCEEDS"),
+    ('\u{22E0}', "DOES NOT PRECEDE OR EQUAL"),
+    ('\u{22E1}', "DOES NOT SUCCEED OR EQUAL"),
+    ('\u{22E2}', "NOT SQUARE IMAGE OF OR EQUAL TO"),
+    ('\u{22E3}', "NOT SQUARE ORIGINAL OF OR EQUAL TO"),
+    ('\u{22E4}', "SQUARE IMAGE OF OR NOT EQUAL TO"),
+    ('\u{22E5}', "SQUARE ORIGINAL OF OR NOT EQUAL TO"),
+    ('\u{22E6}', "LESS-THAN BUT NOT EQUIVALENT TO"),
+    ('\u{22E7}', "GREATER-THAN BUT NOT EQUIVALENT TO"),
+    ('\u{22E8}', "PRECEDES BUT NOT EQUIVALENT TO"),
+    ('\u{22E9}', "SUCCEEDS BUT NOT EQUIVALENT TO"),
+    ('\u{22EA}', "NOT NORMAL SUBGROUP OF"),
+    ('\u{22EB}', "DOES NOT CONTAIN AS NORMAL SUBGROUP"),
+    ('\u{22EC}', "NOT NORMAL SUBGROUP OF OR EQUAL TO"),
+    ('\u{22ED}', "DOES NOT CONTAIN AS NORMAL SUBGROUP OR EQUAL"),
+    ('\u{22EE}', "VERTICAL ELLIPSIS"),
+    ('\u{22EF}', "MIDLINE HORIZONTAL ELLIPSIS"),
+    ('\u{22F0}', "UP RIGHT DIAGONAL ELLIPSIS"),
+    ('\u{22F1}', "DOWN RIGHT DIAGONAL ELLIPSIS"),
+    ('\u{22F2}', "ELEMENT OF WITH LONG HORIZONTAL STROKE"),
+    (
+        '\u{22F3}',
+        "ELEMENT OF WITH VERTICAL BAR AT END OF HORIZONTAL STROKE",
+    ),
+    (
+        '\u{22F4}',
+        "SMALL ELEMENT OF WITH VERTICAL BAR AT END OF HORIZONTAL STROKE",
+    ),
+    ('\u{22F5}', "ELEMENT OF WITH DOT ABOVE"),
+    ('\u{22F6}', "ELEMENT OF WITH OVERBAR"),
+    ('\u{22F7}', "SMALL ELEMENT OF WITH OVERBAR"),
+    ('\u{22F8}', "ELEMENT OF WITH UNDERBAR"),
+    ('\u{22F9}', "ELEMENT OF WITH TWO HORIZONTAL STROKES"),
+    ('\u{22FA}', "CONTAINS WITH LONG HORIZONTAL STROKE"),
+    (
+        '\u{22FB}',
+        "CONTAINS WITH VERTICAL BAR AT END OF HORIZONTAL STROKE",
+    ),
+    (
+        '\u{22FC}',
+        "SMALL CONTAINS WITH VERTICAL BAR AT END OF HORIZONTAL STROKE",
+    ),
+    ('\u{22FD}', "CONTAINS WITH OVERBAR"),
+    ('\u{22FE}', "SMALL CONTAINS WITH OVERBAR"),
+    ('\u{22FF}', "Z NOTATION BAG MEMBERSHIP"),
+    ('\u{2300}', "DIAMETER SIGN"),
+    ('\u{2301}', "ELECTRIC ARROW"),
+    ('\u{2302}', "HOUSE"),
+    ('\u{2303}', "UP ARROWHEAD"),
+    ('\u{2304}', "DOWN ARROWHEAD"),
+    ('\u{2305}', "PROJECTIVE"),
+    ('\u{2306}', "PERSPECTIVE"),
+    ('\u{2307}', "WAVY LINE"),
+    ('\u{2308}', "LEFT CEILING"),
+    ('\u{2309}', "RIGHT CEILING"),
+    ('\u{230A}', "LEFT FLOOR"),
+    ('\u{230B}', "RIGHT FLOOR"),
+    ('\u{230C}', "BOTTOM RIGHT CROP"),
+    ('\u{230D}', "BOTTOM LEFT CROP"),
+    ('\u{230E}', "TOP RIGHT CROP"),
+    ('\u{230F}', "TOP LEFT CROP"),
+    ('\u{2310}', "REVERSED NOT SIGN"),
+    ('\u{2311}', "SQUARE LOZENGE"),
+    ('\u{2312}', "ARC"),
+    ('\u{2313}', "SEGMENT"),
+    ('\u{2314}', "SECTOR"),
+    ('\u{2315}', "TELEPHONE RECORDER"),
+    ('\u{2316}', "POSITION INDICATOR"),
+    ('\u{2317}', "VIEWDATA SQUARE"),
+    ('\u{2318}', "PLACE OF INTEREST SIGN"),
+    ('\u{2319}', "TURNED NOT SIGN"),
+    ('\u{231A}', "WATCH"),
+    ('\u{231B}', "HOURGLASS"),
+    ('\u{231C}', "TOP LEFT CORNER"),
+    ('\u{231D}', "TOP RIGHT CORNER"),
+    ('\u{231E}', "BOTTOM LEFT CORNER"),
+    ('\u{231F}', "BOTTOM RIGHT CORNER"),
+    ('\u{2320}', "TOP HALF INTEGRAL"),
+    ('\u{2321}', "BOTTOM HALF INTEGRAL"),
+    ('\u{2322}', "FROWN"),
+    ('\u{2323}', "SMILE"),
+    ('\u{2324}', "UP ARROWHEAD BETWEEN TWO HORIZONTAL BARS"),
+    ('\u{2325}', "OPTION KEY"),
+    ('\u{2326}', "ERASE TO THE RIGHT"),
+    ('\u{2327}', "X IN A RECTANGLE BOX"),
+    ('\u{2328}', "KEYBOARD"),
+    ('\u{2329}', "LEFT-POINTING ANGLE BRACKET"),
+    ('\u{232A}', "RIGHT-POINTING ANGLE BRACKET"),
+    ('\u{232B}', "ERASE TO THE LEFT"),
+    ('\u{232C}', "BENZENE RING"),
+    ('\u{232D}', "CYLINDRICITY"),
+    ('\u{232E}', "ALL AROUND-PROFILE"),
+    ('\u{232F}', "SYMMETRY"),
+    ('\u{2330}', "TOTAL RUNOUT"),
+    ('\u{2331}', "DIMENSION ORIGIN"),
+    ('\u{2332}', "CONICAL TAPER"),
+    ('\u{2333}', "SLOPE"),
+    ('\u{2334}', "COUNTERBORE"),
+    ('\u{2335}', "COUNTERSINK"),
+    ('\u{2336}', "APL FUNCTIONAL SYMBOL I-BEAM"),
+    ('\u{2337}', "APL FUNCTIONAL SYMBOL SQUISH QUAD"),
+    ('\u{2338}', "APL FUNCTIONAL SYMBOL QUAD EQUAL"),
+    ('\u{2339}', "APL FUNCTIONAL SYMBOL QUAD DIVIDE"),
+    ('\u{233A}', "APL FUNCTIONAL SYMBOL QUAD DIAMOND"),
+    ('\u{233B}', "APL FUNCTIONAL SYMBOL QUAD JOT"),
+    ('\u{233C}', "APL FUNCTIONAL SYMBOL QUAD CIRCLE"),
+    ('\u{233D}', "APL FUNCTIONAL SYMBOL CIRCLE STILE"),
+    ('\u{233E}', "APL FUNCTIONAL SYMBOL CIRCLE JOT"),
+    ('\u{233F}', "APL FUNCTIONAL SYMBOL SLASH BAR"),
+    ('\u{2340}', "APL FUNCTIONAL SYMBOL BACKSLASH BAR"),
+    ('\u{2341}', "APL FUNCTIONAL SYMBOL QUAD SLASH"),
+    ('\u{2342}', "APL FUNCTIONAL SYMBOL QUAD BACKSLASH"),
+    ('\u{2343}', "APL FUNCTIONAL SYMBOL QUAD LESS-THAN"),
+    ('\u{2344}', "APL FUNCTIONAL SYMBOL QUAD GREATER-THAN"),
+    ('\u{2345}', "APL FUNCTIONAL SYMBOL LEFTWARDS VANE"),
+    ('\u{2346}', "APL FUNCTIONAL SYMBOL RIGHTWARDS VANE"),
+    ('\u{2347}', "APL FUNCTIONAL SYMBOL QUAD LEFTWARDS ARROW"),
+    ('\u{2348}', "APL FUNCTIONAL SYMBOL QUAD RIGHTWARDS ARROW"),
+    ('\u{2349}', "APL FUNCTIONAL SYMBOL CIRCLE BACKSLASH"),
+    ('\u{234A}', "APL FUNCTIONAL SYMBOL DOWN TACK UNDERBAR"),
+    ('\u{234B}', "APL FUNCTIONAL SYMBOL DELTA STILE"),
+    ('\u{234C}', "APL FUNCTIONAL SYMBOL QUAD DOWN CARET"),
+    ('\u{234D}', "APL FUNCTIONAL SYMBOL QUAD DELTA"),
+    ('\u{234E}', "APL FUNCTIONAL SYMBOL DOWN TACK JOT"),
+    ('\u{234F}', "APL FUNCTIONAL SYMBOL UPWARDS VANE"),
+    ('\u{2350}', "APL FUNCTIONAL SYMBOL QUAD UPWARDS ARROW"),
+    ('\u{2351}', "APL FUNCTIONAL SYMBOL UP TACK OVERBAR"),
+    ('\u{2352}', "APL FUNCTIONAL SYMBOL DEL STILE"),
+    ('\u{2353}', "APL FUNCTIONAL SYMBOL QUAD UP CARET"),
+    ('\u{2354}', "APL FUNCTIONAL SYMBOL QUAD DEL"),
+    ('\u{2355}', "APL FUNCTIONAL SYMBOL UP TACK JOT"),
+    ('\u{2356}', "APL FUNCTIONAL SYMBOL DOWNWARDS VANE"),
+    ('\u{2357}', "APL FUNCTIONAL SYMBOL QUAD DOWNWARDS ARROW"),
+    ('\u{2358}', "APL FUNCTIONAL SYMBOL QUOTE UNDERBAR"),
+    ('\u{2359}', "APL FUNCTIONAL SYMBOL DELTA UNDERBAR"),
+    ('\u{235A}', "APL FUNCTIONAL SYMBOL DIAMOND UNDERBAR"),
+    ('\u{235B}', "APL FUNCTIONAL SYMBOL JOT UNDERBAR"),
+    ('\u{235C}', "APL FUNCTIONAL SYMBOL CIRCLE UNDERBAR"),
+    ('\u{235D}', "APL FUNCTIONAL SYMBOL UP SHOE JOT"),
+    ('\u{235E}', "APL FUNCTIONAL SYMBOL QUOTE QUAD"),
+    ('\u{235F}', "APL FUNCTIONAL SYMBOL CIRCLE STAR"),
+    ('\u{2360}', "APL FUNCTIONAL SYMBOL QUAD COLON"),
+    ('\u{2361}', "APL FUNCTIONAL SYMBOL UP TACK DIAERESIS"),
+    ('\u{2362}', "APL FUNCTIONAL SYMBOL DEL DIAERESIS"),
+    ('\u{2363}', "APL FUNCTIONAL SYMBOL STAR DIAERESIS"),
+    ('\u{2364}', "APL FUNCTIONAL SYMBOL JOT DIAERESIS"),
+    ('\u{2365}', "APL FUNCTIONAL SYMBOL CIRCLE DIAERESIS"),
+    ('\u{2366}', "APL FUNCTIONAL SYMBOL DOWN SHOE STILE"),
+    ('\u{2367}', "APL FUNCTIONAL SYMBOL LEFT SHOE STILE"),
+    ('\u{2368}', "APL FUNCTIONAL SYMBOL TILDE DIAERESIS"),
+    ('\u{2369}', "APL FUNCTIONAL SYMBOL GREATER-THAN DIAERESIS"),
+    ('\u{236A}', "APL FUNCTIONAL SYMBOL COMMA BAR"),
+    ('\u{236B}', "APL FUNCTIONAL SYMBOL DEL TILDE"),
+    ('\u{236C}', "APL FUNCTIONAL SYMBOL ZILDE"),
+    ('\u{236D}', "APL FUNCTIONAL SYMBOL STILE TILDE"),
+    ('\u{236E}', "APL FUNCTIONAL SYMBOL SEMICOLON UNDERBAR"),
+    ('\u{236F}', "APL FUNCTIONAL SYMBOL QUAD NOT EQUAL"),
+    ('\u{2370}', "APL FUNCTIONAL SYMBOL QUAD QUESTION"),
+    ('\u{2371}', "APL FUNCTIONAL SYMBOL DOWN CARET TILDE"),
+    ('\u{2372}', "APL FUNCTIONAL SYMBOL UP CARET TILDE"),
+    ('\u{2373}', "APL FUNCTIONAL SYMBOL IOTA"),
+    ('\u{2374}', "APL FUNCTIONAL SYMBOL RHO"),
+    ('\u{2375}', "APL FUNCTIONAL SYMBOL OMEGA"),
+    ('\u{2376}', "APL FUNCTIONAL SYMBOL ALPHA UNDERBAR"),
+    ('\u{2377}', "APL FUNCTIONAL SYMBOL EPSILON UNDERBAR"),
+    ('\u{2378}', "APL FUNCTIONAL SYMBOL IOTA UNDERBAR"),
+    ('\u{2379}', "APL FUNCTIONAL SYMBOL OMEGA UNDERBAR"),
+    ('\u{237A}', "APL FUNCTIONAL SYMBOL ALPHA"),
+    ('\u{237B}', "NOT CHECK MARK"),
+    ('\u{237C}', "RIGHT ANGLE WITH DOWNWARDS ZIGZAG ARROW"),
+    ('\u{237D}', "SHOULDERED OPEN BOX"),
+    ('\u{237E}', "BELL SYMBOL"),
+    ('\u{237F}', "VERTICAL LINE WITH MIDDLE DOT"),
+    ('\u{2380}', "INSERTION SYMBOL"),
+    ('\u{2381}', "CONTINUOUS UNDERLINE SYMBOL"),
+    ('\u{2382}', "DISCONTINUOUS UNDERLINE SYMBOL"),
+    ('\u{2383}', "EMPHASIS SYMBOL"),
+    ('\u{2384}', "COMPOSITION SYMBOL"),
+    ('\u{2385}', "WHITE SQUARE WITH CENTRE VERTICAL LINE"),
+    ('\u{2386}', "ENTER SYMBOL"),
+    ('\u{2387}', "ALTERNATIVE KEY SYMBOL"),
+    ('\u{2388}', "HELM SYMBOL"),
+    ('\u{2389}', "CIRCLED HORIZONTAL BAR WITH NOTCH"),
+    ('\u{238A}', "CIRCLED TRIANGLE DOWN"),
+    ('\u{238B}', "BROKEN CIRCLE WITH NORTHWEST ARROW"),
+    ('\u{238C}', "UNDO SYMBOL"),
+    ('\u{238D}', "MONOSTABLE SYMBOL"),
+    ('\u{238E}', "HYSTERESIS SYMBOL"),
+    ('\u{238F}', "OPEN-CIRCUIT-OUTPUT H-TYPE SYMBOL"),
+    ('\u{2390}', "OPEN-CIRCUIT-OUTPUT L-TYPE SYMBOL"),
+    ('\u{2391}', "PASSIVE-PULL-DOWN-OUTPUT SYMBOL"),
+    ('\u{2392}', "PASSIVE-PULL-UP-OUTPUT SYMBOL"),
+    ('\u{2393}', "DIRECT CURRENT SYMBOL FORM TWO"),
+    ('\u{2394}', "SOFTWARE-FUNCTION SYMBOL"),
+    ('\u{2395}', "APL FUNCTIONAL SYMBOL QUAD"),
+    ('\u{2396}', "DECIMAL SEPARATOR KEY SYMBOL"),
+    ('\u{2397}', "PREVIOUS PAGE"),
+    ('\u{2398}', "NEXT PAGE"),
+    ('\u{2399}', "PRINT SCREEN SYMBOL"),
+    ('\u{239A}', "CLEAR SCREEN SYMBOL"),
+    ('\u{239B}', "LEFT PARENTHESIS UPPER HOOK"),
+    ('\u{239C}', "LEFT PARENTHESIS EXTENSION"),
+    ('\u{239D}', "LEFT PARENTHESIS LOWER HOOK"),
+    ('\u{239E}', "RIGHT PARENTHESIS UPPER HOOK"),
+    ('\u{239F}', "RIGHT PARENTHESIS EXTENSION"),
+    ('\u{23A0}', "RIGHT PARENTHESIS LOWER HOOK"),
+    ('\u{23A1}', "LEFT SQUARE BRACKET UPPER CORNER"),
+    ('\u{23A2}', "LEFT SQUARE BRACKET EXTENSION"),
+    ('\u{23A3}', "LEFT SQUARE BRACKET LOWER CORNER"),
+    ('\u{23A4}', "RIGHT SQUARE BRACKET UPPER CORNER"),
+    ('\u{23A5}', "RIGHT SQUARE BRACKET EXTENSION"),
+    ('\u{23A6}', "RIGHT SQUARE BRACKET LOWER CORNER"),
+    ('\u{23A7}', "LEFT CURLY BRACKET UPPER HOOK"),
+    ('\u{23A8}', "LEFT CURLY BRACKET MIDDLE PIECE"),
+    ('\u{23A9}', "LEFT CURLY BRACKET LOWER HOOK"),
+    ('\u{23AA}', "CURLY BRACKET EXTENSION"),
+    ('\u{23AB}', "RIGHT CURLY BRACKET UPPER HOOK"),
+    ('\u{23AC}', "RIGHT CURLY BRACKET MIDDLE PIECE"),
+    ('\u{23AD}', "RIGHT CURLY BRACKET LOWER HOOK"),
+    ('\u{23AE}', "INTEGRAL EXTENSION"),
+    ('\u{23AF}', "HORIZONTAL LINE EXTENSION"),
+    (
+        '\u{23B0}',
+        "UPPER LEFT OR LOWER RIGHT CURLY BRACKET SECTION",
+    ),
+    (
+        '\u{23B1}',
+        "UPPER RIGHT OR LOWER LEFT CURLY BRACKET SECTION",
+    ),
+    ('\u{23B2}', "SUMMATION TOP"),
+    ('\u{23B3}', "SUMMATION BOTTOM"),
+    ('\u{23B4}', "TOP SQUARE BRACKET"),
+    ('\u{23B5}', "BOTTOM SQUARE BRACKET"),
+    ('\u{23B6}', "BOTTOM SQUARE BRACKET OVER TOP SQUARE BRACKET"),
+    ('\u{23B7}', "RADICAL SYMBOL BOTTOM"),
+    ('\u{23B8}', "LEFT VERTICAL BOX LINE"),
+    ('\u{23B9}', "RIGHT VERTICAL BOX LINE"),
+    ('\u{23BA}', "HORIZONTAL SCAN LINE-1"),
+    ('\u{23BB}', "HORIZONTAL SCAN LINE-3"),
+    ('\u{23BC}', "HORIZONTAL SCAN LINE-7"),
+    ('\u{23BD}', "HORIZONTAL SCAN LINE-9"),
+    ('\u{23BE}', "DENTISTRY SYMBOL LIGHT VERTICAL AND TOP RIGHT"),
+    (
+        '\u{23BF}',
+        "DENTISTRY SYMBOL LIGHT VERTICAL AND BOTTOM RIGHT",
+    ),
+    ('\u{23C0}', "DENTISTRY SYMBOL LIGHT VERTICAL WITH CIRCLE"),
+    (
+        '\u{23C1}',
+        "DENTISTRY SYMBOL LIGHT DOWN AND HORIZONTAL WITH CIRCLE",
+    ),
+    (
+        '\u{23C2}',
+        "DENTISTRY SYMBOL LIGHT UP AND HORIZONTAL WITH CIRCLE",
+    ),
+    ('\u{23C3}', "DENTISTRY SYMBOL LIGHT VERTICAL WITH TRIANGLE"),
+    (
+        '\u{23C4}',
+        "DENTISTRY SYMBOL LIGHT DOWN AND HORIZONTAL WITH TRIANGLE",
+    ),
+    (
+        '\u{23C5}',
+        "DENTISTRY SYMBOL LIGHT UP AND HORIZONTAL WITH TRIANGLE",
+    ),
+    ('\u{23C6}', "DENTISTRY SYMBOL LIGHT VERTICAL AND WAVE"),
+    (
+        '\u{23C7}',
+        "DENTISTRY SYMBOL LIGHT DOWN AND HORIZONTAL WITH WAVE",
+    ),
+    (
+        '\u{23C8}',
+        "DENTISTRY SYMBOL LIGHT UP AND HORIZONTAL WITH WAVE",
+    ),
+    ('\u{23C9}', "DENTISTRY SYMBOL LIGHT DOWN AND HORIZONTAL"),
+    ('\u{23CA}', "DENTISTRY SYMBOL LIGHT UP AND HORIZONTAL"),
+    ('\u{23CB}', "DENTISTRY SYMBOL LIGHT VERTICAL AND TOP LEFT"),
+    (
+        '\u{23CC}',
+        "DENTISTRY SYMBOL LIGHT VERTICAL AND BOTTOM LEFT",
+    ),
+    ('\u{23CD}', "SQUARE FOOT"),
+    ('\u{23CE}', "RETURN SYMBOL"),
+    ('\u{23CF}', "EJECT SYMBOL"),
+    ('\u{23D0}', "VERTICAL LINE EXTENSION"),
+    ('\u{23D1}', "METRICAL BREVE"),
+    ('\u{23D2}', "METRICAL LONG OVER SHORT"),
+    ('\u{23D3}', "METRICAL SHORT OVER LONG"),
+    ('\u{23D4}', "METRICAL LONG OVER TWO SHORTS"),
+    ('\u{23D5}', "METRICAL TWO SHORTS OVER LONG"),
+    ('\u{23D6}', "METRICAL TWO SHORTS JOINED"),
+    ('\u{23D7}', "METRICAL TRISEME"),
+    ('\u{23D8}', "METRICAL TETRASEME"),
+    ('\u{23D9}', "METRICAL PENTASEME"),
+    ('\u{23DA}', "EARTH GROUND"),
+    ('\u{23DB}', "FUSE"),
+    ('\u{23DC}', "TOP PARENTHESIS"),
+    ('\u{23DD}', "BOTTOM PARENTHESIS"),
+    ('\u{23DE}', "TOP CURLY BRACKET"),
+    ('\u{23DF}', "BOTTOM CURLY BRACKET"),
+    ('\u{23E0}', "TOP TORTOISE SHELL BRACKET"),
+    ('\u{23E1}', "BOTTOM TORTOISE SHELL BRACKET"),
+    ('\u{23E2}', "WHITE TRAPEZIUM"),
+    ('\u{23E3}', "BENZENE RING WITH CIRCLE"),
+    ('\u{23E4}', "STRAIGHTNESS"),
+    ('\u{23E5}', "FLATNESS"),
+    ('\u{23E6}', "AC CURRENT"),
+    ('\u{23E7}', "ELECTRICAL INTERSECTION"),
+    ('\u{23E8}', "DECIMAL EXPONENT SYMBOL"),
+    ('\u{23E9}', "BLACK RIGHT-POINTING DOUBLE TRIANGLE"),
+    ('\u{23EA}', "BLACK LEFT-POINTING DOUBLE TRIANGLE"),
+    ('\u{23EB}', "BLACK UP-POINTING DOUBLE TRIANGLE"),
+    ('\u{23EC}', "BLACK DOWN-POINTING DOUBLE TRIANGLE"),
+    (
+        '\u{23ED}',
+        "BLACK RIGHT-POINTING DOUBLE TRIANGLE WITH VERTICAL BAR",
+    ),
+    (
+        '\u{23EE}',
+        "BLACK LEFT-POINTING DOUBLE TRIANGLE WITH VERTICAL BAR",
+    ),
+    (
+        '\u{23EF}',
+        "BLACK RIGHT-POINTING TRIANGLE WITH DOUBLE VERTICAL BAR",
+    ),
+    ('\u{23F0}', "ALARM CLOCK"),
+    ('\u{23F1}', "STOPWATCH"),
+    ('\u{23F2}', "TIMER CLOCK"),
+    ('\u{23F3}', "HOURGLASS WITH FLOWING SAND"),
+    ('\u{23F4}', "BLACK MEDIUM LEFT-POINTING TRIANGLE"),
+    ('\u{23F5}', "BLACK MEDIUM RIGHT-POINTING TRIANGLE"),
+    ('\u{23F6}', "BLACK MEDIUM UP-POINTING TRIANGLE"),
+    ('\u{23F7}', "BLACK MEDIUM DOWN-POINTING TRIANGLE"),
+    ('\u{23F8}', "DOUBLE VERTICAL BAR"),
+    ('\u{23F9}', "BLACK SQUARE FOR STOP"),
+    ('\u{23FA}', "BLACK CIRCLE FOR RECORD"),
+    ('\u{23FB}', "POWER SYMBOL"),
+    ('\u{23FC}', "POWER ON-OFF SYMBOL"),
+    ('\u{23FD}', "POWER ON SYMBOL"),
+    ('\u{23FE}', "POWER SLEEP SYMBOL"),
+    ('\u{23FF}', "OBSERVER EYE SYMBOL"),
+    ('\u{25A0}', "BLACK SQUARE"),
+    ('\u{25A1}', "WHITE SQUARE"),
+    ('\u{25A2}', "WHITE SQUARE WITH ROUNDED CORNERS"),
+    ('\u{25A3}', "WHITE SQUARE CONTAINING BLACK SMALL SQUARE"),
+    ('\u{25A4}', "SQUARE WITH HORIZONTAL FILL"),
+    ('\u{25A5}', "SQUARE WITH VERTICAL FILL"),
+    ('\u{25A6}', "SQUARE WITH ORTHOGONAL CROSSHATCH FILL"),
+    ('\u{25A7}', "SQUARE WITH UPPER LEFT TO LOWER RIGHT FILL"),
+    ('\u{25A8}', "SQUARE WITH UPPER RIGHT TO LOWER LEFT FILL"),
+    ('\u{25A9}', "SQUARE WITH DIAGONAL CROSSHATCH FILL"),
+    ('\u{25AA}', "BLACK SMALL SQUARE"),
+    ('\u{25AB}', "WHITE SMALL SQUARE"),
+    ('\u{25AC}', "BLACK RECTANGLE"),
+    ('\u{25AD}', "WHITE RECTANGLE"),
+    ('\u{25AE}', "BLACK VERTICAL RECTANGLE"),
+    ('\u{25AF}', "WHITE VERTICAL RECTANGLE"),
+    ('\u{25B0}', "BLACK PARALLELOGRAM"),
+    ('\u{25B1}', "WHITE PARALLELOGRAM"),
+    ('\u{25B2}', "BLACK UP-POINTING TRIANGLE"),
+    ('\u{25B3}', "WHITE UP-POINTING TRIANGLE"),
+    ('\u{25B4}', "BLACK UP-POINTING SMALL TRIANGLE"),
+    ('\u{25B5}', "WHITE UP-POINTING SMALL TRIANGLE"),
+    ('\u{25B6}', "BLACK RIGHT-POINTING TRIANGLE"),
+    ('\u{25B7}', "WHITE RIGHT-POINTING TRIANGLE"),
+    ('\u{25B8}', "BLACK RIGHT-POINTING SMALL TRIANGLE"),
+    ('\u{25B9}', "WHITE RIGHT-POINTING SMALL TRIANGLE"),
+    ('\u{25BA}', "BLACK RIGHT-POINTING POINTER"),
+    ('\u{25BB}', "WHITE RIGHT-POINTING POINTER"),
+    ('\u{25BC}', "BLACK DOWN-POINTING TRIANGLE"),
+    ('\u{25BD}', "WHITE DOWN-POINTING TRIANGLE"),
+    ('\u{25BE}', "BLACK DOWN-POINTING SMALL TRIANGLE"),
+    ('\u{25BF}', "WHITE DOWN-POINTING SMALL TRIANGLE"),
+    ('\u{25C0}', "BLACK LEFT-POINTING TRIANGLE"),
+    ('\u{25C1}', "WHITE LEFT-POINTING TRIANGLE"),
+    ('\u{25C2}', "BLACK LEFT-POINTING SMALL TRIANGLE"),
+    ('\u{25C3}', "WHITE LEFT-POINTING SMALL TRIANGLE"),
+    ('\u{25C4}', "BLACK LEFT-POINTING POINTER"),
+    ('\u{25C5}', "WHITE LEFT-POINTING POINTER"),
+    ('\u{25C6}', "BLACK DIAMOND"),
+    ('\u{25C7}', "WHITE DIAMOND"),
+    ('\u{25C8}', "WHITE DIAMOND CONTAINING BLACK SMALL DIAMOND"),
+    ('\u{25C9}', "FISHEYE"),
+    ('\u{25CA}', "LOZENGE"),
+    ('\u{25CB}', "WHITE CIRCLE"),
+    ('\u{25CC}', "DOTTED CIRCLE"),
+    ('\u{25CD}', "CIRCLE WITH VERTICAL FILL"),
+    ('\u{25CE}', "BULLSEYE"),
+    ('\u{25CF}', "BLACK CIRCLE"),
+    ('\u{25D0}', "CIRCLE WITH LEFT HALF BLACK"),
+    ('\u{25D1}', "CIRCLE WITH RIGHT HALF BLACK"),
+    ('\u{25D2}', "CIRCLE WITH LOWER HALF BLACK"),
+    ('\u{25D3}', "CIRCLE WITH UPPER HALF BLACK"),
+    ('\u{25D4}', "CIRCLE WITH UPPER RIGHT QUADRANT BLACK"),
+    ('\u{25D5}', "CIRCLE WITH ALL BUT UPPER LEFT QUADRANT BLACK"),
+    ('\u{25D6}', "LEFT HALF BLACK CIRCLE"),
+    ('\u{25D7}', "RIGHT HALF BLACK CIRCLE"),
+    ('\u{25D8}', "INVERSE BULLET"),
+    ('\u{25D9}', "INVERSE WHITE CIRCLE"),
+    ('\u{25DA}', "UPPER HALF INVERSE WHITE CIRCLE"),
+    ('\u{25DB}', "LOWER HALF INVERSE WHITE CIRCLE"),
+    ('\u{25DC}', "UPPER LEFT QUADRANT CIRCULAR ARC"),
+    ('\u{25DD}', "UPPER RIGHT QUADRANT CIRCULAR ARC"),
+    ('\u{25DE}', "LOWER RIGHT QUADRANT CIRCULAR ARC"),
+    ('\u{25DF}', "LOWER LEFT QUADRANT CIRCULAR ARC"),
+    ('\u{25E0}', "UPPER HALF CIRCLE"),
+    ('\u{25E1}', "LOWER HALF CIRCLE"),
+    ('\u{25E2}', "BLACK LOWER RIGHT TRIANGLE"),
+    ('\u{25E3}', "BLACK LOWER LEFT TRIANGLE"),
+    ('\u{25E4}', "BLACK UPPER LEFT TRIANGLE"),
+    ('\u{25E5}', "BLACK UPPER RIGHT TRIANGLE"),
+    ('\u{25E6}', "WHITE BULLET"),
+    ('\u{25E7}', "SQUARE WITH LEFT HALF BLACK"),
+    ('\u{25E8}', "SQUARE WITH RIGHT HALF BLACK"),
+    ('\u{25E9}', "SQUARE WITH UPPER LEFT DIAGONAL HALF BLACK"),
+    ('\u{25EA}', "SQUARE WITH LOWER RIGHT DIAGONAL HALF BLACK"),
+    ('\u{25EB}', "WHITE SQUARE WITH VERTICAL BISECTING LINE"),
+    ('\u{25EC}', "WHITE UP-POINTING TRIANGLE WITH DOT"),
+    ('\u{25ED}', "UP-POINTING TRIANGLE WITH LEFT HALF BLACK"),
+    ('\u{25EE}', "UP-POINTING TRIANGLE WITH RIGHT HALF BLACK"),
+    ('\u{25EF}', "LARGE CIRCLE"),
+    ('\u{25F0}', "WHITE SQUARE WITH UPPER LEFT QUADRANT"),
+    ('\u{25F1}', "WHITE SQUARE WITH LOWER LEFT QUADRANT"),
+    ('\u{25F2}', "WHITE SQUARE WITH LOWER RIGHT QUADRANT"),
+    ('\u{25F3}', "WHITE SQUARE WITH UPPER RIGHT QUADRANT"),
+    ('\u{25F4}', "WHITE CIRCLE WITH UPPER LEFT QUADRANT"),
+    ('\u{25F5}', "WHITE CIRCLE WITH LOWER LEFT QUADRANT"),
+    ('\u{25F6}', "WHITE CIRCLE WITH LOWER RIGHT QUADRANT"),
+    ('\u{25F7}', "WHITE CIRCLE WITH UPPER RIGHT QUADRANT"),
+    ('\u{25F8}', "UPPER LEFT TRIANGLE"),
+    ('\u{25F9}', "UPPER RIGHT TRIANGLE"),
+    ('\u{25FA}', "LOWER LEFT TRIANGLE"),
+    ('\u{25FB}', "WHITE MEDIUM SQUARE"),
+    ('\u{25FC}', "BLACK MEDIUM SQUARE"),
+    ('\u{25FD}', "WHITE MEDIUM SMALL SQUARE"),
+    ('\u{25FE}', "BLACK MEDIUM SMALL SQUARE"),
+    ('\u{25FF}', "LOWER RIGHT TRIANGLE"),
+    ('\u{2605}', "BLACK STAR"),
+    ('\u{2606}', "WHITE STAR"),
+    ('\u{2660}', "BLACK SPADE SUIT"),
+    ('\u{2661}', "WHITE HEART SUIT"),
+    ('\u{2662}', "WHITE DIAMOND SUIT"),
+    ('\u{2663}', "BLACK CLUB SUIT"),
+    ('\u{2664}', "WHITE SPADE SUIT"),
+    ('\u{2665}', "BLACK HEART SUIT"),
+    ('\u{2666}', "BLACK DIAMOND SUIT"),
+    ('\u{2667}', "WHITE CLUB SUIT"),
+    ('\u{266D}', "MUSIC FLAT SIGN"),
+    ('\u{266E}', "MUSIC NATURAL SIGN"),
+    ('\u{266F}', "MUSIC SHARP SIGN"),
+    ('\u{27C0}', "THREE DIMENSIONAL ANGLE"),
+    ('\u{27C1}', "WHITE TRIANGLE CONTAINING SMALL WHITE TRIANGLE"),
+    ('\u{27C2}', "PERPENDICULAR"),
+    ('\u{27C3}', "OPEN SUBSET"),
+    ('\u{27C4}', "OPEN SUPERSET"),
+    ('\u{27C5}', "LEFT S-SHAPED BAG DELIMITER"),
+    ('\u{27C6}', "RIGHT S-SHAPED BAG DELIMITER"),
+    ('\u{27C7}', "OR WITH DOT INSIDE"),
+    ('\u{27C8}', "REVERSE SOLIDUS PRECEDING SUBSET"),
+    ('\u{27C9}', "SUPERSET PRECEDING SOLIDUS"),
+    ('\u{27CA}', "VERTICAL BAR WITH HORIZONTAL STROKE"),
+    ('\u{27CB}', "MATHEMATICAL RISING DIAGONAL"),
+    ('\u{27CC}', "LONG DIVISION"),
+    ('\u{27CD}', "MATHEMATICAL FALLING DIAGONAL"),
+    ('\u{27CE}', "SQUARED LOGICAL AND"),
+    ('\u{27CF}', "SQUARED LOGICAL OR"),
+    ('\u{27D0}', "WHITE DIAMOND WITH CENTRED DOT"),
+    ('\u{27D1}', "AND WITH DOT"),
+    ('\u{27D2}', "ELEMENT OF OPENING UPWARDS"),
+    ('\u{27D3}', "LOWER RIGHT CORNER WITH DOT"),
+    ('\u{27D4}', "UPPER LEFT CORNER WITH DOT"),
+    ('\u{27D5}', "LEFT OUTER JOIN"),
+    ('\u{27D6}', "RIGHT OUTER JOIN"),
+    ('\u{27D7}', "FULL OUTER JOIN"),
+    ('\u{27D8}', "LARGE UP TACK"),
+    ('\u{27D9}', "LARGE DOWN TACK"),
+    ('\u{27DA}', "LEFT AND RIGHT DOUBLE TURNSTILE"),
+    ('\u{27DB}', "LEFT AND RIGHT TACK"),
+    ('\u{27DC}', "LEFT MULTIMAP"),
+    ('\u{27DD}', "LONG RIGHT TACK"),
+    ('\u{27DE}', "LONG LEFT TACK"),
+    ('\u{27DF}', "UP TACK WITH CIRCLE ABOVE"),
+    ('\u{27E0}', "LOZENGE DIVIDED BY HORIZONTAL RULE"),
+    ('\u{27E1}', "WHITE CONCAVE-SIDED DIAMOND"),
+    (
+        '\u{27E2}',
+        "WHITE CONCAVE-SIDED DIAMOND WITH LEFTWARDS TICK",
+    ),
+    (
+        '\u{27E3}',
+        "WHITE CONCAVE-SIDED DIAMOND WITH RIGHTWARDS TICK",
+    ),
+    ('\u{27E4}', "WHITE SQUARE WITH LEFTWARDS TICK"),
+    ('\u{27E5}', "WHITE SQUARE WITH RIGHTWARDS TICK"),
+    ('\u{27E6}', "MATHEMATICAL LEFT WHITE SQUARE BRACKET"),
+    ('\u{27E7}', "MATHEMATICAL RIGHT WHITE SQUARE BRACKET"),
+    ('\u{27E8}', "MATHEMATICAL LEFT ANGLE BRACKET"),
+    ('\u{27E9}', "MATHEMATICAL RIGHT ANGLE BRACKET"),
+    ('\u{27EA}', "MATHEMATICAL LEFT DOUBLE ANGLE BRACKET"),
+    ('\u{27EB}', "MATHEMATICAL RIGHT DOUBLE ANGLE BRACKET"),
+    ('\u{27EC}', "MATHEMATICAL LEFT WHITE TORTOISE SHELL BRACKET"),
+    (
+        '\u{27ED}',
+        "MATHEMATICAL RIGHT WHITE TORTOISE SHELL BRACKET",
+    ),
+    ('\u{27EE}', "MATHEMATICAL LEFT FLATTENED PARENTHESIS"),
+    ('\u{27EF}', "MATHEMATICAL RIGHT FLATTENED PARENTHESIS"),
+    ('\u{27F0}', "UPWARDS QUADRUPLE ARROW"),
+    ('\u{27F1}', "DOWNWARDS QUADRUPLE ARROW"),
+    ('\u{27F2}', "ANTICLOCKWISE GAPPED CIRCLE ARROW"),
+    ('\u{27F3}', "CLOCKWISE GAPPED CIRCLE ARROW"),
+    ('\u{27F4}', "RIGHT ARROW WITH CIRCLED PLUS"),
+    ('\u{27F5}', "LONG LEFTWARDS ARROW"),
+    ('\u{27F6}', "LONG RIGHTWARDS ARROW"),
+    ('\u{27F7}', "LONG LEFT RIGHT ARROW"),
+    ('\u{27F8}', "LONG LEFTWARDS DOUBLE ARROW"),
+    ('\u{27F9}', "LONG RIGHTWARDS DOUBLE ARROW"),
+    ('\u{27FA}', "LONG LEFT RIGHT DOUBLE ARROW"),
+    ('\u{27FB}', "LONG LEFTWARDS ARROW FROM BAR"),
+    ('\u{27FC}', "LONG RIGHTWARDS ARROW FROM BAR"),
+    ('\u{27FD}', "LONG LEFTWARDS DOUBLE ARROW FROM BAR"),
+    ('\u{27FE}', "LONG RIGHTWARDS DOUBLE ARROW FROM BAR"),
+    ('\u{27FF}', "LONG RIGHTWARDS SQUIGGLE ARROW"),
+    (
+        '\u{2900}',
+        "RIGHTWARDS TWO-HEADED ARROW WITH VERTICAL STROKE",
+    ),
+    (
+        '\u{2901}',
+        "RIGHTWARDS TWO-HEADED ARROW WITH DOUBLE VERTICAL STROKE",
+    ),
+    ('\u{2902}', "LEFTWARDS DOUBLE ARROW WITH VERTICAL STROKE"),
+    ('\u{2903}', "RIGHTWARDS DOUBLE ARROW WITH VERTICAL STROKE"),
+    ('\u{2904}', "LEFT RIGHT DOUBLE ARROW WITH VERTICAL STROKE"),
+    ('\u{2905}', "RIGHTWARDS TWO-HEADED ARROW FROM BAR"),
+    ('\u{2906}', "LEFTWARDS DOUBLE ARROW FROM BAR"),
+    ('\u{2907}', "RIGHTWARDS DOUBLE ARROW FROM BAR"),
+    ('\u{2908}', "DOWNWARDS ARROW WITH HORIZONTAL STROKE"),
+    ('\u{2909}', "UPWARDS ARROW WITH HORIZONTAL STROKE"),
+    ('\u{290A}', "UPWARDS TRIPLE ARROW"),
+    ('\u{290B}', "DOWNWARDS TRIPLE ARROW"),
+    ('\u{290C}', "LEFTWARDS DOUBLE DASH ARROW"),
+    ('\u{290D}', "RIGHTWARDS DOUBLE DASH ARROW"),
+    ('\u{290E}', "LEFTWARDS TRIPLE DASH ARROW"),
+    ('\u{290F}', "RIGHTWARDS TRIPLE DASH ARROW"),
+    ('\u{2910}', "RIGHTWARDS TWO-HEADED TRIPLE DASH ARROW"),
+    ('\u{2911}', "RIGHTWARDS ARROW WITH DOTTED STEM"),
+    ('\u{2912}', "UPWARDS ARROW TO BAR"),
+    ('\u{2913}', "DOWNWARDS ARROW TO BAR"),
+    (
+        '\u{2914}',
+        "RIGHTWARDS ARROW WITH TAIL WITH VERTICAL STROKE",
+    ),
+    (
+        '\u{2915}',
+        "RIGHTWARDS ARROW WITH TAIL WITH DOUBLE VERTICAL STROKE",
+    ),
+    ('\u{2916}', "RIGHTWARDS TWO-HEADED ARROW WITH TAIL"),
+    (
+        '\u{2917}',
+        "RIGHTWARDS TWO-HEADED ARROW WITH TAIL WITH VERTICAL STROKE",
+    ),
+    (
+        '\u{2918}',
+        "RIGHTWARDS TWO-HEADED ARROW WITH TAIL WITH DOUBLE VERTICAL STROKE",
+    ),
+    ('\u{2919}', "LEFTWARDS ARROW-TAIL"),
+    ('\u{291A}', "RIGHTWARDS ARROW-TAIL"),
+    ('\u{291B}', "LEFTWARDS DOUBLE ARROW-TAIL"),
+    ('\u{291C}', "RIGHTWARDS DOUBLE ARROW-TAIL"),
+    ('\u{291D}', "LEFTWARDS ARROW TO BLACK DIAMOND"),
+    ('\u{291E}', "RIGHTWARDS ARROW TO BLACK DIAMOND"),
+    ('\u{291F}', "LEFTWARDS ARROW FROM BAR TO BLACK DIAMOND"),
+    ('\u{2920}', "RIGHTWARDS ARROW FROM BAR TO BLACK DIAMOND"),
+    ('\u{2921}', "NORTH WEST AND SOUTH EAST ARROW"),
+    ('\u{2922}', "NORTH EAST AND SOUTH WEST ARROW"),
+    ('\u{2923}', "NORTH WEST ARROW WITH HOOK"),
+    ('\u{2924}', "NORTH EAST ARROW WITH HOOK"),
+    ('\u{2925}', "SOUTH EAST ARROW WITH HOOK"),
+    ('\u{2926}', "SOUTH WEST ARROW WITH HOOK"),
+    ('\u{2927}', "NORTH WEST ARROW AND NORTH EAST ARROW"),
+    ('\u{2928}', "NORTH EAST ARROW AND SOUTH EAST ARROW"),
+    ('\u{2929}', "SOUTH EAST ARROW AND SOUTH WEST ARROW"),
+    ('\u{292A}', "SOUTH WEST ARROW AND NORTH WEST ARROW"),
+    ('\u{292B}', "RISING DIAGONAL CROSSING FALLING DIAGONAL"),
+    ('\u{292C}', "FALLING DIAGONAL CROSSING RISING DIAGONAL"),
+    ('\u{292D}', "SOUTH EAST ARROW CROSSING NORTH EAST ARROW"),
+    ('\u{292E}', "NORTH EAST ARROW CROSSING SOUTH EAST ARROW"),
+    ('\u{292F}', "FALLING DIAGONAL CROSSING NORTH EAST ARROW"),
+    ('\u{2930}', "RISING DIAGONAL CROSSING SOUTH EAST ARROW"),
+    ('\u{2931}', "NORTH EAST ARROW CROSSING NORTH WEST ARROW"),
+    ('\u{2932}', "NORTH WEST ARROW CROSSING NORTH EAST ARROW"),
+    ('\u{2933}', "WAVE ARROW POINTING DIRECTLY RIGHT"),
+    ('\u{2934}', "ARROW POINTING RIGHTWARDS THEN CURVING UPWARDS"),
+    (
+        '\u{2935}',
+        "ARROW POINTING RIGHTWARDS THEN CURVING DOWNWARDS",
+    ),
+    (
+        '\u{2936}',
+        "ARROW POINTING DOWNWARDS THEN CURVING LEFTWARDS",
+    ),
+    (
+        '\u{2937}',
+        "ARROW POINTING DOWNWARDS THEN CURVING RIGHTWARDS",
+    ),
+    ('\u{2938}', "RIGHT-SIDE ARC CLOCKWISE ARROW"),
+    ('\u{2939}', "LEFT-SIDE ARC ANTICLOCKWISE ARROW"),
+    ('\u{293A}', "TOP ARC ANTICLOCKWISE ARROW"),
+    ('\u{293B}', "BOTTOM ARC ANTICLOCKWISE ARROW"),
+    ('\u{293C}', "TOP ARC CLOCKWISE ARROW WITH MINUS"),
+    ('\u{293D}', "TOP ARC ANTICLOCKWISE ARROW WITH PLUS"),
+    ('\u{293E}', "LOWER RIGHT SEMICIRCULAR CLOCKWISE ARROW"),
+    ('\u{293F}', "LOWER LEFT SEMICIRCULAR ANTICLOCKWISE ARROW"),
+    ('\u{2940}', "ANTICLOCKWISE CLOSED CIRCLE ARROW"),
+    ('\u{2941}', "CLOCKWISE CLOSED CIRCLE ARROW"),
+    ('\u{2942}', "RIGHTWARDS ARROW ABOVE SHORT LEFTWARDS ARROW"),
+    ('\u{2943}', "LEFTWARDS ARROW ABOVE SHORT RIGHTWARDS ARROW"),
+    ('\u{2944}', "SHORT RIGHTWARDS ARROW ABOVE LEFTWARDS ARROW"),
+    ('\u{2945}', "RIGHTWARDS ARROW WITH PLUS BELOW"),
+    ('\u{2946}', "LEFTWARDS ARROW WITH PLUS BELOW"),
+    ('\u{2947}', "RIGHTWARDS ARROW THROUGH X"),
+    ('\u{2948}', "LEFT RIGHT ARROW THROUGH SMALL CIRCLE"),
+    ('\u{2949}', "UPWARDS TWO-HEADED ARROW FROM SMALL CIRCLE"),
+    ('\u{294A}', "LEFT BARB UP RIGHT BARB DOWN HARPOON"),
+    ('\u{294B}', "LEFT BARB DOWN RIGHT BARB UP HARPOON"),
+    ('\u{294C}', "UP BARB RIGHT DOWN BARB LEFT HARPOON"),
+    ('\u{294D}', "UP BARB LEFT DOWN BARB RIGHT HARPOON"),
+    ('\u{294E}', "LEFT BARB UP RIGHT BARB UP HARPOON"),
+    ('\u{294F}', "UP BARB RIGHT DOWN BARB RIGHT HARPOON"),
+    ('\u{2950}', "LEFT BARB DOWN RIGHT BARB DOWN HARPOON"),
+    ('\u{2951}', "UP BARB LEFT DOWN BARB LEFT HARPOON"),
+    ('\u{2952}', "LEFTWARDS HARPOON WITH BARB UP TO BAR"),
+    ('\u{2953}', "RIGHTWARDS HARPOON WITH BARB UP TO BAR"),
+    ('\u{2954}', "UPWARDS HARPOON WITH BARB RIGHT TO BAR"),
+    ('\u{2955}', "DOWNWARDS HARPOON WITH BARB RIGHT TO BAR"),
+    ('\u{2956}', "LEFTWARDS HARPOON WITH BARB DOWN TO BAR"),
+    ('\u{2957}', "RIGHTWARDS HARPOON WITH BARB DOWN TO BAR"),
+    ('\u{2958}', "UPWARDS HARPOON WITH BARB LEFT TO BAR"),
+    ('\u{2959}', "DOWNWARDS HARPOON WITH BARB LEFT TO BAR"),
+    ('\u{295A}', "LEFTWARDS HARPOON WITH BARB UP FROM BAR"),
+    ('\u{295B}', "RIGHTWARDS HARPOON WITH BARB UP FROM BAR"),
+    ('\u{295C}', "UPWARDS HARPOON WITH BARB RIGHT FROM BAR"),
+    ('\u{295D}', "DOWNWARDS HARPOON WITH BARB RIGHT FROM BAR"),
+    ('\u{295E}', "LEFTWARDS HARPOON WITH BARB DOWN FROM BAR"),
+    ('\u{295F}', "RIGHTWARDS HARPOON WITH BARB DOWN FROM BAR"),
+    ('\u{2960}', "UPWARDS HARPOON WITH BARB LEFT FROM BAR"),
+    ('\u{2961}', "DOWNWARDS HARPOON WITH BARB LEFT FROM BAR"),
+    (
+        '\u{2962}',
+        "LEFTWARDS HARPOON WITH BARB UP ABOVE LEFTWARDS HARPOON WITH BARB DOWN",
+    ),
+    (
+        '\u{2963}',
+        "UPWARDS HARPOON WITH BARB LEFT BESIDE UPWARDS HARPOON WITH BARB RIGHT",
+    ),
+    (
+        '\u{2964}',
+        "RIGHTWARDS HARPOON WITH BARB UP ABOVE RIGHTWARDS HARPOON WITH BARB DOWN",
+    ),
+    (
+        '\u{2965}',
+        "DOWNWARDS HARPOON WITH BARB LEFT BESIDE DOWNWARDS HARPOON WITH BARB RIGHT",
+    ),
+    (
+        '\u{2966}',
+        "LEFTWARDS HARPOON WITH BARB UP ABOVE RIGHTWARDS HARPOON WITH BARB UP",
+    ),
+    (
+        '\u{2967}',
+        "LEFTWARDS HARPOON WITH BARB DOWN ABOVE RIGHTWARDS HARPOON WITH BARB DOWN",
+    ),
+    (
+        '\u{2968}',
+        "RIGHTWARDS HARPOON WITH BARB UP ABOVE LEFTWARDS HARPOON WITH BARB UP",
+    ),
+    (
+        '\u{2969}',
+        "RIGHTWARDS HARPOON WITH BARB DOWN ABOVE LEFTWARDS HARPOON WITH BARB DOWN",
+    ),
+    ('\u{296A}', "LEFTWARDS HARPOON WITH BARB UP ABOVE LONG DASH"),
+    (
+        '\u{296B}',
+        "LEFTWARDS HARPOON WITH BARB DOWN BELOW LONG DASH",
+    ),
+    (
+        '\u{296C}',
+        "RIGHTWARDS HARPOON WITH BARB UP ABOVE LONG DASH",
+    ),
+    (
+        '\u{296D}',
+        "RIGHTWARDS HARPOON WITH BARB DOWN BELOW LONG DASH",
+    ),
+    (
+        '\u{296E}',
+        "UPWARDS HARPOON WITH BARB LEFT BESIDE DOWNWARDS HARPOON WITH BARB RIGHT",
+    ),
+    (
+        '\u{296F}',
+        "DOWNWARDS HARPOON WITH BARB LEFT BESIDE UPWARDS HARPOON WITH BARB RIGHT",
+    ),
+    ('\u{2970}', "RIGHT DOUBLE ARROW WITH ROUNDED HEAD"),
+    ('\u{2971}', "EQUALS SIGN ABOVE RIGHTWARDS ARROW"),
+    ('\u{2972}', "TILDE OPERATOR ABOVE RIGHTWARDS ARROW"),
+    ('\u{2973}', "LEFTWARDS ARROW ABOVE TILDE OPERATOR"),
+    ('\u{2974}', "RIGHTWARDS ARROW ABOVE TILDE OPERATOR"),
+    ('\u{2975}', "RIGHTWARDS ARROW ABOVE ALMOST EQUAL TO"),
+    ('\u{2976}', "LESS-THAN ABOVE LEFTWARDS ARROW"),
+    ('\u{2977}', "LEFTWARDS ARROW THROUGH LESS-THAN"),
+    ('\u{2978}', "GREATER-THAN ABOVE RIGHTWARDS ARROW"),
+    ('\u{2979}', "SUBSET ABOVE RIGHTWARDS ARROW"),
+    ('\u{297A}', "LEFTWARDS ARROW THROUGH SUBSET"),
+    ('\u{297B}', "SUPERSET ABOVE LEFTWARDS ARROW"),
+    ('\u{297C}', "LEFT FISH TAIL"),
+    ('\u{297D}', "RIGHT FISH TAIL"),
+    ('\u{297E}', "UP FISH TAIL"),
+    ('\u{297F}', "DOWN FISH TAIL"),
+    ('\u{2980}', "TRIPLE VERTICAL BAR DELIMITER"),
+    ('\u{2981}', "Z NOTATION SPOT"),
+    ('\u{2982}', "Z NOTATION TYPE COLON"),
+    ('\u{2983}', "LEFT WHITE CURLY BRACKET"),
+    ('\u{2984}', "RIGHT WHITE CURLY BRACKET"),
+    ('\u{2985}', "LEFT WHITE PARENTHESIS"),
+    ('\u{2986}', "RIGHT WHITE PARENTHESIS"),
+    ('\u{2987}', "Z NOTATION LEFT IMAGE BRACKET"),
+    ('\u{2988}', "Z NOTATION RIGHT IMAGE BRACKET"),
+    ('\u{2989}', "Z NOTATION LEFT BINDING BRACKET"),
+    ('\u{298A}', "Z NOTATION RIGHT BINDING BRACKET"),
+    ('\u{298B}', "LEFT SQUARE BRACKET WITH UNDERBAR"),
+    ('\u{298C}', "RIGHT SQUARE BRACKET WITH UNDERBAR"),
+    ('\u{298D}', "LEFT SQUARE BRACKET WITH TICK IN TOP CORNER"),
+    (
+        '\u{298E}',
+        "RIGHT SQUARE BRACKET WITH TICK IN BOTTOM CORNER",
+    ),
+    ('\u{298F}', "LEFT SQUARE BRACKET WITH TICK IN BOTTOM CORNER"),
+    ('\u{2990}', "RIGHT SQUARE BRACKET WITH TICK IN TOP CORNER"),
+    ('\u{2991}', "LEFT ANGLE BRACKET WITH DOT"),
+    ('\u{2992}', "RIGHT ANGLE BRACKET WITH DOT"),
+    ('\u{2993}', "LEFT ARC LESS-THAN BRACKET"),
+    ('\u{2994}', "RIGHT ARC GREATER-THAN BRACKET"),
+    ('\u{2995}', "DOUBLE LEFT ARC GREATER-THAN BRACKET"),
+    ('\u{2996}', "DOUBLE RIGHT ARC LESS-THAN BRACKET"),
+    ('\u{2997}', "LEFT BLACK TORTOISE SHELL BRACKET"),
+    ('\u{2998}', "RIGHT BLACK TORTOISE SHELL BRACKET"),
+    ('\u{2999}', "DOTTED FENCE"),
+    ('\u{299A}', "VERTICAL ZIGZAG LINE"),
+    ('\u{299B}', "MEASURED ANGLE OPENING LEFT"),
+    ('\u{299C}', "RIGHT ANGLE VARIANT WITH SQUARE"),
+    ('\u{299D}', "MEASURED RIGHT ANGLE WITH DOT"),
+    ('\u{299E}', "ANGLE WITH S INSIDE"),
+    ('\u{299F}', "ACUTE ANGLE"),
+    ('\u{29A0}', "SPHERICAL ANGLE OPENING LEFT"),
+    ('\u{29A1}', "SPHERICAL ANGLE OPENING UP"),
+    ('\u{29A2}', "TURNED ANGLE"),
+    ('\u{29A3}', "REVERSED ANGLE"),
+    ('\u{29A4}', "ANGLE WITH UNDERBAR"),
+    ('\u{29A5}', "REVERSED ANGLE WITH UNDERBAR"),
+    ('\u{29A6}', "OBLIQUE ANGLE OPENING UP"),
+    ('\u{29A7}', "OBLIQUE ANGLE OPENING DOWN"),
+    (
+        '\u{29A8}',
+        "MEASURED ANGLE WITH OPEN ARM ENDING IN ARROW POINTING UP AND RIGHT",
+    ),
+    (
+        '\u{29A9}',
+        "MEASURED ANGLE WITH OPEN ARM ENDING IN ARROW POINTING UP AND LEFT",
+    ),
+    (
+        '\u{29AA}',
+        "MEASURED ANGLE WITH OPEN ARM ENDING IN ARROW POINTING DOWN AND RIGHT",
+    ),
+    (
+        '\u{29AB}',
+        "MEASURED ANGLE WITH OPEN ARM ENDING IN ARROW POINTING DOWN AND LEFT",
+    ),
+    (
+        '\u{29AC}',
+        "MEASURED ANGLE WITH OPEN ARM ENDING IN ARROW POINTING RIGHT AND UP",
+    ),
+    (
+        '\u{29AD}',
+        "MEASURED ANGLE WITH OPEN ARM ENDING IN ARROW POINTING LEFT AND UP",
+    ),
+    (
+        '\u{29AE}',
+        "MEASURED ANGLE WITH OPEN ARM ENDING IN ARROW POINTING RIGHT AND DOWN",
+    ),
+    (
+        '\u{29AF}',
+        "MEASURED ANGLE WITH OPEN ARM ENDING IN ARROW POINTING LEFT AND DOWN",
+    ),
+    ('\u{29B0}', "REVERSED EMPTY SET"),
+    ('\u{29B1}', "EMPTY SET WITH OVERBAR"),
+    ('\u{29B2}', "EMPTY SET WITH SMALL CIRCLE ABOVE"),
+    ('\u{29B3}', "EMPTY SET WITH RIGHT ARROW ABOVE"),
+    ('\u{29B4}', "EMPTY SET WITH LEFT ARROW ABOVE"),
+    ('\u{29B5}', "CIRCLE WITH HORIZONTAL BAR"),
+    ('\u{29B6}', "CIRCLED VERTICAL BAR"),
+    ('\u{29B7}', "CIRCLED PARALLEL"),
+    ('\u{29B8}', "CIRCLED REVERSE SOLIDUS"),
+    ('\u{29B9}', "CIRCLED PERPENDICULAR"),
+    (
+        '\u{29BA}',
+        "CIRCLE DIVIDED BY HORIZONTAL BAR AND TOP HALF DIVIDED BY VERTICAL BAR",
+    ),
+    ('\u{29BB}', "CIRCLE WITH SUPERIMPOSED X"),
+    ('\u{29BC}', "CIRCLED ANTICLOCKWISE-ROTATED DIVISION SIGN"),
+    ('\u{29BD}', "UP ARROW THROUGH CIRCLE"),
+    ('\u{29BE}', "CIRCLED WHITE BULLET"),
+    ('\u{29BF}', "CIRCLED BULLET"),
+    ('\u{29C0}', "CIRCLED LESS-THAN"),
+    ('\u{29C1}', "CIRCLED GREATER-THAN"),
+    ('\u{29C2}', "CIRCLE WITH SMALL CIRCLE TO THE RIGHT"),
+    (
+        '\u{29C3}',
+        "CIRCLE WITH TWO HORIZONTAL STROKES TO THE RIGHT",
+    ),
+    ('\u{29C4}', "SQUARED RISING DIAGONAL SLASH"),
+    ('\u{29C5}', "SQUARED FALLING DIAGONAL SLASH"),
+    ('\u{29C6}', "SQUARED ASTERISK"),
+    ('\u{29C7}', "SQUARED SMALL CIRCLE"),
+    ('\u{29C8}', "SQUARED SQUARE"),
+    ('\u{29C9}', "TWO JOINED SQUARES"),
+    ('\u{29CA}', "TRIANGLE WITH DOT ABOVE"),
+    ('\u{29CB}', "TRIANGLE WITH UNDERBAR"),
+    ('\u{29CC}', "S IN TRIANGLE"),
+    ('\u{29CD}', "TRIANGLE WITH SERIFS AT BOTTOM"),
+    ('\u{29CE}', "RIGHT TRIANGLE ABOVE LEFT TRIANGLE"),
+    ('\u{29CF}', "LEFT TRIANGLE BESIDE VERTICAL BAR"),
+    ('\u{29D0}', "VERTICAL BAR BESIDE RIGHT TRIANGLE"),
+    ('\u{29D1}', "BOWTIE WITH LEFT HALF BLACK"),
+    ('\u{29D2}', "BOWTIE WITH RIGHT HALF BLACK"),
+    ('\u{29D3}', "BLACK BOWTIE"),
+    ('\u{29D4}', "TIMES WITH LEFT HALF BLACK"),
+    ('\u{29D5}', "TIMES WITH RIGHT HALF BLACK"),
+    ('\u{29D6}', "WHITE HOURGLASS"),
+    ('\u{29D7}', "BLACK HOURGLASS"),
+    ('\u{29D8}', "LEFT WIGGLY FENCE"),
+    ('\u{29D9}', "RIGHT WIGGLY FENCE"),
+    ('\u{29DA}', "LEFT DOUBLE WIGGLY FENCE"),
+    ('\u{29DB}', "RIGHT DOUBLE WIGGLY FENCE"),
+    ('\u{29DC}', "INCOMPLETE INFINITY"),
+    ('\u{29DD}', "TIE OVER INFINITY"),
+    ('\u{29DE}', "INFINITY NEGATED WITH VERTICAL BAR"),
+    ('\u{29DF}', "DOUBLE-ENDED MULTIMAP"),
+    ('\u{29E0}', "SQUARE WITH CONTOURED OUTLINE"),
+    ('\u{29E1}', "INCREASES AS"),
+    ('\u{29E2}', "SHUFFLE PRODUCT"),
+    ('\u{29E3}', "EQUALS SIGN AND SLANTED PARALLEL"),
+    (
+        '\u{29E4}',
+        "EQUALS SIGN AND SLANTED PARALLEL WITH TILDE ABOVE",
+    ),
+    ('\u{29E5}', "IDENTICAL TO AND SLANTED PARALLEL"),
+    ('\u{29E6}', "GLEICH STARK"),
+    ('\u{29E7}', "THERMODYNAMIC"),
+    ('\u{29E8}', "DOWN-POINTING TRIANGLE WITH LEFT HALF BLACK"),
+    ('\u{29E9}', "DOWN-POINTING TRIANGLE WITH RIGHT HALF BLACK"),
+    ('\u{29EA}', "BLACK DIAMOND WITH DOWN ARROW"),
+    ('\u{29EB}', "BLACK LOZENGE"),
+    ('\u{29EC}', "WHITE CIRCLE WITH DOWN ARROW"),
+    ('\u{29ED}', "BLACK CIRCLE WITH DOWN ARROW"),
+    ('\u{29EE}', "ERROR-BARRED WHITE SQUARE"),
+    ('\u{29EF}', "ERROR-BARRED BLACK SQUARE"),
+    ('\u{29F0}', "ERROR-BARRED WHITE DIAMOND"),
+    ('\u{29F1}', "ERROR-BARRED BLACK DIAMOND"),
+    ('\u{29F2}', "ERROR-BARRED WHITE CIRCLE"),
+    ('\u{29F3}', "ERROR-BARRED BLACK CIRCLE"),
+    ('\u{29F4}', "RULE-DELAYED"),
+    ('\u{29F5}', "REVERSE SOLIDUS OPERATOR"),
+    ('\u{29F6}', "SOLIDUS WITH OVERBAR"),
+    ('\u{29F7}', "REVERSE SOLIDUS WITH HORIZONTAL STROKE"),
+    ('\u{29F8}', "BIG SOLIDUS"),
+    ('\u{29F9}', "BIG REVERSE SOLIDUS"),
+    ('\u{29FA}', "DOUBLE PLUS"),
+    ('\u{29FB}', "TRIPLE PLUS"),
+    ('\u{29FC}', "LEFT-POINTING CURVED ANGLE BRACKET"),
+    ('\u{29FD}', "RIGHT-POINTING CURVED ANGLE BRACKET"),
+    ('\u{29FE}', "TINY"),
+    ('\u{29FF}', "MINY"),
+    ('\u{2A00}', "N-ARY CIRCLED DOT OPERATOR"),
+    ('\u{2A01}', "N-ARY CIRCLED PLUS OPERATOR"),
+    ('\u{2A02}', "N-ARY CIRCLED TIMES OPERATOR"),
+    ('\u{2A03}', "N-ARY UNION OPERATOR WITH DOT"),
+    ('\u{2A04}', "N-ARY UNION OPERATOR WITH PLUS"),
+    ('\u{2A05}', "N-ARY SQUARE INTERSECTION OPERATOR"),
+    ('\u{2A06}', "N-ARY SQUARE UNION OPERATOR"),
+    ('\u{2A07}', "TWO LOGICAL AND OPERATOR"),
+    ('\u{2A08}', "TWO LOGICAL OR OPERATOR"),
+    ('\u{2A09}', "N-ARY TIMES OPERATOR"),
+    ('\u{2A0A}', "MODULO TWO SUM"),
+    ('\u{2A0B}', "SUMMATION WITH INTEGRAL"),
+    ('\u{2A0C}', "QUADRUPLE INTEGRAL OPERATOR"),
+    ('\u{2A0D}', "FINITE PART INTEGRAL"),
+    ('\u{2A0E}', "INTEGRAL WITH DOUBLE STROKE"),
+    ('\u{2A0F}', "INTEGRAL AVERAGE WITH SLASH"),
+    ('\u{2A10}', "CIRCULATION FUNCTION"),
+    ('\u{2A11}', "ANTICLOCKWISE INTEGRATION"),
+    (
+        '\u{2A12}',
+        "LINE INTEGRATION WITH RECTANGULAR PATH AROUND POLE",
+    ),
+    (
+        '\u{2A13}',
+        "LINE INTEGRATION WITH SEMICIRCULAR PATH AROUND POLE",
+    ),
+    ('\u{2A14}', "LINE INTEGRATION NOT INCLUDING THE POLE"),
+    ('\u{2A15}', "INTEGRAL AROUND A POINT OPERATOR"),
+    ('\u{2A16}', "QUATERNION INTEGRAL OPERATOR"),
+    ('\u{2A17}', "INTEGRAL WITH LEFTWARDS ARROW WITH HOOK"),
+    ('\u{2A18}', "INTEGRAL WITH TIMES SIGN"),
+    ('\u{2A19}', "INTEGRAL WITH INTERSECTION"),
+    ('\u{2A1A}', "INTEGRAL WITH UNION"),
+    ('\u{2A1B}', "INTEGRAL WITH OVERBAR"),
+    ('\u{2A1C}', "INTEGRAL WITH UNDERBAR"),
+    ('\u{2A1D}', "JOIN"),
+    ('\u{2A1E}', "LARGE LEFT TRIANGLE OPERATOR"),
+    ('\u{2A1F}', "Z NOTATION SCHEMA COMPOSITION"),
+    ('\u{2A20}', "Z NOTATION SCHEMA PIPING"),
+    ('\u{2A21}', "Z NOTATION SCHEMA PROJECTION"),
+    ('\u{2A22}', "PLUS SIGN WITH SMALL CIRCLE ABOVE"),
+    ('\u{2A23}', "PLUS SIGN WITH CIRCUMFLEX ACCENT ABOVE"),
+    ('\u{2A24}', "PLUS SIGN WITH TILDE ABOVE"),
+    ('\u{2A25}', "PLUS SIGN WITH DOT BELOW"),
+    ('\u{2A26}', "PLUS SIGN WITH TILDE BELOW"),
+    ('\u{2A27}', "PLUS SIGN WITH SUBSCRIPT TWO"),
+    ('\u{2A28}', "PLUS SIGN WITH BLACK TRIANGLE"),
+    ('\u{2A29}', "MINUS SIGN WITH COMMA ABOVE"),
+    ('\u{2A2A}', "MINUS SIGN WITH DOT BELOW"),
+    ('\u{2A2B}', "MINUS SIGN WITH FALLING DOTS"),
+    ('\u{2A2C}', "MINUS SIGN WITH RISING DOTS"),
+    ('\u{2A2D}', "PLUS SIGN IN LEFT HALF CIRCLE"),
+    ('\u{2A2E}', "PLUS SIGN IN RIGHT HALF CIRCLE"),
+    ('\u{2A2F}', "VECTOR OR CROSS PRODUCT"),
+    ('\u{2A30}', "MULTIPLICATION SIGN WITH DOT ABOVE"),
+    ('\u{2A31}', "MULTIPLICATION SIGN WITH UNDERBAR"),
+    ('\u{2A32}', "SEMIDIRECT PRODUCT WITH BOTTOM CLOSED"),
+    ('\u{2A33}', "SMASH PRODUCT"),
+    ('\u{2A34}', "MULTIPLICATION SIGN IN LEFT HALF CIRCLE"),
+    ('\u{2A35}', "MULTIPLICATION SIGN IN RIGHT HALF CIRCLE"),
+    (
+        '\u{2A36}',
+        "CIRCLED MULTIPLICATION SIGN WITH CIRCUMFLEX ACCENT",
+    ),
+    ('\u{2A37}', "MULTIPLICATION SIGN IN DOUBLE CIRCLE"),
+    ('\u{2A38}', "CIRCLED DIVISION SIGN"),
+    ('\u{2A39}', "PLUS SIGN IN TRIANGLE"),
+    ('\u{2A3A}', "MINUS SIGN IN TRIANGLE"),
+    ('\u{2A3B}', "MULTIPLICATION SIGN IN TRIANGLE"),
+    ('\u{2A3C}', "INTERIOR PRODUCT"),
+    ('\u{2A3D}', "RIGHTHAND INTERIOR PRODUCT"),
+    ('\u{2A3E}', "Z NOTATION RELATIONAL COMPOSITION"),
+    ('\u{2A3F}', "AMALGAMATION OR COPRODUCT"),
+    ('\u{2A40}', "INTERSECTION WITH DOT"),
+    ('\u{2A41}', "UNION WITH MINUS SIGN"),
+    ('\u{2A42}', "UNION WITH OVERBAR"),
+    ('\u{2A43}', "INTERSECTION WITH OVERBAR"),
+    ('\u{2A44}', "INTERSECTION WITH LOGICAL AND"),
+    ('\u{2A45}', "UNION WITH LOGICAL OR"),
+    ('\u{2A46}', "UNION ABOVE INTERSECTION"),
+    ('\u{2A47}', "INTERSECTION ABOVE UNION"),
+    ('\u{2A48}', "UNION ABOVE BAR ABOVE INTERSECTION"),
+    ('\u{2A49}', "INTERSECTION ABOVE BAR ABOVE UNION"),
+    ('\u{2A4A}', "UNION BESIDE AND JOINED WITH UNION"),
+    (
+        '\u{2A4B}',
+        "INTERSECTION BESIDE AND JOINED WITH INTERSECTION",
+    ),
+    ('\u{2A4C}', "CLOSED UNION WITH SERIFS"),
+    ('\u{2A4D}', "CLOSED INTERSECTION WITH SERIFS"),
+    ('\u{2A4E}', "DOUBLE SQUARE INTERSECTION"),
+    ('\u{2A4F}', "DOUBLE SQUARE UNION"),
+    ('\u{2A50}', "CLOSED UNION WITH SERIFS AND SMASH PRODUCT"),
+    ('\u{2A51}', "LOGICAL AND WITH DOT ABOVE"),
+    ('\u{2A52}', "LOGICAL OR WITH DOT ABOVE"),
+    ('\u{2A53}', "DOUBLE LOGICAL AND"),
+    ('\u{2A54}', "DOUBLE LOGICAL OR"),
+    ('\u{2A55}', "TWO INTERSECTING LOGICAL AND"),
+    ('\u{2A56}', "TWO INTERSECTING LOGICAL OR"),
+    ('\u{2A57}', "SLOPING LARGE OR"),
+    ('\u{2A58}', "SLOPING LARGE AND"),
+    ('\u{2A59}', "LOGICAL OR OVERLAPPING LOGICAL AND"),
+    ('\u{2A5A}', "LOGICAL AND WITH MIDDLE STEM"),
+    ('\u{2A5B}', "LOGICAL OR WITH MIDDLE STEM"),
+    ('\u{2A5C}', "LOGICAL AND WITH HORIZONTAL DASH"),
+    ('\u{2A5D}', "LOGICAL OR WITH HORIZONTAL DASH"),
+    ('\u{2A5E}', "LOGICAL AND WITH DOUBLE OVERBAR"),
+    ('\u{2A5F}', "LOGICAL AND WITH UNDERBAR"),
+    ('\u{2A60}', "LOGICAL AND WITH DOUBLE UNDERBAR"),
+    ('\u{2A61}', "SMALL VEE WITH UNDERBAR"),
+    ('\u{2A62}', "LOGICAL OR WITH DOUBLE OVERBAR"),
+    ('\u{2A63}', "LOGICAL OR WITH DOUBLE UNDERBAR"),
+    ('\u{2A64}', "Z NOTATION DOMAIN ANTIRESTRICTION"),
+    ('\u{2A65}', "Z NOTATION RANGE ANTIRESTRICTION"),
+    ('\u{2A66}', "EQUALS SIGN WITH DOT BELOW"),
+    ('\u{2A67}', "IDENTICAL WITH DOT ABOVE"),
+    (
+        '\u{2A68}',
+        "TRIPLE HORIZONTAL BAR WITH DOUBLE VERTICAL STROKE",
+    ),
+    (
+        '\u{2A69}',
+        "TRIPLE HORIZONTAL BAR WITH TRIPLE VERTICAL STROKE",
+    ),
+    ('\u{2A6A}', "TILDE OPERATOR WITH DOT ABOVE"),
+    ('\u{2A6B}', "TILDE OPERATOR WITH RISING DOTS"),
+    ('\u{2A6C}', "SIMILAR MINUS SIMILAR"),
+    ('\u{2A6D}', "CONGRUENT WITH DOT ABOVE"),
+    ('\u{2A6E}', "EQUALS WITH ASTERISK"),
+    ('\u{2A6F}', "ALMOST EQUAL TO WITH CIRCUMFLEX ACCENT"),
+    ('\u{2A70}', "APPROXIMATELY EQUAL OR EQUAL TO"),
+    ('\u{2A71}', "EQUALS SIGN ABOVE PLUS SIGN"),
+    ('\u{2A72}', "PLUS SIGN ABOVE EQUALS SIGN"),
+    ('\u{2A73}', "EQUALS SIGN ABOVE TILDE OPERATOR"),
+    ('\u{2A74}', "DOUBLE COLON EQUAL"),
+    ('\u{2A75}', "TWO CONSECUTIVE EQUALS SIGNS"),
+    ('\u{2A76}', "THREE CONSECUTIVE EQUALS SIGNS"),
+    (
+        '\u{2A77}',
+        "EQUALS SIGN WITH TWO DOTS ABOVE AND TWO DOTS BELOW",
+    ),
+    ('\u{2A78}', "EQUIVALENT WITH FOUR DOTS ABOVE"),
+    ('\u{2A79}', "LESS-THAN WITH CIRCLE INSIDE"),
+    ('\u{2A7A}', "GREATER-THAN WITH CIRCLE INSIDE"),
+    ('\u{2A7B}', "LESS-THAN WITH QUESTION MARK ABOVE"),
+    ('\u{2A7C}', "GREATER-THAN WITH QUESTION MARK ABOVE"),
+    ('\u{2A7D}', "LESS-THAN OR SLANTED EQUAL TO"),
+    ('\u{2A7E}', "GREATER-THAN OR SLANTED EQUAL TO"),
+    ('\u{2A7F}', "LESS-THAN OR SLANTED EQUAL TO WITH DOT INSIDE"),
+    (
+        '\u{2A80}',
+        "GREATER-THAN OR SLANTED EQUAL TO WITH DOT INSIDE",
+    ),
+    ('\u{2A81}', "LESS-THAN OR SLANTED EQUAL TO WITH DOT ABOVE"),
+    (
+        '\u{2A82}',
+        "GREATER-THAN OR SLANTED EQUAL TO WITH DOT ABOVE",
+    ),
+    (
+        '\u{2A83}',
+        "LESS-THAN OR SLANTED EQUAL TO WITH DOT ABOVE RIGHT",
+    ),
+    (
+        '\u{2A84}',
+        "GREATER-THAN OR SLANTED EQUAL TO WITH DOT ABOVE LEFT",
+    ),
+    ('\u{2A85}', "LESS-THAN OR APPROXIMATE"),
+    ('\u{2A86}', "GREATER-THAN OR APPROXIMATE"),
+    ('\u{2A87}', "LESS-THAN AND SINGLE-LINE NOT EQUAL TO"),
+    ('\u{2A88}', "GREATER-THAN AND SINGLE-LINE NOT EQUAL TO"),
+    ('\u{2A89}', "LESS-THAN AND NOT APPROXIMATE"),
+    ('\u{2A8A}', "GREATER-THAN AND NOT APPROXIMATE"),
+    (
+        '\u{2A8B}',
+        "LESS-THAN ABOVE DOUBLE-LINE EQUAL ABOVE GREATER-THAN",
+    ),
+    (
+        '\u{2A8C}',
+        "GREATER-THAN ABOVE DOUBLE-LINE EQUAL ABOVE LESS-THAN",
+    ),
+    ('\u{2A8D}', "LESS-THAN ABOVE SIMILAR OR EQUAL"),
+    ('\u{2A8E}', "GREATER-THAN ABOVE SIMILAR OR EQUAL"),
+    ('\u{2A8F}', "LESS-THAN ABOVE SIMILAR ABOVE GREATER-THAN"),
+    ('\u{2A90}', "GREATER-THAN ABOVE SIMILAR ABOVE LESS-THAN"),
+    (
+        '\u{2A91}',
+        "LESS-THAN ABOVE GREATER-THAN ABOVE DOUBLE-LINE EQUAL",
+    ),
+    (
+        '\u{2A92}',
+        "GREATER-THAN ABOVE LESS-THAN ABOVE DOUBLE-LINE EQUAL",
+    ),
+    (
+        '\u{2A93}',
+        "LESS-THAN ABOVE SLANTED EQUAL ABOVE GREATER-THAN ABOVE SLANTED EQUAL",
+    ),
+    (
+        '\u{2A94}',
+        "GREATER-THAN ABOVE SLANTED EQUAL ABOVE LESS-THAN ABOVE SLANTED EQUAL",
+    ),
+    ('\u{2A95}', "SLANTED EQUAL TO OR LESS-THAN"),
+    ('\u{2A96}', "SLANTED EQUAL TO OR GREATER-THAN"),
+    ('\u{2A97}', "SLANTED EQUAL TO OR LESS-THAN WITH DOT INSIDE"),
+    (
+        '\u{2A98}',
+        "SLANTED EQUAL TO OR GREATER-THAN WITH DOT INSIDE",
+    ),
+    ('\u{2A99}', "DOUBLE-LINE EQUAL TO OR LESS-THAN"),
+    ('\u{2A9A}', "DOUBLE-LINE EQUAL TO OR GREATER-THAN"),
+    ('\u{2A9B}', "DOUBLE-LINE SLANTED EQUAL TO OR LESS-THAN"),
+    ('\u{2A9C}', "DOUBLE-LINE SLANTED EQUAL TO OR GREATER-THAN"),
+    ('\u{2A9D}', "SIMILAR OR LESS-THAN"),
+    ('\u{2A9E}', "SIMILAR OR GREATER-THAN"),
+    ('\u{2A9F}', "SIMILAR ABOVE LESS-THAN ABOVE EQUALS SIGN"),
+    ('\u{2AA0}', "SIMILAR ABOVE GREATER-THAN ABOVE EQUALS SIGN"),
+    ('\u{2AA1}', "DOUBLE NESTED LESS-THAN"),
+    ('\u{2AA2}', "DOUBLE NESTED GREATER-THAN"),
+    ('\u{2AA3}', "DOUBLE NESTED LESS-THAN WITH UNDERBAR"),
+    ('\u{2AA4}', "GREATER-THAN OVERLAPPING LESS-THAN"),
+    ('\u{2AA5}', "GREATER-THAN BESIDE LESS-THAN"),
+    ('\u{2AA6}', "LESS-THAN CLOSED BY CURVE"),
+    ('\u{2AA7}', "GREATER-THAN CLOSED BY CURVE"),
+    ('\u{2AA8}', "LESS-THAN CLOSED BY CURVE ABOVE SLANTED EQUAL"),
+    (
+        '\u{2AA9}',
+        "GREATER-THAN CLOSED BY CURVE ABOVE SLANTED EQUAL",
+    ),
+    ('\u{2AAA}', "SMALLER THAN"),
+    ('\u{2AAB}', "LARGER THAN"),
+    ('\u{2AAC}', "SMALLER THAN OR EQUAL TO"),
+    ('\u{2AAD}', "LARGER THAN OR EQUAL TO"),
+    ('\u{2AAE}', "EQUALS SIGN WITH BUMPY ABOVE"),
+    ('\u{2AAF}', "PRECEDES ABOVE SINGLE-LINE EQUALS SIGN"),
+    ('\u{2AB0}', "SUCCEEDS ABOVE SINGLE-LINE EQUALS SIGN"),
+    ('\u{2AB1}', "PRECEDES ABOVE SINGLE-LINE NOT EQUAL TO"),
+    ('\u{2AB2}', "SUCCEEDS ABOVE SINGLE-LINE NOT EQUAL TO"),
+    ('\u{2AB3}', "PRECEDES ABOVE EQUALS SIGN"),
+    ('\u{2AB4}', "SUCCEEDS ABOVE EQUALS SIGN"),
+    ('\u{2AB5}', "PRECEDES ABOVE NOT EQUAL TO"),
+    ('\u{2AB6}', "SUCCEEDS ABOVE NOT EQUAL TO"),
+    ('\u{2AB7}', "PRECEDES ABOVE ALMOST EQUAL TO"),
+    ('\u{2AB8}', "SUCCEEDS ABOVE ALMOST EQUAL TO"),
+    ('\u{2AB9}', "PRECEDES ABOVE NOT ALMOST EQUAL TO"),
+    ('\u{2ABA}', "SUCCEEDS ABOVE NOT ALMOST EQUAL TO"),
+    ('\u{2ABB}', "DOUBLE PRECEDES"),
+    ('\u{2ABC}', "DOUBLE SUCCEEDS"),
+    ('\u{2ABD}', "SUBSET WITH DOT"),
+    ('\u{2ABE}', "SUPERSET WITH DOT"),
+    ('\u{2ABF}', "SUBSET WITH PLUS SIGN BELOW"),
+    ('\u{2AC0}', "SUPERSET WITH PLUS SIGN BELOW"),
+    ('\u{2AC1}', "SUBSET WITH MULTIPLICATION SIGN BELOW"),
+    ('\u{2AC2}', "SUPERSET WITH MULTIPLICATION SIGN BELOW"),
+    ('\u{2AC3}', "SUBSET OF OR EQUAL TO WITH DOT ABOVE"),
+    ('\u{2AC4}', "SUPERSET OF OR EQUAL TO WITH DOT ABOVE"),
+    ('\u{2AC5}', "SUBSET OF ABOVE EQUALS SIGN"),
+    ('\u{2AC6}', "SUPERSET OF ABOVE EQUALS SIGN"),
+    ('\u{2AC7}', "SUBSET OF ABOVE TILDE OPERATOR"),
+    ('\u{2AC8}', "SUPERSET OF ABOVE TILDE OPERATOR"),
+    ('\u{2AC9}', "SUBSET OF ABOVE ALMOST EQUAL TO"),
+    ('\u{2ACA}', "SUPERSET OF ABOVE ALMOST EQUAL TO"),
+    ('\u{2ACB}', "SUBSET OF ABOVE NOT EQUAL TO"),
+    ('\u{2ACC}', "SUPERSET OF ABOVE NOT EQUAL TO"),
+    ('\u{2ACD}', "SQUARE LEFT OPEN BOX OPERATOR"),
+    ('\u{2ACE}', "SQUARE RIGHT OPEN BOX OPERATOR"),
+    ('\u{2ACF}', "CLOSED SUBSET"),
+    ('\u{2AD0}', "CLOSED SUPERSET"),
+    ('\u{2AD1}', "CLOSED SUBSET OR EQUAL TO"),
+    ('\u{2AD2}', "CLOSED SUPERSET OR EQUAL TO"),
+    ('\u{2AD3}', "SUBSET ABOVE SUPERSET"),
+    ('\u{2AD4}', "SUPERSET ABOVE SUBSET"),
+    ('\u{2AD5}', "SUBSET ABOVE SUBSET"),
+    ('\u{2AD6}', "SUPERSET ABOVE SUPERSET"),
+    ('\u{2AD7}', "SUPERSET BESIDE SUBSET"),
+    ('\u{2AD8}', "SUPERSET BESIDE AND JOINED BY DASH WITH SUBSET"),
+    ('\u{2AD9}', "ELEMENT OF OPENING DOWNWARDS"),
+    ('\u{2ADA}', "PITCHFORK WITH TEE TOP"),
+    ('\u{2ADB}', "TRANSVERSAL INTERSECTION"),
+    ('\u{2ADC}', "FORKING"),
+    ('\u{2ADD}', "NONFORKING"),
+    ('\u{2ADE}', "SHORT LEFT TACK"),
+    ('\u{2ADF}', "SHORT DOWN TACK"),
+    ('\u{2AE0}', "SHORT UP TACK"),
+    ('\u{2AE1}', "PERPENDICULAR WITH S"),
+    ('\u{2AE2}', "VERTICAL BAR TRIPLE RIGHT TURNSTILE"),
+    ('\u{2AE3}', "DOUBLE VERTICAL BAR LEFT TURNSTILE"),
+    ('\u{2AE4}', "VERTICAL BAR DOUBLE LEFT TURNSTILE"),
+    ('\u{2AE5}', "DOUBLE VERTICAL BAR DOUBLE LEFT TURNSTILE"),
+    ('\u{2AE6}', "LONG DASH FROM LEFT MEMBER OF DOUBLE VERTICAL"),
+    ('\u{2AE7}', "SHORT DOWN TACK WITH OVERBAR"),
+    ('\u{2AE8}', "SHORT UP TACK WITH UNDERBAR"),
+    ('\u{2AE9}', "SHORT UP TACK ABOVE SHORT DOWN TACK"),
+    ('\u{2AEA}', "DOUBLE DOWN TACK"),
+    ('\u{2AEB}', "DOUBLE UP TACK"),
+    ('\u{2AEC}', "DOUBLE STROKE NOT SIGN"),
+    ('\u{2AED}', "REVERSED DOUBLE STROKE NOT SIGN"),
+    ('\u{2AEE}', "DOES NOT DIVIDE WITH REVERSED NEGATION SLASH"),
+    ('\u{2AEF}', "VERTICAL LINE WITH CIRCLE ABOVE"),
+    ('\u{2AF0}', "VERTICAL LINE WITH CIRCLE BELOW"),
+    ('\u{2AF1}', "DOWN TACK WITH CIRCLE BELOW"),
+    ('\u{2AF2}', "PARALLEL WITH HORIZONTAL STROKE"),
+    ('\u{2AF3}', "PARALLEL WITH TILDE OPERATOR"),
+    ('\u{2AF4}', "TRIPLE VERTICAL BAR BINARY RELATION"),
+    ('\u{2AF5}', "TRIPLE VERTICAL BAR WITH HORIZONTAL STROKE"),
+    ('\u{2AF6}', "TRIPLE COLON OPERATOR"),
+    ('\u{2AF7}', "TRIPLE NESTED LESS-THAN"),
+    ('\u{2AF8}', "TRIPLE NESTED GREATER-THAN"),
+    ('\u{2AF9}', "DOUBLE-LINE SLANTED LESS-THAN OR EQUAL TO"),
+    ('\u{2AFA}', "DOUBLE-LINE SLANTED GREATER-THAN OR EQUAL TO"),
+    ('\u{2AFB}', "TRIPLE SOLIDUS BINARY RELATION"),
+    ('\u{2AFC}', "LARGE TRIPLE VERTICAL BAR OPERATOR"),
+    ('\u{2AFD}', "DOUBLE SOLIDUS OPERATOR"),
+    ('\u{2AFE}', "WHITE VERTICAL BAR"),
+    ('\u{2AFF}', "N-ARY WHITE VERTICAL BAR"),
+];
+
+/// 文字の Unicode 文字名。表に無い文字であれば None。
+pub fn char_name(c: char) -> Option<&'static str> {
+    NAMES
+        .binary_search_by_key(&c, |&(ch, _)| ch)
+        .ok()
+        .map(|idx| NAMES[idx].1)
+}
+
+/// 文字列を構成する各文字の Unicode 文字名を空白で繋げたもの。
+/// 表に無い文字が含まれていれば None。
+pub fn glyph_name(glyph: &str) -> Option<String> {
+    let names = glyph.chars().map(char_name).collect::<Option<Vec<_>>>()?;
+    Some(names.join(" "))
+}
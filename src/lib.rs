@@ -10,7 +10,7 @@ mod config;
 mod documents;
 mod util;
 
-pub use language_server::LanguageServer;
+pub use language_server::{LanguageServer, SnippetEditService};
 
 pub fn version() -> String {
     env!("CARGO_PKG_VERSION").to_owned()
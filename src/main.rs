@@ -4,6 +4,7 @@
 
 use anyhow::Result;
 use lspower::{LspService, Server};
+use satysfi_language_server::SnippetEditService;
 
 use structopt::StructOpt;
 use tokio::net::TcpListener;
@@ -33,7 +34,7 @@ async fn main() -> Result<()> {
         let (service, messages) = LspService::new(satysfi_language_server::LanguageServer::new);
         Server::new(read, write)
             .interleave(messages)
-            .serve(service)
+            .serve(SnippetEditService::new(service))
             .await;
     } else {
         let stdin = tokio::io::stdin();
@@ -42,7 +43,7 @@ async fn main() -> Result<()> {
         let (service, messages) = LspService::new(satysfi_language_server::LanguageServer::new);
         Server::new(stdin, stdout)
             .interleave(messages)
-            .serve(service)
+            .serve(SnippetEditService::new(service))
             .await;
     };
 
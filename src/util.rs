@@ -1,22 +1,21 @@
 use itertools::Itertools;
-use lspower::lsp::{Position, Url};
+use lspower::lsp::{Position, Range, Url};
 use satysfi_parser::{structure::ProgramText, CstText, LineCol, Span};
 
 /// Position を convert する関数の提供。
+/// 行の先頭位置さえ分かれば変換できるので、実装する型はそれだけを与える。
 pub trait ConvertPosition {
-    fn get_position(&self, pos: usize) -> Option<Position>;
-    fn from_position(&self, pos: &Position) -> Option<usize>;
-}
+    /// テキスト本体。
+    fn text(&self) -> &str;
+    /// line 行目の先頭位置。
+    fn line_offset(&self, line: usize) -> Option<usize>;
+    /// pos が属する行の番号。
+    fn line_of(&self, pos: usize) -> Option<usize>;
 
-impl ConvertPosition for CstText {
     fn get_position(&self, pos: usize) -> Option<Position> {
-        let LineCol { line, .. } = self.get_line_col(pos)?;
-        let span = {
-            let start = self.from_line_col(line, 0).unwrap();
-            Span { start, end: pos }
-        };
-        let text = self.get_text_from_span(span);
-        let character = text.encode_utf16().collect_vec().len();
+        let line = self.line_of(pos)?;
+        let start = self.line_offset(line)?;
+        let character = self.text().get(start..pos)?.encode_utf16().count();
         Some(Position {
             line: line as u32,
             character: character as u32,
@@ -26,49 +25,70 @@ impl ConvertPosition for CstText {
     fn from_position(&self, pos: &Position) -> Option<usize> {
         let &Position { line, character } = pos;
         // position が属する行のテキストを取り出す。
-        let text = {
-            let start = self.from_line_col(line as usize, 0).unwrap();
-            let end = self
-                .from_line_col((line + 1) as usize, 0)
-                .unwrap_or(self.cst.span.end);
-            self.get_text_from_span(Span { start, end })
-        };
+        let start = self.line_offset(line as usize)?;
+        let end = self
+            .line_offset((line + 1) as usize)
+            .unwrap_or_else(|| self.text().len());
+        let text = self.text().get(start..end)?;
         let vec_utf16 = text.encode_utf16().take(character as usize).collect_vec();
-        let text = String::from_utf16_lossy(&vec_utf16);
-        let column = text.len();
-        self.from_line_col(line as usize, column)
+        let column = String::from_utf16_lossy(&vec_utf16).len();
+        Some(start + column)
+    }
+
+    fn get_range(&self, span: Span) -> Option<Range> {
+        Some(Range {
+            start: self.get_position(span.start)?,
+            end: self.get_position(span.end)?,
+        })
+    }
+}
+
+impl ConvertPosition for CstText {
+    fn text(&self) -> &str {
+        &self.text
+    }
+
+    fn line_offset(&self, line: usize) -> Option<usize> {
+        self.from_line_col(line, 0)
+    }
+
+    fn line_of(&self, pos: usize) -> Option<usize> {
+        self.get_line_col(pos).map(|LineCol { line, .. }| line)
     }
 }
 
 impl ConvertPosition for ProgramText {
-    fn get_position(&self, pos: usize) -> Option<Position> {
-        let LineCol { line, .. } = self.get_line_col(pos)?;
-        let span = {
-            let start = self.from_line_col(line, 0).unwrap();
-            Span { start, end: pos }
-        };
-        let text = self.get_text_from_span(span);
-        let character = text.encode_utf16().collect_vec().len();
-        Some(Position {
-            line: line as u32,
-            character: character as u32,
-        })
+    fn text(&self) -> &str {
+        &self.text
     }
 
-    fn from_position(&self, pos: &Position) -> Option<usize> {
-        let &Position { line, character } = pos;
-        // position が属する行のテキストを取り出す。
-        let text = {
-            let start = self.from_line_col(line as usize, 0).unwrap();
-            let end = self
-                .from_line_col((line + 1) as usize, 0)
-                .unwrap_or(self.cst.span.end);
-            self.get_text_from_span(Span { start, end })
-        };
-        let vec_utf16 = text.encode_utf16().take(character as usize).collect_vec();
-        let text = String::from_utf16_lossy(&vec_utf16);
-        let column = text.len();
-        self.from_line_col(line as usize, column)
+    fn line_offset(&self, line: usize) -> Option<usize> {
+        self.from_line_col(line, 0)
+    }
+
+    fn line_of(&self, pos: usize) -> Option<usize> {
+        self.get_line_col(pos).map(|LineCol { line, .. }| line)
+    }
+}
+
+/// パースできなかったテキストに対して用いる。
+impl ConvertPosition for str {
+    fn text(&self) -> &str {
+        self
+    }
+
+    fn line_offset(&self, line: usize) -> Option<usize> {
+        if line == 0 {
+            Some(0)
+        } else {
+            self.match_indices('\n')
+                .nth(line - 1)
+                .map(|(idx, _)| idx + 1)
+        }
+    }
+
+    fn line_of(&self, pos: usize) -> Option<usize> {
+        Some(self.get(..pos)?.matches('\n').count())
     }
 }
 
@@ -77,3 +97,134 @@ pub struct UrlPos {
     pub url: Url,
     pub pos: Position,
 }
+
+/// テキスト中のバイト位置を LSP の Position に変換する。
+/// パースできなかったテキストに対して用いる。
+pub fn offset_to_position(text: &str, pos: usize) -> Position {
+    let start = line_start(text, pos);
+    Position {
+        line: text[..start].matches('\n').count() as u32,
+        character: text[start..pos].encode_utf16().count() as u32,
+    }
+}
+
+/// pos を含む行の先頭位置を返す。
+pub fn line_start(text: &str, pos: usize) -> usize {
+    text[..pos].rfind('\n').map(|idx| idx + 1).unwrap_or(0)
+}
+
+/// pos を含む行の末尾（改行文字の直後）を返す。
+pub fn line_end(text: &str, pos: usize) -> usize {
+    text[pos..]
+        .find('\n')
+        .map(|idx| pos + idx + 1)
+        .unwrap_or_else(|| text.len())
+}
+
+/// span の前後がその行において空白のみであれば、 span を行全体に広げる。
+/// 行単位で削除したい場合に用いる。
+pub fn expand_to_lines(text: &str, span: Span) -> Span {
+    let start = line_start(text, span.start);
+    let end = line_end(text, span.end);
+    if text[start..span.start].trim().is_empty() && text[span.end..end].trim().is_empty() {
+        Span { start, end }
+    } else {
+        span
+    }
+}
+
+/// 関数適用の並びを区切るキーワード。
+pub const KEYWORDS: &[&str] = &[
+    "let", "in", "if", "then", "else", "match", "with", "fun", "and", "while", "do", "before",
+    "open", "module", "struct", "sig", "val", "type", "of", "end", "mod", "not",
+];
+
+/// ある引数より前のテキスト（同じ行のもの）から、呼び出している関数・コマンドの名前と、
+/// その引数が何番目（1 から数える）であるかを求める。
+/// 括弧で囲まれた引数、 `?:` や `?*` の付いたオプション引数、変数などの単純な引数のみを数える。
+pub fn enclosing_call(before: &str) -> Option<(&str, usize)> {
+    let (callee, written) = written_arguments(before)?;
+    Some((callee, written.len() + 1))
+}
+
+/// ある引数より前のテキスト（同じ行のもの）から、呼び出している関数・コマンドの名前と、
+/// それより前に書かれた引数それぞれがオプション引数（`?:` や `?*`）であるかを、書かれた順に求める。
+pub fn written_arguments(before: &str) -> Option<(&str, Vec<bool>)> {
+    let mut rest = before.trim_end();
+    // 後ろから読んだ引数
+    let mut args = vec![];
+    let mut callee = None;
+    loop {
+        if let Some(stripped) = rest.strip_suffix("?*") {
+            rest = stripped.trim_end();
+            args.push(true);
+            continue;
+        }
+        if rest.ends_with(|c| matches!(c, ')' | '}' | ']')) {
+            rest = rest[..group_start(rest)?].trim_end();
+            let optional = rest.ends_with("?:");
+            rest = rest.strip_suffix("?:").unwrap_or(rest).trim_end();
+            args.push(optional);
+            continue;
+        }
+        let token_start = rest
+            .trim_end_matches(|c: char| c.is_alphanumeric() || "-_.\\+#'".contains(c))
+            .len();
+        let token = &rest[token_start..];
+        if token.is_empty() || KEYWORDS.contains(&token) {
+            break;
+        }
+        callee = Some((token, args.iter().rev().copied().collect()));
+        // コマンドはそれ自体が呼び出しの先頭である
+        if token.starts_with(|c| matches!(c, '\\' | '+')) {
+            break;
+        }
+        rest = rest[..token_start].trim_end();
+        args.push(false);
+    }
+    callee
+}
+
+/// 文字列リテラルより前のテキスト（同じ行のもの）から、その文字列リテラルを受け取る関数・コマンドの
+/// module の修飾を除いた名前と、それが何番目の引数であるかを求める。
+/// ``(`path`)`` や ``?:(`key`)`` のように括弧で囲まれている場合も扱う。
+pub fn literal_argument(before: &str) -> Option<(String, usize)> {
+    let before = before.trim_end();
+    let before = before.strip_suffix('(').unwrap_or(before).trim_end();
+    let before = before.strip_suffix("?:").unwrap_or(before);
+    let (callee, position) = enclosing_call(before)?;
+    Some((unqualified(callee), position))
+}
+
+/// 閉じ括弧で終わるテキストについて、対応する開き括弧の位置。
+pub fn group_start(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (idx, c) in text.char_indices().rev() {
+        match c {
+            ')' | '}' | ']' => depth += 1,
+            '(' | '{' | '[' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(idx);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// `\Mod.insert-image` のように module 名で修飾された名前から、修飾を除いたもの。
+pub fn unqualified(name: &str) -> String {
+    match name.rfind('.') {
+        Some(idx) => {
+            let sigil = if name.starts_with(|c| matches!(c, '\\' | '+' | '#')) {
+                &name[..1]
+            } else {
+                ""
+            };
+            format!("{}{}", sigil, &name[idx + 1..])
+        }
+        None => name.to_owned(),
+    }
+}
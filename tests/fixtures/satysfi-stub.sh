@@ -0,0 +1,26 @@
+#!/bin/sh
+# satysfi の代わりに決まった出力を返す。コンパイラの出力の解析を確かめるために用いる。
+# 出力ファイルのパス（-o の次の引数）を標準エラー出力に書き出す。
+
+while [ $# -gt 0 ]; do
+    if [ "$1" = "-o" ]; then
+        echo "  output: $2" >&2
+    fi
+    shift
+done
+
+cat <<'OUTPUT'
+ ---- ---- ---- ----
+  target file: 'doc.pdf'
+  parsing 'doc.saty' ...
+  type checking 'doc.saty' ...
+! [Type Error] at "doc.saty", line 3, characters 12-18:
+    this expression has type
+      int,
+    but is expected of type
+      string.
+! [Syntax Error at Parser] at "lib/util.satyh", line 5, character 4 to line 7, character 2:
+    unexpected token.
+! [Error] cannot find package 'missing'.
+OUTPUT
+exit 1